@@ -1,7 +1,8 @@
 //! Benchmarks for the RBN spot parser.
 
 use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
-use rbn_parser::parser::{looks_like_spot, parse_spot};
+use rbn_parser::parser::{looks_like_spot, parse_spot, parse_spot_ref};
+use rbn_parser::{Band, SpotFilter};
 
 /// Sample spot lines for benchmarking.
 const SAMPLE_SPOTS: &[&str] = &[
@@ -37,6 +38,60 @@ fn bench_parse_spot(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_owned_vs_borrowed(c: &mut Criterion) {
+    let mut group = c.benchmark_group("owned_vs_borrowed");
+
+    group.throughput(Throughput::Elements(SAMPLE_SPOTS.len() as u64));
+    group.bench_function("parse_owned", |b| {
+        b.iter(|| {
+            for line in SAMPLE_SPOTS {
+                let _ = parse_spot(black_box(line));
+            }
+        })
+    });
+
+    group.bench_function("parse_borrowed", |b| {
+        b.iter(|| {
+            for line in SAMPLE_SPOTS {
+                let _ = parse_spot_ref(black_box(line));
+            }
+        })
+    });
+
+    // Parse and filter, only allocating spots that match
+    let filter: SpotFilter = SpotFilter {
+        bands: Some(vec![Band::B40m]),
+        min_snr: Some(15),
+        ..Default::default()
+    };
+
+    group.bench_function("filter_owned", |b| {
+        b.iter(|| {
+            for line in SAMPLE_SPOTS {
+                if let Ok(spot) = parse_spot(black_box(line))
+                    && filter.matches(&spot)
+                {
+                    black_box(spot);
+                }
+            }
+        })
+    });
+
+    group.bench_function("filter_borrowed", |b| {
+        b.iter(|| {
+            for line in SAMPLE_SPOTS {
+                if let Ok(spot) = parse_spot_ref(black_box(line))
+                    && filter.matches_ref(&spot)
+                {
+                    black_box(spot.into_owned());
+                }
+            }
+        })
+    });
+
+    group.finish();
+}
+
 fn bench_looks_like_spot(c: &mut Criterion) {
     let mut group = c.benchmark_group("looks_like_spot");
 
@@ -84,6 +139,7 @@ fn bench_full_pipeline(c: &mut Criterion) {
 criterion_group!(
     benches,
     bench_parse_spot,
+    bench_owned_vs_borrowed,
     bench_looks_like_spot,
     bench_full_pipeline
 );
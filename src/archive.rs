@@ -0,0 +1,267 @@
+//! Import of the reversebeacon.net historical daily CSV archives.
+//!
+//! RBN publishes one zip file per day containing a single CSV with the
+//! columns:
+//!
+//! ```text
+//! callsign,de_pfx,de_cont,freq,band,dx,dx_pfx,dx_cont,mode,db,date,speed,tx_mode
+//! KM3T,K,NA,14025.0,20m,W1AW,K,NA,CQ,15,2024-01-01 00:00:12,22,CW
+//! ```
+//!
+//! Note that `mode` holds the spot type (CQ, BEACON, NCDXF B) and `tx_mode`
+//! the transmission mode. Records are converted to [`CwSpot`] values with
+//! a full `timestamp`, so they can be fed to [`crate::stats::SpotStats`]
+//! and [`crate::filter::SpotFilter`] just like live spots.
+
+use anyhow::{Context, Result, bail};
+use chrono::{NaiveDateTime, Timelike};
+use serde::Deserialize;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::frequency::Frequency;
+use crate::parser::{ParseError, ParseResult, parse_mode_token, parse_spot_type_token};
+use crate::spot::{CwSpot, Mode, Speed, SpotType};
+
+/// Columns that must be present in the CSV header.
+const REQUIRED_COLUMNS: &[&str] = &["callsign", "freq", "dx", "mode", "db", "date", "tx_mode"];
+
+/// Magic bytes at the start of a zip file.
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+/// One row of an archive CSV. Columns not listed here are ignored.
+#[derive(Debug, Deserialize)]
+struct ArchiveRecord {
+    callsign: String,
+    freq: Frequency,
+    dx: String,
+    mode: String,
+    #[serde(deserialize_with = "csv::invalid_option")]
+    db: Option<i32>,
+    date: String,
+    #[serde(default, deserialize_with = "csv::invalid_option")]
+    speed: Option<u16>,
+    tx_mode: String,
+}
+
+impl ArchiveRecord {
+    fn into_spot(self) -> ParseResult<CwSpot> {
+        let timestamp = NaiveDateTime::parse_from_str(&self.date, "%Y-%m-%d %H:%M:%S")
+            .map_err(|_| ParseError::InvalidFormat(format!("invalid date: {}", self.date)))?
+            .and_utc();
+
+        let mode = parse_mode_token(&self.tx_mode).unwrap_or(Mode::Unknown);
+        let speed = match (&mode, self.speed) {
+            (_, None | Some(0)) => None,
+            (Mode::Cw, Some(wpm)) => Some(Speed::Wpm(wpm)),
+            (Mode::Rtty | Mode::Psk31, Some(bps)) => Some(Speed::Bps(bps)),
+            _ => None,
+        };
+
+        Ok(CwSpot {
+            spotter: self.callsign,
+            frequency: self.freq,
+            dx_call: self.dx,
+            mode,
+            snr_db: self.db,
+            speed,
+            spot_type: parse_spot_type_token(&self.mode).unwrap_or(SpotType::Unknown),
+            // Live spots only carry minute precision
+            time: timestamp.time().with_second(0).unwrap_or_default(),
+            comment: None,
+            received_at: None,
+            timestamp: Some(timestamp),
+            dx_dxcc: None,
+            spotter_dxcc: None,
+            source: None,
+        })
+    }
+}
+
+/// Read an archive file from disk, zipped or plain CSV.
+///
+/// `on_record` is called for every data row, with a [`ParseError`] for rows
+/// that can't be converted. Returns the number of rows read. I/O errors,
+/// unreadable zip files and missing header columns abort the import.
+pub fn read_archive<F>(path: &Path, on_record: F) -> Result<u64>
+where
+    F: FnMut(ParseResult<CwSpot>),
+{
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    read_archive_from(BufReader::new(file), on_record)
+        .with_context(|| format!("Failed to import {}", path.display()))
+}
+
+/// Read an archive from any seekable reader, zipped or plain CSV.
+///
+/// Zip files are recognized by their magic bytes, not their extension.
+/// Every `.csv` entry in a zip file is read in turn.
+pub fn read_archive_from<R, F>(mut reader: R, mut on_record: F) -> Result<u64>
+where
+    R: Read + Seek,
+    F: FnMut(ParseResult<CwSpot>),
+{
+    let mut magic = [0u8; 4];
+    let n = reader.read(&mut magic)?;
+    reader.seek(SeekFrom::Start(0))?;
+
+    if &magic[..n] != ZIP_MAGIC {
+        return read_csv(reader, &mut on_record);
+    }
+
+    let mut zip = zip::ZipArchive::new(reader).context("Invalid zip file")?;
+    let mut rows = 0;
+    let mut found = false;
+    for i in 0..zip.len() {
+        let entry = zip.by_index(i)?;
+        if !entry.name().to_ascii_lowercase().ends_with(".csv") {
+            continue;
+        }
+        found = true;
+        let name = entry.name().to_string();
+        rows += read_csv(entry, &mut on_record).with_context(|| format!("In {}", name))?;
+    }
+
+    if !found {
+        bail!("No CSV file found in zip archive");
+    }
+    Ok(rows)
+}
+
+/// Read rows from a plain archive CSV.
+pub fn read_csv<R, F>(reader: R, on_record: &mut F) -> Result<u64>
+where
+    R: Read,
+    F: FnMut(ParseResult<CwSpot>),
+{
+    let mut csv = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+
+    let headers = csv.headers().context("Failed to read CSV header")?.clone();
+    for column in REQUIRED_COLUMNS {
+        if !headers.iter().any(|h| h == *column) {
+            bail!("Missing column '{}' in CSV header", column);
+        }
+    }
+
+    let mut rows = 0;
+    for result in csv.deserialize::<ArchiveRecord>() {
+        rows += 1;
+        match result {
+            Ok(record) => on_record(record.into_spot()),
+            Err(e) if e.is_io_error() => return Err(e).context("Read error"),
+            Err(e) => {
+                let line = e.position().map(|p| p.line()).unwrap_or(0);
+                on_record(Err(ParseError::InvalidFormat(format!(
+                    "line {}: {}",
+                    line, e
+                ))));
+            }
+        }
+    }
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::band::Band;
+    use std::io::{Cursor, Write};
+
+    const SAMPLE: &str = "\
+callsign,de_pfx,de_cont,freq,band,dx,dx_pfx,dx_cont,mode,db,date,speed,tx_mode
+KM3T,K,NA,14025.0,20m,W1AW,K,NA,CQ,15,2024-01-01 00:00:12,22,CW
+DK8NE,DL,EU,14080.5,20m,JA1XYZ,JA,AS,CQ,8,2024-01-01 00:01:40,45,RTTY
+W3OA,K,NA,7074.0,40m,K1ABC,K,NA,CQ,-12,2024-01-01 23:59:59,,FT8
+VE2WU,VE,NA,14100.0,20m,4U1UN,4U,NA,NCDXF B,21,2024-01-01 12:00:03,22,CW
+";
+
+    fn collect(data: &[u8]) -> (u64, Vec<ParseResult<CwSpot>>) {
+        let mut records = Vec::new();
+        let rows = read_archive_from(Cursor::new(data), |r| records.push(r)).unwrap();
+        (rows, records)
+    }
+
+    #[test]
+    fn test_read_plain_csv() {
+        let (rows, records) = collect(SAMPLE.as_bytes());
+        assert_eq!(rows, 4);
+
+        let spots: Vec<_> = records.into_iter().map(|r| r.unwrap()).collect();
+
+        let cw = &spots[0];
+        assert_eq!(cw.spotter, "KM3T");
+        assert_eq!(cw.dx_call, "W1AW");
+        assert_eq!(cw.frequency, Frequency::from_khz(14025.0));
+        assert_eq!(cw.mode, Mode::Cw);
+        assert_eq!(cw.spot_type, SpotType::Cq);
+        assert_eq!(cw.snr_db, Some(15));
+        assert_eq!(cw.speed, Some(Speed::Wpm(22)));
+        assert_eq!(cw.time.format("%H%M%S").to_string(), "000000");
+        assert_eq!(cw.timestamp, Some("2024-01-01T00:00:12Z".parse().unwrap()));
+        assert_eq!(cw.band(), Some(Band::B20m));
+
+        assert_eq!(spots[1].mode, Mode::Rtty);
+        assert_eq!(spots[1].speed, Some(Speed::Bps(45)));
+
+        assert_eq!(spots[2].mode, Mode::Ft8);
+        assert_eq!(spots[2].snr_db, Some(-12));
+        assert_eq!(spots[2].speed, None);
+
+        assert_eq!(spots[3].spot_type, SpotType::NcdxfBeacon);
+    }
+
+    #[test]
+    fn test_read_zip_archive() {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file("20240101.csv", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(SAMPLE.as_bytes()).unwrap();
+        let data = zip.finish().unwrap().into_inner();
+
+        let (rows, records) = collect(&data);
+        assert_eq!(rows, 4);
+        assert!(records.iter().all(|r| r.is_ok()));
+    }
+
+    #[test]
+    fn test_zip_without_csv_is_error() {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file("README.txt", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        let data = zip.finish().unwrap().into_inner();
+
+        assert!(read_archive_from(Cursor::new(data), |_| {}).is_err());
+    }
+
+    #[test]
+    fn test_bad_rows_are_reported() {
+        let data = "\
+callsign,de_pfx,de_cont,freq,band,dx,dx_pfx,dx_cont,mode,db,date,speed,tx_mode
+KM3T,K,NA,lots,20m,W1AW,K,NA,CQ,15,2024-01-01 00:00:12,22,CW
+KM3T,K,NA,14025.0,20m,W1AW,K,NA,CQ,15,yesterday,22,CW
+KM3T,K,NA,14025.0,20m,W1AW,K,NA,CQ,15,2024-01-01 00:00:12,22,CW
+";
+        let (rows, records) = collect(data.as_bytes());
+
+        assert_eq!(rows, 3);
+        assert!(
+            matches!(&records[0], Err(ParseError::InvalidFormat(m)) if m.starts_with("line 2"))
+        );
+        assert!(
+            matches!(&records[1], Err(ParseError::InvalidFormat(m)) if m.contains("yesterday"))
+        );
+        assert!(records[2].is_ok());
+    }
+
+    #[test]
+    fn test_missing_column_is_error() {
+        let data = "callsign,freq,dx\nKM3T,14025.0,W1AW\n";
+        let err = read_archive_from(Cursor::new(data.as_bytes()), |_| {}).unwrap_err();
+        assert!(err.to_string().contains("mode"));
+    }
+}
@@ -0,0 +1,122 @@
+//! Reconnect backoff with full jitter.
+//!
+//! Retrying a down server every few seconds for hours just adds load when
+//! it comes back, and every client retrying on the same schedule arrives
+//! at once. [`Backoff`] doubles the delay ceiling after each attempt up to
+//! a maximum, and picks the actual delay uniformly between zero and the
+//! ceiling ("full jitter"), so clients spread out.
+
+use std::time::Duration;
+
+/// Exponential reconnect backoff with an optional attempt budget.
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    initial: Duration,
+    max: Duration,
+    max_attempts: Option<u32>,
+    attempts: u32,
+}
+
+impl Backoff {
+    /// Create a backoff starting at `initial` and doubling up to `max`.
+    ///
+    /// With `max_attempts`, [`Backoff::next_delay`] gives up after that
+    /// many attempts without a [`Backoff::reset`].
+    pub fn new(initial: Duration, max: Duration, max_attempts: Option<u32>) -> Self {
+        Self {
+            initial,
+            max: max.max(initial),
+            max_attempts,
+            attempts: 0,
+        }
+    }
+
+    /// The delay before the next attempt, or None once the attempt budget
+    /// is spent.
+    pub fn next_delay(&mut self) -> Option<Duration> {
+        self.next_delay_with(fastrand::f64())
+    }
+
+    /// The delay before the next attempt, scaling the ceiling by `jitter`
+    /// (0.0 to 1.0).
+    fn next_delay_with(&mut self, jitter: f64) -> Option<Duration> {
+        if self.max_attempts.is_some_and(|max| self.attempts >= max) {
+            return None;
+        }
+        let ceiling = self.ceiling();
+        self.attempts += 1;
+        Some(ceiling.mul_f64(jitter.clamp(0.0, 1.0)))
+    }
+
+    /// The upper bound for the next delay: `initial * 2^attempts`, capped
+    /// at the maximum.
+    pub fn ceiling(&self) -> Duration {
+        let factor = 1u32.checked_shl(self.attempts).unwrap_or(u32::MAX);
+        self.initial.saturating_mul(factor).min(self.max)
+    }
+
+    /// Number of attempts since the last reset.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Start over from the initial delay, with a fresh attempt budget.
+    pub fn reset(&mut self) {
+        self.attempts = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ceiling_doubles_up_to_max() {
+        let mut backoff = Backoff::new(Duration::from_secs(5), Duration::from_secs(60), None);
+        let ceilings: Vec<_> = (0..6)
+            .map(|_| {
+                let ceiling = backoff.ceiling();
+                backoff.next_delay_with(1.0);
+                ceiling.as_secs()
+            })
+            .collect();
+
+        assert_eq!(ceilings, vec![5, 10, 20, 40, 60, 60]);
+        assert_eq!(backoff.attempts(), 6);
+    }
+
+    #[test]
+    fn test_full_jitter() {
+        let mut backoff = Backoff::new(Duration::from_secs(4), Duration::from_secs(60), None);
+        assert_eq!(backoff.next_delay_with(0.0), Some(Duration::ZERO));
+        assert_eq!(backoff.next_delay_with(0.5), Some(Duration::from_secs(4)));
+        assert_eq!(backoff.next_delay_with(1.0), Some(Duration::from_secs(16)));
+
+        for _ in 0..100 {
+            let ceiling = backoff.ceiling();
+            assert!(backoff.next_delay().unwrap() <= ceiling);
+        }
+    }
+
+    #[test]
+    fn test_attempt_budget_and_reset() {
+        let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(10), Some(2));
+        assert!(backoff.next_delay().is_some());
+        assert!(backoff.next_delay().is_some());
+        assert_eq!(backoff.next_delay(), None);
+        assert_eq!(backoff.attempts(), 2);
+
+        backoff.reset();
+        assert_eq!(backoff.ceiling(), Duration::from_secs(1));
+        assert!(backoff.next_delay().is_some());
+    }
+
+    #[test]
+    fn test_many_attempts_do_not_overflow() {
+        let mut backoff = Backoff::new(Duration::from_secs(5), Duration::from_secs(300), None);
+        for _ in 0..100 {
+            backoff.next_delay_with(1.0);
+        }
+        assert_eq!(backoff.ceiling(), Duration::from_secs(300));
+    }
+}
@@ -0,0 +1,584 @@
+//! Amateur radio bands and region-aware band plans.
+//!
+//! [`Band`] names an amateur band. A [`BandPlan`] maps frequencies to bands
+//! using the edges of one IARU region, optionally overridden from the
+//! `[band_plan]` config section. The plan installed with
+//! [`BandPlan::install`] is used by [`CwSpot::band`](crate::spot::CwSpot::band)
+//! everywhere; until one is installed, the Region 2 plan applies.
+//!
+//! The plan also classifies frequencies into sub-band [`Segment`]s (CW,
+//! digital, beacon, phone, contest-free) from a segment data file. The
+//! bundled `data/segments.toml` is used unless `band_plan.segment_file`
+//! points elsewhere.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::frequency::Frequency;
+
+/// The bundled segment data file.
+const DEFAULT_SEGMENTS: &str = include_str!("../data/segments.toml");
+
+/// An amateur radio band, named by its wavelength.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub enum Band {
+    #[serde(rename = "2200m")]
+    B2200m,
+    #[serde(rename = "630m")]
+    B630m,
+    #[serde(rename = "160m")]
+    B160m,
+    #[serde(rename = "80m")]
+    B80m,
+    #[serde(rename = "60m")]
+    B60m,
+    #[serde(rename = "40m")]
+    B40m,
+    #[serde(rename = "30m")]
+    B30m,
+    #[serde(rename = "20m")]
+    B20m,
+    #[serde(rename = "17m")]
+    B17m,
+    #[serde(rename = "15m")]
+    B15m,
+    #[serde(rename = "12m")]
+    B12m,
+    #[serde(rename = "10m")]
+    B10m,
+    #[serde(rename = "6m")]
+    B6m,
+    #[serde(rename = "4m")]
+    B4m,
+    #[serde(rename = "2m")]
+    B2m,
+    #[serde(rename = "70cm")]
+    B70cm,
+    #[serde(rename = "23cm")]
+    B23cm,
+}
+
+impl Band {
+    /// Every band, from longest to shortest wavelength.
+    pub const ALL: [Band; 17] = [
+        Band::B2200m,
+        Band::B630m,
+        Band::B160m,
+        Band::B80m,
+        Band::B60m,
+        Band::B40m,
+        Band::B30m,
+        Band::B20m,
+        Band::B17m,
+        Band::B15m,
+        Band::B12m,
+        Band::B10m,
+        Band::B6m,
+        Band::B4m,
+        Band::B2m,
+        Band::B70cm,
+        Band::B23cm,
+    ];
+
+    /// The band's name, e.g. "20m" or "70cm".
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Band::B2200m => "2200m",
+            Band::B630m => "630m",
+            Band::B160m => "160m",
+            Band::B80m => "80m",
+            Band::B60m => "60m",
+            Band::B40m => "40m",
+            Band::B30m => "30m",
+            Band::B20m => "20m",
+            Band::B17m => "17m",
+            Band::B15m => "15m",
+            Band::B12m => "12m",
+            Band::B10m => "10m",
+            Band::B6m => "6m",
+            Band::B4m => "4m",
+            Band::B2m => "2m",
+            Band::B70cm => "70cm",
+            Band::B23cm => "23cm",
+        }
+    }
+}
+
+impl fmt::Display for Band {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Band {
+    type Err = String;
+
+    /// Parse a band name, case-insensitively ("20m", "70CM").
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Band::ALL
+            .into_iter()
+            .find(|band| band.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown band '{}'", s))
+    }
+}
+
+impl<'de> Deserialize<'de> for Band {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// An IARU region, which determines the default band edges.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(try_from = "u8")]
+pub enum IaruRegion {
+    /// Europe, Africa, the Middle East and northern Asia.
+    Region1,
+    /// The Americas.
+    #[default]
+    Region2,
+    /// The rest of Asia and the Pacific.
+    Region3,
+}
+
+impl TryFrom<u8> for IaruRegion {
+    type Error = String;
+
+    fn try_from(region: u8) -> Result<Self, Self::Error> {
+        match region {
+            1 => Ok(IaruRegion::Region1),
+            2 => Ok(IaruRegion::Region2),
+            3 => Ok(IaruRegion::Region3),
+            _ => Err(format!("IARU region must be 1, 2 or 3, not {}", region)),
+        }
+    }
+}
+
+/// The edges of one band (both inclusive), given in kHz in config files.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct BandEdges {
+    /// The band these edges belong to.
+    pub band: Band,
+    /// Lower edge.
+    #[serde(rename = "start_khz")]
+    pub start: Frequency,
+    /// Upper edge.
+    #[serde(rename = "end_khz")]
+    pub end: Frequency,
+}
+
+impl BandEdges {
+    const fn new(band: Band, start_khz: f64, end_khz: f64) -> Self {
+        Self {
+            band,
+            start: Frequency::from_khz(start_khz),
+            end: Frequency::from_khz(end_khz),
+        }
+    }
+
+    /// Whether the frequency falls within these edges.
+    pub fn contains(&self, frequency: Frequency) -> bool {
+        frequency >= self.start && frequency <= self.end
+    }
+}
+
+/// A sub-band segment of the band plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Segment {
+    /// CW-only segment
+    Cw,
+    /// Narrow-band digital modes
+    Digital,
+    /// Beacons, including the NCDXF/IARU network
+    Beacon,
+    /// Phone and all modes
+    Phone,
+    /// WARC bands, where contests are not held
+    ContestFree,
+}
+
+impl Segment {
+    /// The segment's name, as used in filters and the data file.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Segment::Cw => "cw",
+            Segment::Digital => "digital",
+            Segment::Beacon => "beacon",
+            Segment::Phone => "phone",
+            Segment::ContestFree => "contest_free",
+        }
+    }
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One range of a segment data file, from `start_khz` up to but not
+/// including `end_khz`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SegmentRange {
+    /// The segment this range belongs to.
+    pub segment: Segment,
+    /// Lower edge (inclusive).
+    #[serde(rename = "start_khz")]
+    pub start: Frequency,
+    /// Upper edge (exclusive).
+    #[serde(rename = "end_khz")]
+    pub end: Frequency,
+    /// Regions the range applies to; all regions if empty.
+    #[serde(default)]
+    pub regions: Vec<IaruRegion>,
+}
+
+impl SegmentRange {
+    /// Whether the frequency falls within this range.
+    pub fn contains(&self, frequency: Frequency) -> bool {
+        frequency >= self.start && frequency < self.end
+    }
+
+    fn applies_to(&self, region: IaruRegion) -> bool {
+        self.regions.is_empty() || self.regions.contains(&region)
+    }
+}
+
+/// The layout of a segment data file.
+#[derive(Debug, Deserialize)]
+struct SegmentFile {
+    segments: Vec<SegmentRange>,
+}
+
+/// Parse a segment data file, keeping the ranges for one region.
+fn parse_segments(contents: &str, region: IaruRegion) -> Result<Vec<SegmentRange>> {
+    let file: SegmentFile = toml::from_str(contents)?;
+    for range in &file.segments {
+        if range.start >= range.end {
+            anyhow::bail!(
+                "Invalid {} segment: {} - {} kHz",
+                range.segment,
+                range.start,
+                range.end
+            );
+        }
+    }
+    Ok(file
+        .segments
+        .into_iter()
+        .filter(|r| r.applies_to(region))
+        .collect())
+}
+
+/// Bands with the same edges in every region.
+const COMMON_EDGES: &[BandEdges] = &[
+    BandEdges::new(Band::B2200m, 135.7, 137.8),
+    BandEdges::new(Band::B630m, 472.0, 479.0),
+    BandEdges::new(Band::B30m, 10100.0, 10150.0),
+    BandEdges::new(Band::B20m, 14000.0, 14350.0),
+    BandEdges::new(Band::B17m, 18068.0, 18168.0),
+    BandEdges::new(Band::B15m, 21000.0, 21450.0),
+    BandEdges::new(Band::B12m, 24890.0, 24990.0),
+    BandEdges::new(Band::B10m, 28000.0, 29700.0),
+    BandEdges::new(Band::B23cm, 1240000.0, 1300000.0),
+];
+
+const REGION1_EDGES: &[BandEdges] = &[
+    BandEdges::new(Band::B160m, 1810.0, 2000.0),
+    BandEdges::new(Band::B80m, 3500.0, 3800.0),
+    BandEdges::new(Band::B60m, 5351.5, 5366.5),
+    BandEdges::new(Band::B40m, 7000.0, 7200.0),
+    BandEdges::new(Band::B6m, 50000.0, 52000.0),
+    BandEdges::new(Band::B4m, 70000.0, 70500.0),
+    BandEdges::new(Band::B2m, 144000.0, 146000.0),
+    BandEdges::new(Band::B70cm, 430000.0, 440000.0),
+];
+
+// 60m covers the US channels as well as the WRC-15 allocation
+const REGION2_EDGES: &[BandEdges] = &[
+    BandEdges::new(Band::B160m, 1800.0, 2000.0),
+    BandEdges::new(Band::B80m, 3500.0, 4000.0),
+    BandEdges::new(Band::B60m, 5330.0, 5410.0),
+    BandEdges::new(Band::B40m, 7000.0, 7300.0),
+    BandEdges::new(Band::B6m, 50000.0, 54000.0),
+    BandEdges::new(Band::B2m, 144000.0, 148000.0),
+    BandEdges::new(Band::B70cm, 420000.0, 450000.0),
+];
+
+const REGION3_EDGES: &[BandEdges] = &[
+    BandEdges::new(Band::B160m, 1800.0, 2000.0),
+    BandEdges::new(Band::B80m, 3500.0, 3900.0),
+    BandEdges::new(Band::B60m, 5351.5, 5366.5),
+    BandEdges::new(Band::B40m, 7000.0, 7300.0),
+    BandEdges::new(Band::B6m, 50000.0, 54000.0),
+    BandEdges::new(Band::B2m, 144000.0, 148000.0),
+    BandEdges::new(Band::B70cm, 430000.0, 440000.0),
+];
+
+/// The plan installed by [`BandPlan::install`].
+static INSTALLED: OnceLock<BandPlan> = OnceLock::new();
+
+/// A table of band edges and sub-band segments used to classify
+/// frequencies.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BandPlan {
+    region: IaruRegion,
+    edges: Vec<BandEdges>,
+    segments: Vec<SegmentRange>,
+}
+
+impl BandPlan {
+    /// The standard plan for an IARU region.
+    pub fn for_region(region: IaruRegion) -> Self {
+        let regional = match region {
+            IaruRegion::Region1 => REGION1_EDGES,
+            IaruRegion::Region2 => REGION2_EDGES,
+            IaruRegion::Region3 => REGION3_EDGES,
+        };
+        let mut edges: Vec<_> = COMMON_EDGES.iter().chain(regional).copied().collect();
+        edges.sort_by_key(|e| e.start);
+        let segments =
+            parse_segments(DEFAULT_SEGMENTS, region).expect("bundled segment file is valid");
+        Self {
+            region,
+            edges,
+            segments,
+        }
+    }
+
+    /// Replace the segments with those of a data file in the bundled format.
+    pub fn with_segment_data(mut self, contents: &str) -> Result<Self> {
+        self.segments = parse_segments(contents, self.region)?;
+        Ok(self)
+    }
+
+    /// Replace the segments with those of a segment data file on disk.
+    pub fn with_segment_file(self, path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read segment file {}", path.display()))?;
+        self.with_segment_data(&contents)
+            .with_context(|| format!("Invalid segment file {}", path.display()))
+    }
+
+    /// Replace the edges of a band, or add a band the plan lacks.
+    pub fn with_edges(mut self, edges: BandEdges) -> Self {
+        self.edges.retain(|e| e.band != edges.band);
+        self.edges.push(edges);
+        self.edges.sort_by_key(|e| e.start);
+        self
+    }
+
+    /// Returns the band containing a frequency, if any.
+    pub fn band_for(&self, frequency: Frequency) -> Option<Band> {
+        self.edges
+            .iter()
+            .find(|e| e.contains(frequency))
+            .map(|e| e.band)
+    }
+
+    /// Returns every segment containing a frequency, in order.
+    pub fn segments_for(&self, frequency: Frequency) -> Vec<Segment> {
+        let mut segments: Vec<_> = self
+            .segments
+            .iter()
+            .filter(|r| r.contains(frequency))
+            .map(|r| r.segment)
+            .collect();
+        segments.sort();
+        segments.dedup();
+        segments
+    }
+
+    /// Returns the edges of a band, if it is in the plan.
+    pub fn edges(&self, band: Band) -> Option<&BandEdges> {
+        self.edges.iter().find(|e| e.band == band)
+    }
+
+    /// Make this the plan used for every spot in the process.
+    ///
+    /// Must be called before any band lookup (such as
+    /// [`crate::spot::band_for_frequency`] or a band filter), since the first
+    /// lookup installs the Region 2 plan. Only the first call has any effect;
+    /// returns `false` if a plan was already installed.
+    pub fn install(self) -> bool {
+        INSTALLED.set(self).is_ok()
+    }
+
+    /// The installed plan, or the Region 2 plan if none was installed yet
+    /// (which then stays in place).
+    pub fn current() -> &'static BandPlan {
+        INSTALLED.get_or_init(BandPlan::default)
+    }
+}
+
+impl Default for BandPlan {
+    fn default() -> Self {
+        Self::for_region(IaruRegion::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn khz(khz: f64) -> Frequency {
+        Frequency::from_khz(khz)
+    }
+
+    #[test]
+    fn test_band_names() {
+        for band in Band::ALL {
+            assert_eq!(band.as_str().parse::<Band>(), Ok(band));
+            assert_eq!(band.to_string(), band.as_str());
+        }
+        assert_eq!("20M".parse::<Band>(), Ok(Band::B20m));
+        assert_eq!(" 70cm ".parse::<Band>(), Ok(Band::B70cm));
+        assert!("21m".parse::<Band>().is_err());
+        assert!("".parse::<Band>().is_err());
+    }
+
+    #[test]
+    fn test_band_serde() {
+        assert_eq!(serde_json::to_string(&Band::B70cm).unwrap(), "\"70cm\"");
+        assert_eq!(
+            serde_json::from_str::<Band>("\"160M\"").unwrap(),
+            Band::B160m
+        );
+        assert!(serde_json::from_str::<Band>("\"11m\"").is_err());
+    }
+
+    #[test]
+    fn test_region2_plan() {
+        let plan = BandPlan::default();
+        assert_eq!(plan.band_for(khz(1800.0)), Some(Band::B160m));
+        assert_eq!(plan.band_for(khz(3950.0)), Some(Band::B80m));
+        assert_eq!(plan.band_for(khz(5332.0)), Some(Band::B60m));
+        assert_eq!(plan.band_for(khz(7000.0)), Some(Band::B40m));
+        assert_eq!(plan.band_for(khz(7300.0)), Some(Band::B40m));
+        assert_eq!(plan.band_for(khz(6999.0)), None);
+        assert_eq!(plan.band_for(khz(70200.0)), None);
+        assert_eq!(plan.band_for(khz(432100.0)), Some(Band::B70cm));
+        assert_eq!(plan.band_for(khz(1296200.0)), Some(Band::B23cm));
+    }
+
+    #[test]
+    fn test_fractional_frequencies_at_edges() {
+        let plan = BandPlan::default();
+        assert_eq!(plan.band_for(khz(1799.95)), None);
+        assert_eq!(plan.band_for(khz(1800.05)), Some(Band::B160m));
+        assert_eq!(plan.band_for(khz(7299.99)), Some(Band::B40m));
+        assert_eq!(plan.band_for(khz(7300.001)), None);
+        assert_eq!(plan.band_for(khz(135.7)), Some(Band::B2200m));
+    }
+
+    #[test]
+    fn test_region_edges_differ() {
+        let region1 = BandPlan::for_region(IaruRegion::Region1);
+        assert_eq!(region1.band_for(khz(1805.0)), None);
+        assert_eq!(region1.band_for(khz(3950.0)), None);
+        assert_eq!(region1.band_for(khz(5332.0)), None);
+        assert_eq!(region1.band_for(khz(5354.0)), Some(Band::B60m));
+        assert_eq!(region1.band_for(khz(70200.0)), Some(Band::B4m));
+
+        let region3 = BandPlan::for_region(IaruRegion::Region3);
+        assert_eq!(region3.band_for(khz(3850.0)), Some(Band::B80m));
+        assert_eq!(region3.band_for(khz(3950.0)), None);
+    }
+
+    #[test]
+    fn test_override_edges() {
+        let plan = BandPlan::for_region(IaruRegion::Region1)
+            .with_edges(BandEdges::new(Band::B40m, 7000.0, 7300.0))
+            .with_edges(BandEdges::new(Band::B4m, 70000.0, 71000.0));
+
+        assert_eq!(plan.band_for(khz(7250.0)), Some(Band::B40m));
+        assert_eq!(plan.band_for(khz(70750.0)), Some(Band::B4m));
+        assert_eq!(plan.edges(Band::B40m).unwrap().end, khz(7300.0));
+    }
+
+    #[test]
+    fn test_default_segments() {
+        let plan = BandPlan::default();
+        assert_eq!(plan.segments_for(khz(14025.0)), vec![Segment::Cw]);
+        assert_eq!(plan.segments_for(khz(14074.0)), vec![Segment::Digital]);
+        assert_eq!(plan.segments_for(khz(14100.0)), vec![Segment::Beacon]);
+        assert_eq!(plan.segments_for(khz(14200.0)), vec![Segment::Phone]);
+        assert_eq!(
+            plan.segments_for(khz(10110.0)),
+            vec![Segment::Cw, Segment::ContestFree]
+        );
+        assert_eq!(
+            plan.segments_for(khz(18110.0)),
+            vec![Segment::Beacon, Segment::ContestFree]
+        );
+        // Shared edges belong to the upper segment only
+        assert_eq!(plan.segments_for(khz(14070.0)), vec![Segment::Digital]);
+        assert!(plan.segments_for(khz(5000.0)).is_empty());
+    }
+
+    #[test]
+    fn test_segments_by_region() {
+        assert_eq!(
+            BandPlan::for_region(IaruRegion::Region2).segments_for(khz(1845.0)),
+            vec![Segment::Digital]
+        );
+        assert_eq!(
+            BandPlan::for_region(IaruRegion::Region1).segments_for(khz(1845.0)),
+            vec![Segment::Phone]
+        );
+        assert_eq!(
+            BandPlan::for_region(IaruRegion::Region1).segments_for(khz(3575.0)),
+            vec![Segment::Cw]
+        );
+    }
+
+    #[test]
+    fn test_custom_segment_data() {
+        let data = r#"
+            [[segments]]
+            segment = "cw"
+            start_khz = 14000
+            end_khz = 14060
+
+            [[segments]]
+            segment = "beacon"
+            start_khz = 14000
+            end_khz = 14001
+            regions = [1]
+        "#;
+        let plan = BandPlan::default().with_segment_data(data).unwrap();
+        assert_eq!(plan.segments_for(khz(14000.5)), vec![Segment::Cw]);
+        assert!(plan.segments_for(khz(14100.0)).is_empty());
+
+        let plan = BandPlan::for_region(IaruRegion::Region1)
+            .with_segment_data(data)
+            .unwrap();
+        assert_eq!(
+            plan.segments_for(khz(14000.5)),
+            vec![Segment::Cw, Segment::Beacon]
+        );
+
+        let bad = "[[segments]]\nsegment = \"cw\"\nstart_khz = 14060\nend_khz = 14000\n";
+        assert!(BandPlan::default().with_segment_data(bad).is_err());
+        let unknown = "[[segments]]\nsegment = \"ssb\"\nstart_khz = 1\nend_khz = 2\n";
+        assert!(BandPlan::default().with_segment_data(unknown).is_err());
+    }
+
+    #[test]
+    fn test_region_from_number() {
+        assert_eq!(IaruRegion::try_from(1), Ok(IaruRegion::Region1));
+        assert_eq!(IaruRegion::try_from(3), Ok(IaruRegion::Region3));
+        assert!(IaruRegion::try_from(4).is_err());
+    }
+}
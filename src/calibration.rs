@@ -0,0 +1,341 @@
+//! Per-skimmer frequency offset estimation.
+//!
+//! Skimmers report the same signal slightly apart (e.g. 7018.2 vs 7018.3 kHz
+//! for one CQ) because each receiver's reference is a little off.
+//! [`FrequencyCalibrator`] groups spots of the same DX call, band and mode
+//! heard within a short window, takes the median of the other skimmers'
+//! corrected frequencies as the consensus, and keeps a running average of
+//! each skimmer's deviation from it.
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::band::Band;
+use crate::config::CalibrationConfig;
+use crate::frequency::Frequency;
+use crate::spot::{CwSpot, Mode};
+
+/// Estimated frequency offset of one skimmer.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpotterOffset {
+    /// Spotter callsign as reported, e.g. "KM3T-2-#".
+    pub spotter: String,
+    /// How far above the consensus the skimmer reads, in kHz.
+    pub offset_khz: f64,
+    /// Number of consensus comparisons the estimate is based on.
+    pub samples: u64,
+}
+
+/// Running offset estimate for one skimmer.
+#[derive(Debug, Clone, Copy, Default)]
+struct OffsetEstimate {
+    offset_khz: f64,
+    samples: u64,
+}
+
+impl OffsetEstimate {
+    /// Fold in one deviation from the consensus.
+    ///
+    /// This is a plain mean until `smoothing` samples have been seen, and
+    /// an exponential moving average over roughly that many samples after,
+    /// so the estimate follows slow drift.
+    fn update(&mut self, deviation_khz: f64, smoothing: u64) {
+        self.samples += 1;
+        let weight = 1.0 / self.samples.min(smoothing.max(1)) as f64;
+        self.offset_khz += (deviation_khz - self.offset_khz) * weight;
+    }
+}
+
+/// One skimmer's report of a signal.
+#[derive(Debug, Clone)]
+struct Observation {
+    spotter: String,
+    frequency_khz: f64,
+    at: DateTime<Utc>,
+}
+
+/// Spots of the same DX call on the same band and mode.
+type SignalKey = (String, Band, Mode);
+
+/// Recent observations, grouped by signal.
+#[derive(Debug, Default)]
+struct RecentSignals {
+    by_signal: HashMap<SignalKey, Vec<Observation>>,
+    /// Spot time of the last sweep for stale signals.
+    last_sweep: Option<DateTime<Utc>>,
+}
+
+impl RecentSignals {
+    /// Drop observations older than `window` before `now`, at most once
+    /// per window.
+    fn sweep(&mut self, now: DateTime<Utc>, window: TimeDelta) {
+        if self.last_sweep.is_some_and(|last| now - last < window) {
+            return;
+        }
+        self.by_signal.retain(|_, observations| {
+            observations.retain(|o| now - o.at <= window);
+            !observations.is_empty()
+        });
+        self.last_sweep = Some(now);
+    }
+}
+
+/// Estimates each skimmer's systematic frequency offset from the consensus
+/// of other skimmers hearing the same signal.
+///
+/// Only skimmer spots (`-#`) with a full timestamp and a known band are
+/// used; human cluster spots are typed in by hand and would skew the
+/// consensus.
+#[derive(Debug)]
+pub struct FrequencyCalibrator {
+    window: TimeDelta,
+    min_spotters: usize,
+    max_deviation_khz: f64,
+    min_samples: u64,
+    smoothing_samples: u64,
+    recent: RwLock<RecentSignals>,
+    estimates: RwLock<HashMap<String, OffsetEstimate>>,
+}
+
+impl FrequencyCalibrator {
+    /// Create a calibrator with the given settings.
+    pub fn new(config: &CalibrationConfig) -> Self {
+        Self {
+            window: TimeDelta::seconds(config.window_secs as i64),
+            min_spotters: config.min_spotters,
+            max_deviation_khz: config.max_deviation_khz,
+            min_samples: config.min_samples,
+            smoothing_samples: config.smoothing_samples,
+            recent: RwLock::new(RecentSignals::default()),
+            estimates: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Compare a spot against recent spots of the same signal, updating
+    /// the spotter's offset estimate when there is a consensus.
+    pub fn observe(&self, spot: &CwSpot) {
+        if !spot.spotter_id().skimmer {
+            return;
+        }
+        let (Some(at), Some(band)) = (spot.timestamp, spot.band()) else {
+            return;
+        };
+
+        let consensus = {
+            let Ok(mut recent) = self.recent.write() else {
+                return;
+            };
+            recent.sweep(at, self.window);
+
+            let key = (spot.dx_call.to_ascii_uppercase(), band, spot.mode.clone());
+            let observations = recent.by_signal.entry(key).or_default();
+            observations.retain(|o| (at - o.at).abs() <= self.window && o.spotter != spot.spotter);
+
+            let mut others: Vec<f64> = observations
+                .iter()
+                .map(|o| o.frequency_khz - self.applied_offset(&o.spotter))
+                .collect();
+            observations.push(Observation {
+                spotter: spot.spotter.clone(),
+                frequency_khz: spot.frequency.khz(),
+                at,
+            });
+
+            if others.len() < self.min_spotters.max(1) {
+                return;
+            }
+            median(&mut others)
+        };
+
+        let deviation = spot.frequency.khz() - consensus;
+        if deviation.abs() > self.max_deviation_khz {
+            // Most likely a different signal, or a bad decode
+            return;
+        }
+        if let Ok(mut estimates) = self.estimates.write() {
+            estimates
+                .entry(spot.spotter.clone())
+                .or_default()
+                .update(deviation, self.smoothing_samples);
+        }
+    }
+
+    /// The offset applied to a spotter's frequencies: its estimate once it
+    /// has enough samples, zero before.
+    fn applied_offset(&self, spotter: &str) -> f64 {
+        self.offset(spotter).map_or(0.0, |o| o.offset_khz)
+    }
+
+    /// Get a spotter's offset, once it is based on at least `min_samples`
+    /// comparisons.
+    pub fn offset(&self, spotter: &str) -> Option<SpotterOffset> {
+        let estimates = self.estimates.read().ok()?;
+        let estimate = estimates.get(spotter)?;
+        (estimate.samples >= self.min_samples).then(|| SpotterOffset {
+            spotter: spotter.to_string(),
+            offset_khz: estimate.offset_khz,
+            samples: estimate.samples,
+        })
+    }
+
+    /// Get the offsets of all calibrated spotters, sorted by callsign.
+    pub fn offsets(&self) -> Vec<SpotterOffset> {
+        let mut offsets: Vec<_> = self
+            .estimates
+            .read()
+            .map(|m| {
+                m.iter()
+                    .filter(|(_, e)| e.samples >= self.min_samples)
+                    .map(|(spotter, e)| SpotterOffset {
+                        spotter: spotter.clone(),
+                        offset_khz: e.offset_khz,
+                        samples: e.samples,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        offsets.sort_by(|a, b| a.spotter.cmp(&b.spotter));
+        offsets
+    }
+
+    /// The spot's frequency with its spotter's offset removed, or None if
+    /// the spotter isn't calibrated yet.
+    pub fn corrected_frequency(&self, spot: &CwSpot) -> Option<Frequency> {
+        let offset = self.offset(&spot.spotter)?;
+        Some(Frequency::from_khz(
+            spot.frequency.khz() - offset.offset_khz,
+        ))
+    }
+}
+
+/// Median of a non-empty slice, reordering it.
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_spot;
+
+    fn calibrator() -> FrequencyCalibrator {
+        FrequencyCalibrator::new(&CalibrationConfig {
+            min_samples: 2,
+            ..Default::default()
+        })
+    }
+
+    fn observe(calibrator: &FrequencyCalibrator, spotter: &str, freq: f64, dx: &str, at: &str) {
+        let line = format!(
+            "DX de {}: {:>10.1}  {:<13}  CW    19 dB  18 WPM  CQ      {}Z",
+            spotter, freq, dx, at
+        );
+        let received = format!("2026-01-09T{}:{}:20Z", &at[..2], &at[2..]);
+        let spot = parse_spot(&line)
+            .unwrap()
+            .with_received_at(received.parse().unwrap());
+        calibrator.observe(&spot);
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(&mut [3.0]), 3.0);
+        assert_eq!(median(&mut [3.0, 1.0, 2.0]), 2.0);
+        assert_eq!(median(&mut [4.0, 1.0, 3.0, 2.0]), 2.5);
+    }
+
+    #[test]
+    fn test_offset_from_consensus() {
+        let calibrator = calibrator();
+        for (dx, at) in [("RW1M", "2259"), ("K1ABC", "2300"), ("DL1XX", "2301")] {
+            observe(&calibrator, "EA5WU-#", 7018.2, dx, at);
+            observe(&calibrator, "KM3T-#", 7018.2, dx, at);
+            observe(&calibrator, "W3OA-#", 7018.3, dx, at);
+        }
+
+        let offset = calibrator
+            .offset("W3OA-#")
+            .expect("W3OA should be calibrated");
+        assert!((offset.offset_khz - 0.1).abs() < 1e-6, "{:?}", offset);
+        assert_eq!(offset.samples, 3);
+
+        // Not enough other skimmers had heard the signal when EA5WU spotted it
+        assert!(calibrator.offset("EA5WU-#").is_none());
+        // KM3T only ever had one other skimmer to compare against
+        assert!(calibrator.offset("KM3T-#").is_none());
+    }
+
+    #[test]
+    fn test_ignores_distant_and_stale_spots() {
+        let calibrator = calibrator();
+        for at in ["2259", "2300"] {
+            observe(&calibrator, "EA5WU-#", 7018.2, "RW1M", at);
+            observe(&calibrator, "KM3T-#", 7018.2, "RW1M", at);
+        }
+        // Too far off the consensus to be the same signal
+        observe(&calibrator, "W3OA-#", 7021.0, "RW1M", "2300");
+        // Five minutes later, the earlier spots are out of the window
+        observe(&calibrator, "K3LR-#", 7018.3, "RW1M", "2305");
+        observe(&calibrator, "K3LR-#", 7018.3, "RW1M", "2306");
+
+        assert!(calibrator.offsets().is_empty());
+    }
+
+    #[test]
+    fn test_corrected_frequency() {
+        let calibrator = calibrator();
+        for (dx, at) in [("RW1M", "2259"), ("K1ABC", "2300")] {
+            observe(&calibrator, "EA5WU-#", 14025.0, dx, at);
+            observe(&calibrator, "KM3T-#", 14025.0, dx, at);
+            observe(&calibrator, "W3OA-#", 14024.9, dx, at);
+        }
+
+        let spot = parse_spot(
+            "DX de W3OA-#:    14030.0  W1AW           CW    19 dB  18 WPM  CQ      2301Z",
+        )
+        .unwrap();
+        assert_eq!(
+            calibrator.corrected_frequency(&spot),
+            Some(Frequency::from_khz(14030.1))
+        );
+
+        let offsets = calibrator.offsets();
+        assert_eq!(offsets.len(), 1);
+        assert_eq!(offsets[0].spotter, "W3OA-#");
+    }
+
+    #[test]
+    fn test_skips_human_spots() {
+        let calibrator = FrequencyCalibrator::new(&CalibrationConfig {
+            min_spotters: 1,
+            min_samples: 1,
+            ..Default::default()
+        });
+        observe(&calibrator, "EA5WU-#", 7018.2, "RW1M", "2259");
+        observe(&calibrator, "K1ABC", 7018.5, "RW1M", "2259");
+
+        assert!(calibrator.offset("K1ABC").is_none());
+    }
+
+    #[test]
+    fn test_estimate_follows_drift() {
+        let mut estimate = OffsetEstimate::default();
+        for _ in 0..4 {
+            estimate.update(0.1, 4);
+        }
+        assert!((estimate.offset_khz - 0.1).abs() < 1e-9);
+
+        for _ in 0..40 {
+            estimate.update(0.2, 4);
+        }
+        assert!((estimate.offset_khz - 0.2).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,319 @@
+//! Raw session capture and timed replay.
+//!
+//! A capture file records every line received from the servers, one per
+//! line, as receive time (RFC 3339, microseconds), source name and the line
+//! itself, separated by tabs (`\t` below):
+//!
+//! ```text
+//! 2026-01-08T22:59:20.123456Z\trbn\tDX de EA5WU-#:    7018.3  RW1M  ...
+//! ```
+//!
+//! [`replay`] reads a capture back as a stream of [`SourceEvent`]s, the same
+//! stream [`crate::client::connect_all`] produces, so filters, storage and
+//! statistics can be exercised offline against real traffic.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, LineWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+use crate::client::{RbnEvent, SourceEvent};
+
+/// Writes received lines to a capture file.
+pub struct CaptureWriter<W: Write> {
+    writer: W,
+}
+
+impl CaptureWriter<LineWriter<File>> {
+    /// Open a capture file for appending, creating it if needed.
+    ///
+    /// Every line is flushed as it is written, so the capture survives the
+    /// process being killed.
+    pub fn create(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open capture file {}", path.display()))?;
+        Ok(Self::new(LineWriter::new(file)))
+    }
+}
+
+impl<W: Write> CaptureWriter<W> {
+    /// Write captured lines to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Record one line received from `source` at `received_at`.
+    pub fn write_line(
+        &mut self,
+        received_at: DateTime<Utc>,
+        source: &str,
+        line: &str,
+    ) -> io::Result<()> {
+        writeln!(
+            self.writer,
+            "{}\t{}\t{}",
+            received_at.to_rfc3339_opts(SecondsFormat::Micros, true),
+            source,
+            line
+        )
+    }
+
+    /// Unwrap the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// One line of a capture file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedLine {
+    /// When the line was received.
+    pub received_at: DateTime<Utc>,
+
+    /// Name of the source it was received from.
+    pub source: String,
+
+    /// The line as received.
+    pub line: String,
+}
+
+impl FromStr for CapturedLine {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.splitn(3, '\t');
+        let (Some(time), Some(source), Some(line)) = (fields.next(), fields.next(), fields.next())
+        else {
+            return Err("expected time, source and line separated by tabs".to_string());
+        };
+        let received_at = DateTime::parse_from_rfc3339(time)
+            .map_err(|e| format!("invalid time '{}': {}", time, e))?
+            .with_timezone(&Utc);
+        Ok(Self {
+            received_at,
+            source: source.to_string(),
+            line: line.to_string(),
+        })
+    }
+}
+
+/// How fast to replay a capture.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplaySpeed {
+    /// Keep the original gaps between lines, divided by this factor
+    /// (1.0 = real time).
+    Scaled(f64),
+
+    /// Send lines as fast as the receiver takes them.
+    Max,
+}
+
+impl FromStr for ReplaySpeed {
+    type Err = String;
+
+    /// Parse "max", or a factor such as "1x", "10x" or "0.5".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("max") {
+            return Ok(Self::Max);
+        }
+        let factor = s.strip_suffix(['x', 'X']).unwrap_or(s);
+        match factor.parse::<f64>() {
+            Ok(factor) if factor.is_finite() && factor > 0.0 => Ok(Self::Scaled(factor)),
+            _ => Err(format!(
+                "invalid replay speed '{}': expected e.g. 1x, 10x or max",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ReplaySpeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Scaled(factor) => write!(f, "{}x", factor),
+            Self::Max => write!(f, "max speed"),
+        }
+    }
+}
+
+/// Replay a capture file as a stream of line events.
+///
+/// Each event carries its source name and original receive time. The
+/// stream ends at the end of the file.
+pub async fn replay(path: &Path, speed: ReplaySpeed) -> Result<mpsc::Receiver<SourceEvent>> {
+    let file = tokio::fs::File::open(path)
+        .await
+        .with_context(|| format!("Failed to open capture file {}", path.display()))?;
+    Ok(replay_from(tokio::io::BufReader::new(file), speed))
+}
+
+/// Replay a capture from any reader. Lines that can't be parsed are
+/// skipped with a warning.
+pub fn replay_from<R>(reader: R, speed: ReplaySpeed) -> mpsc::Receiver<SourceEvent>
+where
+    R: AsyncBufRead + Unpin + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel(1000);
+
+    tokio::spawn(async move {
+        let mut lines = reader.lines();
+        let mut sources: HashMap<String, Arc<str>> = HashMap::new();
+        let mut start: Option<(DateTime<Utc>, Instant)> = None;
+        let mut number = 0;
+        let mut replayed = 0;
+
+        loop {
+            let text = match lines.next_line().await {
+                Ok(Some(text)) => text,
+                Ok(None) => break,
+                Err(e) => {
+                    warn!("Read error in capture: {}", e);
+                    break;
+                }
+            };
+            number += 1;
+            if text.is_empty() {
+                continue;
+            }
+            let captured = match text.parse::<CapturedLine>() {
+                Ok(captured) => captured,
+                Err(e) => {
+                    warn!("Skipping capture line {}: {}", number, e);
+                    continue;
+                }
+            };
+
+            if let ReplaySpeed::Scaled(factor) = speed {
+                let (first, started) = *start.get_or_insert((captured.received_at, Instant::now()));
+                let offset = (captured.received_at - first).to_std().unwrap_or_default();
+                tokio::time::sleep_until(started + offset.div_f64(factor)).await;
+            }
+
+            let source = sources
+                .entry(captured.source)
+                .or_insert_with_key(|name| name.as_str().into());
+            let event = SourceEvent {
+                source: Arc::clone(source),
+                event: RbnEvent::Line {
+                    line: captured.line,
+                    received_at: captured.received_at,
+                },
+            };
+            if tx.send(event).await.is_err() {
+                return;
+            }
+            replayed += 1;
+        }
+
+        info!("Replayed {} lines", replayed);
+    });
+
+    rx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const SPOT: &str =
+        "DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z";
+
+    fn time(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn capture() -> Vec<u8> {
+        let mut writer = CaptureWriter::new(Vec::new());
+        writer
+            .write_line(time("2026-01-08T22:59:20.123456Z"), "rbn-cw", SPOT)
+            .unwrap();
+        writer
+            .write_line(
+                time("2026-01-08T22:59:21.123456Z"),
+                "skimmer",
+                "W6JSV de NODE >",
+            )
+            .unwrap();
+        writer.into_inner()
+    }
+
+    async fn collect(data: Vec<u8>, speed: ReplaySpeed) -> Vec<SourceEvent> {
+        let mut rx = replay_from(std::io::Cursor::new(data), speed);
+        let mut events = Vec::new();
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+        events
+    }
+
+    #[test]
+    fn test_capture_format() {
+        let capture = String::from_utf8(capture()).unwrap();
+        let first = capture.lines().next().unwrap();
+        assert_eq!(
+            first,
+            format!("2026-01-08T22:59:20.123456Z\trbn-cw\t{}", SPOT)
+        );
+
+        let parsed: CapturedLine = first.parse().unwrap();
+        assert_eq!(parsed.received_at, time("2026-01-08T22:59:20.123456Z"));
+        assert_eq!(parsed.source, "rbn-cw");
+        assert_eq!(parsed.line, SPOT);
+
+        assert!("2026-01-08T22:59:20Z\trbn".parse::<CapturedLine>().is_err());
+        assert!("yesterday\trbn\tline".parse::<CapturedLine>().is_err());
+    }
+
+    #[test]
+    fn test_parse_replay_speed() {
+        assert_eq!("max".parse(), Ok(ReplaySpeed::Max));
+        assert_eq!("1x".parse(), Ok(ReplaySpeed::Scaled(1.0)));
+        assert_eq!("10X".parse(), Ok(ReplaySpeed::Scaled(10.0)));
+        assert_eq!("0.5".parse(), Ok(ReplaySpeed::Scaled(0.5)));
+        assert!("0x".parse::<ReplaySpeed>().is_err());
+        assert!("fast".parse::<ReplaySpeed>().is_err());
+        assert_eq!(ReplaySpeed::Scaled(10.0).to_string(), "10x");
+    }
+
+    #[tokio::test]
+    async fn test_replay_events() {
+        let mut data = b"not a capture line\n\n".to_vec();
+        data.extend(capture());
+        let events = collect(data, ReplaySpeed::Max).await;
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(&*events[0].source, "rbn-cw");
+        assert!(matches!(
+            &events[0].event,
+            RbnEvent::Line { line, received_at }
+                if line == SPOT && *received_at == time("2026-01-08T22:59:20.123456Z")
+        ));
+        assert_eq!(&*events[1].source, "skimmer");
+    }
+
+    #[tokio::test]
+    async fn test_replay_keeps_scaled_timing() {
+        // One second apart, replayed at 10x
+        let started = std::time::Instant::now();
+        let events = collect(capture(), ReplaySpeed::Scaled(10.0)).await;
+        let elapsed = started.elapsed();
+
+        assert_eq!(events.len(), 2);
+        assert!(elapsed >= Duration::from_millis(100), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_millis(900), "{:?}", elapsed);
+    }
+}
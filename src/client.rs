@@ -1,15 +1,25 @@
 //! Telnet client for connecting to the Reverse Beacon Network.
 //!
 //! This module handles the TCP connection to the RBN telnet server,
-//! including login and streaming of spot data.
+//! including login and streaming of spot data. Telnet framing is handled
+//! by [`crate::telnet`].
 
 use anyhow::{Context, Result};
-use std::time::Duration;
-use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::TcpStream;
 use tokio::sync::mpsc;
 use tokio::time::timeout;
-use tracing::{debug, error, info, warn};
+use tokio_rustls::TlsConnector;
+use tracing::{Instrument, debug, error, info, info_span, warn};
+
+use crate::backoff::Backoff;
+use crate::failover::{Endpoint, Endpoints};
+use crate::telnet::{DEFAULT_MAX_LINE_LENGTH, TelnetStream};
+use crate::tls::TlsConfig;
 
 /// Default RBN telnet server for CW/RTTY spots.
 pub const RBN_HOST: &str = "telnet.reversebeacon.net";
@@ -20,15 +30,88 @@ pub const RBN_PORT_CW: u16 = 7000;
 /// Default port for FT8 spots.
 pub const RBN_PORT_FT8: u16 = 7001;
 
+/// Name of the source when only one server is configured.
+pub const DEFAULT_SOURCE_NAME: &str = "rbn";
+
+/// One step of a login script: wait for a prompt, then send a line.
+///
+/// Either part may be left out: a step without `expect` sends right away,
+/// and a step without anything to send just waits.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct LoginStep {
+    /// Text to wait for, matched case-insensitively anywhere in a line or
+    /// in an unterminated prompt.
+    pub expect: Option<String>,
+
+    /// Line to send.
+    pub send: Option<String>,
+
+    /// Environment variable holding a password to send, instead of `send`.
+    pub send_env: Option<String>,
+
+    /// Seconds to wait for `expect` (default: the connect timeout).
+    pub timeout_secs: Option<u64>,
+}
+
+impl LoginStep {
+    /// Validate the step.
+    ///
+    /// Returns an error if the step does nothing, or sends both a line
+    /// and a password.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.send.is_some() && self.send_env.is_some() {
+            return Err("Cannot specify both 'send' and 'send_env' in one step".to_string());
+        }
+        if self.expect.is_none() && self.send.is_none() && self.send_env.is_none() {
+            return Err("Step needs 'expect', 'send' or 'send_env'".to_string());
+        }
+        if self.expect.as_deref().is_some_and(|e| e.trim().is_empty()) {
+            return Err("'expect' must not be empty".to_string());
+        }
+        Ok(())
+    }
+
+    /// The line this step sends, if any, looking up `send_env` with `env`.
+    ///
+    /// Returns an error if the password variable is not set.
+    pub fn resolve(&self, env: &dyn Fn(&str) -> Option<String>) -> Result<Option<String>, String> {
+        match (&self.send, &self.send_env) {
+            (_, Some(var)) => env(var)
+                .map(Some)
+                .ok_or_else(|| format!("environment variable {} is not set", var)),
+            (Some(line), None) => Ok(Some(line.clone())),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
 /// Configuration for the RBN client.
 #[derive(Debug, Clone)]
 pub struct RbnClientConfig {
+    /// Name of this source, used to tag its events and spots.
+    pub name: String,
+
     /// Hostname of the RBN server.
     pub host: String,
 
     /// Port number.
     pub port: u16,
 
+    /// Endpoints to fail over to, in order of preference, when `host` and
+    /// `port` refuse connections or logins.
+    pub fallbacks: Vec<Endpoint>,
+
+    /// While on a fallback, switch back to a more preferred endpoint once
+    /// it has passed health probes for this long.
+    pub failback_after: Duration,
+
+    /// How often to probe the more preferred endpoints while on a fallback.
+    pub failback_probe_interval: Duration,
+
+    /// Wrap connections in TLS with these settings (None = plain telnet).
+    pub tls: Option<TlsConfig>,
+
     /// Callsign to use for login.
     pub callsign: String,
 
@@ -41,20 +124,49 @@ pub struct RbnClientConfig {
     /// Whether to automatically reconnect on disconnect.
     pub auto_reconnect: bool,
 
-    /// Delay between reconnection attempts.
+    /// Initial delay between reconnection attempts; doubles after each
+    /// failed attempt.
     pub reconnect_delay: Duration,
+
+    /// Upper bound for the delay between reconnection attempts.
+    pub max_reconnect_delay: Duration,
+
+    /// Give up after this many reconnection attempts in a row (None =
+    /// retry forever).
+    pub max_reconnect_attempts: Option<u32>,
+
+    /// A connection that streams for this long resets the backoff.
+    pub reconnect_reset_after: Duration,
+
+    /// Lines longer than this many bytes are dropped.
+    pub max_line_length: usize,
+
+    /// Steps run after sending the callsign, e.g. to answer a password
+    /// prompt or send `set/skimmer`. Without a script, the client waits
+    /// for the command prompt.
+    pub login_script: Vec<LoginStep>,
 }
 
 impl Default for RbnClientConfig {
     fn default() -> Self {
         Self {
+            name: DEFAULT_SOURCE_NAME.to_string(),
             host: RBN_HOST.to_string(),
             port: RBN_PORT_CW,
+            fallbacks: Vec::new(),
+            failback_after: Duration::from_secs(300),
+            failback_probe_interval: Duration::from_secs(30),
+            tls: None,
             callsign: "N0CALL".to_string(),
             connect_timeout: Duration::from_secs(30),
             read_timeout: Duration::from_secs(120),
             auto_reconnect: true,
             reconnect_delay: Duration::from_secs(5),
+            max_reconnect_delay: Duration::from_secs(300),
+            max_reconnect_attempts: None,
+            reconnect_reset_after: Duration::from_secs(60),
+            max_line_length: DEFAULT_MAX_LINE_LENGTH,
+            login_script: Vec::new(),
         }
     }
 }
@@ -68,61 +180,188 @@ impl RbnClientConfig {
         }
     }
 
+    /// Set the source name.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
     /// Set the host and port.
     pub fn with_server(mut self, host: impl Into<String>, port: u16) -> Self {
         self.host = host.into();
         self.port = port;
         self
     }
+
+    /// Connect over TLS with the given settings.
+    pub fn with_tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Add an endpoint to fail over to, after any added before.
+    pub fn with_fallback(mut self, host: impl Into<String>, port: u16) -> Self {
+        self.fallbacks.push(Endpoint::new(host, port));
+        self
+    }
+
+    /// All endpoints in order of preference: `host` and `port`, then the
+    /// fallbacks.
+    pub fn endpoints(&self) -> Vec<Endpoint> {
+        let mut endpoints = vec![Endpoint::new(self.host.clone(), self.port)];
+        endpoints.extend(self.fallbacks.iter().cloned());
+        endpoints
+    }
 }
 
 /// Events from the RBN client.
 #[derive(Debug)]
 pub enum RbnEvent {
-    /// A line was received from the server.
-    Line(String),
+    /// A line was received from the server at `received_at`, taken when
+    /// the line was read off the connection.
+    Line {
+        line: String,
+        received_at: DateTime<Utc>,
+    },
 
-    /// Connection was established.
-    Connected,
+    /// Logged in to the given endpoint.
+    Connected(Endpoint),
 
     /// Connection was lost.
     Disconnected(String),
 
+    /// Waiting `delay` before reconnection attempt number `attempt`.
+    Reconnecting { attempt: u32, delay: Duration },
+
+    /// A connection stayed up for `reconnect_reset_after`, so the backoff
+    /// starts over from the initial delay.
+    BackoffReset,
+
+    /// The reconnection attempt budget was spent; the client has stopped.
+    GaveUp { attempts: u32 },
+
     /// An error occurred.
     Error(String),
 }
 
+/// An event from one of several merged sources.
+#[derive(Debug)]
+pub struct SourceEvent {
+    /// Name of the source the event came from.
+    pub source: Arc<str>,
+
+    /// The event itself.
+    pub event: RbnEvent,
+}
+
+/// Connect to several servers at once, merging their events into one
+/// stream.
+///
+/// Every event is tagged with the name of the client it came from. The
+/// stream ends once all clients have stopped.
+pub async fn connect_all(configs: Vec<RbnClientConfig>) -> Result<mpsc::Receiver<SourceEvent>> {
+    let (tx, rx) = mpsc::channel(1000);
+
+    for config in configs {
+        let source: Arc<str> = config.name.as_str().into();
+        let mut events = RbnClient::new(config).connect().await?;
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            while let Some(event) = events.recv().await {
+                let event = SourceEvent {
+                    source: Arc::clone(&source),
+                    event,
+                };
+                if tx.send(event).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    Ok(rx)
+}
+
+/// Result of a background health probe of a preferred endpoint.
+#[derive(Debug)]
+struct ProbeResult {
+    /// Position of the endpoint in the endpoint list.
+    index: usize,
+    healthy: bool,
+    at: Instant,
+}
+
+/// Aborts a background task when dropped.
+struct AbortOnDrop(tokio::task::JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
 /// Async RBN telnet client.
+#[derive(Clone)]
 pub struct RbnClient {
     config: RbnClientConfig,
+    tls: Option<TlsConnector>,
 }
 
 impl RbnClient {
     /// Create a new RBN client with the given configuration.
     pub fn new(config: RbnClientConfig) -> Self {
-        Self { config }
+        Self { config, tls: None }
     }
 
     /// Connect to the RBN server and start streaming spots.
     ///
     /// Returns a receiver channel that will receive `RbnEvent`s.
-    /// The connection runs in a background task.
-    pub async fn connect(self) -> Result<mpsc::Receiver<RbnEvent>> {
+    /// The connection runs in a background task. Fails right away if the
+    /// TLS settings can't be used, e.g. the CA file is unreadable.
+    pub async fn connect(mut self) -> Result<mpsc::Receiver<RbnEvent>> {
+        if let Some(ref tls) = self.config.tls {
+            self.tls = Some(
+                tls.connector()
+                    .with_context(|| format!("Invalid TLS settings for {}", self.config.name))?,
+            );
+        }
         let (tx, rx) = mpsc::channel(1000);
 
-        tokio::spawn(async move {
-            self.run_connection_loop(tx).await;
-        });
+        // Log lines from each connection carry the source name
+        let span = info_span!("source", name = %self.config.name);
+        tokio::spawn(
+            async move {
+                self.run_connection_loop(tx).await;
+            }
+            .instrument(span),
+        );
 
         Ok(rx)
     }
 
     /// Run the main connection loop with auto-reconnect.
+    ///
+    /// A failed connect or login fails over to the next endpoint right
+    /// away. Once every endpoint has been tried, reconnects back off
+    /// exponentially with full jitter; a connection that stayed up for
+    /// `reconnect_reset_after` starts the backoff over.
     async fn run_connection_loop(self, tx: mpsc::Sender<RbnEvent>) {
+        let mut backoff = Backoff::new(
+            self.config.reconnect_delay,
+            self.config.max_reconnect_delay,
+            self.config.max_reconnect_attempts,
+        );
+        let mut endpoints = Endpoints::new(self.config.endpoints());
+
         loop {
-            match self.connect_and_stream(&tx).await {
+            let endpoint = endpoints.active().clone();
+            let mut connected_at = None;
+            match self
+                .connect_and_stream(&tx, &mut endpoints, &mut connected_at)
+                .await
+            {
                 Ok(()) => {
-                    info!("Connection closed normally");
+                    info!("Connection to {} closed", endpoint);
                 }
                 Err(e) => {
                     error!("Connection error: {}", e);
@@ -130,25 +369,73 @@ impl RbnClient {
                 }
             }
 
-            let _ = tx
-                .send(RbnEvent::Disconnected("Connection lost".to_string()))
-                .await;
+            // Health probes picked a more preferred endpoint to switch to
+            let failing_back = connected_at.is_some() && *endpoints.active() != endpoint;
+            let reason = if failing_back {
+                format!("Switching back to {}", endpoints.active())
+            } else {
+                "Connection lost".to_string()
+            };
+            let _ = tx.send(RbnEvent::Disconnected(reason)).await;
 
-            if !self.config.auto_reconnect {
+            if !self.config.auto_reconnect || tx.is_closed() {
                 break;
             }
 
+            if connected_at.is_some_and(|at| at.elapsed() >= self.config.reconnect_reset_after)
+                && backoff.attempts() > 0
+            {
+                backoff.reset();
+                let _ = tx.send(RbnEvent::BackoffReset).await;
+            }
+            if failing_back {
+                continue;
+            }
+            if connected_at.is_none() && !endpoints.record_failure() {
+                info!("Failing over to {}", endpoints.active());
+                continue;
+            }
+
+            let Some(delay) = backoff.next_delay() else {
+                warn!(
+                    "Giving up after {} reconnection attempts",
+                    backoff.attempts()
+                );
+                let _ = tx
+                    .send(RbnEvent::GaveUp {
+                        attempts: backoff.attempts(),
+                    })
+                    .await;
+                break;
+            };
+
             info!(
-                "Reconnecting in {} seconds...",
-                self.config.reconnect_delay.as_secs()
+                "Reconnecting in {:.1} seconds (attempt {})...",
+                delay.as_secs_f64(),
+                backoff.attempts()
             );
-            tokio::time::sleep(self.config.reconnect_delay).await;
+            let _ = tx
+                .send(RbnEvent::Reconnecting {
+                    attempt: backoff.attempts(),
+                    delay,
+                })
+                .await;
+            tokio::time::sleep(delay).await;
         }
     }
 
-    /// Connect to the server and stream lines until disconnected.
-    async fn connect_and_stream(&self, tx: &mpsc::Sender<RbnEvent>) -> Result<()> {
-        let addr = format!("{}:{}", self.config.host, self.config.port);
+    /// Connect to the active endpoint and stream lines until disconnected,
+    /// or until a more preferred endpoint is healthy again.
+    ///
+    /// `connected_at` is set once login completes.
+    async fn connect_and_stream(
+        &self,
+        tx: &mpsc::Sender<RbnEvent>,
+        endpoints: &mut Endpoints,
+        connected_at: &mut Option<Instant>,
+    ) -> Result<()> {
+        let endpoint = endpoints.active().clone();
+        let addr = endpoint.to_string();
         info!("Connecting to {}...", addr);
 
         // Connect with timeout
@@ -159,34 +446,65 @@ impl RbnClient {
 
         info!("Connected to {}", addr);
 
-        let (reader, mut writer) = stream.into_split();
-        let mut reader = BufReader::new(reader);
+        let (reader, writer) = self.wrap_transport(stream, &endpoint).await?;
+        let mut stream = TelnetStream::new(reader, writer, self.config.max_line_length);
 
         // Phase 1: Handle login sequence
-        // The server sends "Please enter your call: " without a trailing newline,
-        // so we need to read bytes until we see the prompt
-        self.handle_login(&mut reader, &mut writer).await?;
-        let _ = tx.send(RbnEvent::Connected).await;
+        self.handle_login(&mut stream).await?;
+        let now = Instant::now();
+        *connected_at = Some(now);
+        endpoints.record_success(now);
+        let _ = tx.send(RbnEvent::Connected(endpoint)).await;
 
-        // Phase 2: Stream spot lines
-        let mut line_buf = String::with_capacity(256);
+        // Phase 2: Stream spot lines, probing the more preferred endpoints
+        // in the background while on a fallback
+        let probing = self.config.auto_reconnect && endpoints.active_index() > 0;
+        let (mut probes, _probe_task) = if probing {
+            let (rx, task) = self.spawn_probes(endpoints.preferred().to_vec());
+            (rx, Some(task))
+        } else {
+            (mpsc::channel(1).1, None)
+        };
+        let mut deadline = tokio::time::Instant::now() + self.config.read_timeout;
+        let mut overlong_lines = 0;
 
         loop {
-            line_buf.clear();
+            let read_result = tokio::select! {
+                result = tokio::time::timeout_at(deadline, stream.read_line()) => result,
+                Some(probe) = probes.recv(), if probing => {
+                    if self.record_probe(endpoints, probe) {
+                        return Ok(());
+                    }
+                    continue;
+                }
+            };
 
-            let read_result =
-                timeout(self.config.read_timeout, reader.read_line(&mut line_buf)).await;
+            let dropped = stream.decoder().overlong_lines();
+            if dropped > overlong_lines {
+                warn!(
+                    "Dropped {} line(s) longer than {} bytes",
+                    dropped - overlong_lines,
+                    self.config.max_line_length
+                );
+                overlong_lines = dropped;
+            }
 
             match read_result {
-                Ok(Ok(0)) => {
+                Ok(Ok(None)) => {
                     // EOF - connection closed
                     return Ok(());
                 }
-                Ok(Ok(_n)) => {
-                    let line = line_buf.trim_end();
+                Ok(Ok(Some(line))) => {
+                    let received_at = Utc::now();
+                    deadline = tokio::time::Instant::now() + self.config.read_timeout;
+                    let line = line.trim_end();
                     debug!("Received: {}", line);
 
-                    if tx.send(RbnEvent::Line(line.to_string())).await.is_err() {
+                    let event = RbnEvent::Line {
+                        line: line.to_string(),
+                        received_at,
+                    };
+                    if tx.send(event).await.is_err() {
                         // Receiver dropped
                         return Ok(());
                     }
@@ -202,94 +520,167 @@ impl RbnClient {
         }
     }
 
-    /// Handle the login sequence by reading bytes until we see the callsign prompt.
-    async fn handle_login<R, W>(&self, reader: &mut R, writer: &mut W) -> Result<()>
-    where
-        R: AsyncReadExt + Unpin,
-        W: AsyncWriteExt + Unpin,
-    {
-        let mut buf = Vec::with_capacity(1024);
-        let mut byte = [0u8; 1];
+    /// Split the connection into reader and writer, running the TLS
+    /// handshake first if TLS is configured.
+    async fn wrap_transport(
+        &self,
+        stream: TcpStream,
+        endpoint: &Endpoint,
+    ) -> Result<(
+        Box<dyn AsyncRead + Unpin + Send>,
+        Box<dyn AsyncWrite + Unpin + Send>,
+    )> {
+        let (Some(connector), Some(tls)) = (&self.tls, &self.config.tls) else {
+            let (reader, writer) = stream.into_split();
+            return Ok((Box::new(reader), Box::new(writer)));
+        };
 
-        // Read until we see "call:" prompt (case-insensitive)
-        loop {
-            let read_result = timeout(self.config.connect_timeout, reader.read(&mut byte)).await;
+        let server_name = tls.server_name(&endpoint.host)?;
+        let stream = timeout(
+            self.config.connect_timeout,
+            connector.connect(server_name, stream),
+        )
+        .await
+        .context("TLS handshake timeout")?
+        .context("TLS handshake failed")?;
+        debug!("TLS session established with {}", endpoint);
 
-            match read_result {
-                Ok(Ok(0)) => {
-                    return Err(anyhow::anyhow!("Connection closed during login"));
-                }
-                Ok(Ok(_)) => {
-                    buf.push(byte[0]);
-
-                    // Check if we've received the login prompt
-                    // Looking for "call:" which appears in "Please enter your call:"
-                    if buf.len() >= 5 {
-                        let tail = String::from_utf8_lossy(&buf[buf.len() - 5..]);
-                        if tail.eq_ignore_ascii_case("call:") {
-                            debug!(
-                                "Login prompt received: {}",
-                                String::from_utf8_lossy(&buf).trim()
-                            );
-                            break;
-                        }
-                    }
+        let (reader, writer) = tokio::io::split(stream);
+        Ok((Box::new(reader), Box::new(writer)))
+    }
 
-                    // Safety limit to avoid reading forever
-                    if buf.len() > 4096 {
-                        return Err(anyhow::anyhow!("No login prompt found in initial data"));
+    /// Probe `preferred` every `failback_probe_interval` in a background
+    /// task, so slow probes don't hold up reading spots. Results arrive on
+    /// the returned channel; the task stops when the guard is dropped.
+    fn spawn_probes(&self, preferred: Vec<Endpoint>) -> (mpsc::Receiver<ProbeResult>, AbortOnDrop) {
+        let (tx, rx) = mpsc::channel(preferred.len().max(1));
+        let prober = self.clone();
+        let task = tokio::spawn(
+            async move {
+                loop {
+                    tokio::time::sleep(prober.config.failback_probe_interval).await;
+                    for (index, endpoint) in preferred.iter().enumerate() {
+                        let healthy = match prober.probe(endpoint).await {
+                            Ok(()) => {
+                                debug!("Health probe of {}: healthy", endpoint);
+                                true
+                            }
+                            Err(e) => {
+                                debug!("Health probe of {}: {:#}", endpoint, e);
+                                false
+                            }
+                        };
+                        let result = ProbeResult {
+                            index,
+                            healthy,
+                            at: Instant::now(),
+                        };
+                        if tx.send(result).await.is_err() {
+                            return;
+                        }
                     }
                 }
-                Ok(Err(e)) => {
-                    return Err(e).context("Read error during login");
-                }
-                Err(_) => {
-                    return Err(anyhow::anyhow!("Timeout waiting for login prompt"));
-                }
             }
-        }
+            .in_current_span(),
+        );
+        (rx, AbortOnDrop(task))
+    }
+
+    /// Record a health probe result, and switch to the most preferred
+    /// endpoint that has stayed healthy for `failback_after`. Returns true
+    /// if it switched.
+    fn record_probe(&self, endpoints: &mut Endpoints, probe: ProbeResult) -> bool {
+        endpoints.record_probe(probe.index, probe.healthy, probe.at);
+
+        let Some(index) = endpoints.failback_target(self.config.failback_after, Instant::now())
+        else {
+            return false;
+        };
+        endpoints.switch_to(index);
+        info!(
+            "{} has been healthy for {:?}, switching back",
+            endpoints.active(),
+            self.config.failback_after
+        );
+        true
+    }
+
+    /// Check that `endpoint` is ready for a login: it accepts connections,
+    /// completes the TLS handshake for TLS sources, and prompts for a
+    /// callsign. Disconnects without logging in.
+    async fn probe(&self, endpoint: &Endpoint) -> Result<()> {
+        let stream = timeout(
+            self.config.connect_timeout,
+            TcpStream::connect(endpoint.to_string()),
+        )
+        .await
+        .context("Connection timeout")?
+        .context("Failed to connect")?;
+        let (reader, writer) = self.wrap_transport(stream, endpoint).await?;
+        let mut stream = TelnetStream::new(reader, writer, self.config.max_line_length);
+        self.wait_for_login_prompt(&mut stream).await
+    }
+
+    /// Handle the login sequence: wait for the callsign prompt, send the
+    /// callsign, then run the login script or wait for the command prompt.
+    ///
+    /// Option negotiation the server starts along the way is answered by
+    /// the [`TelnetStream`] as each chunk is read.
+    async fn handle_login<R, W>(&self, stream: &mut TelnetStream<R, W>) -> Result<()>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        self.wait_for_login_prompt(stream).await?;
 
         // Send callsign
         info!("Sending callsign: {}", self.config.callsign);
-        writer
-            .write_all(format!("{}\r\n", self.config.callsign).as_bytes())
+        stream
+            .send_line(&self.config.callsign)
             .await
             .context("Failed to send callsign")?;
-        writer.flush().await?;
+
+        if !self.config.login_script.is_empty() {
+            for (i, step) in self.config.login_script.iter().enumerate() {
+                self.run_login_step(stream, i + 1, step).await?;
+            }
+            info!("Login complete");
+            return Ok(());
+        }
 
         // Read the post-login messages until we see the command prompt (ends with ">")
         // e.g., "W6JSV de RELAY 08-Jan-2026 03:13Z >"
-        buf.clear();
+        let mut received = 0;
         loop {
-            let read_result = timeout(self.config.connect_timeout, reader.read(&mut byte)).await;
+            while let Some(line) = stream.decoder().next_line() {
+                let trimmed = line.trim();
+                if !trimmed.is_empty() {
+                    debug!("Login response: {}", trimmed);
+                }
+                if trimmed.ends_with('>') {
+                    info!("Login complete");
+                    return Ok(());
+                }
+            }
+            if stream.decoder().pending().trim_end().ends_with('>') {
+                let prompt = stream.decoder().take_pending();
+                debug!("Login response: {}", prompt.trim());
+                info!("Login complete");
+                return Ok(());
+            }
 
-            match read_result {
+            // Safety limit
+            if received > 4096 {
+                // Assume login succeeded if we got this far
+                debug!("No command prompt found, assuming login succeeded");
+                return Ok(());
+            }
+
+            match timeout(self.config.connect_timeout, stream.fill()).await {
                 Ok(Ok(0)) => {
                     return Err(anyhow::anyhow!("Connection closed after login"));
                 }
-                Ok(Ok(_)) => {
-                    buf.push(byte[0]);
-
-                    // Check for command prompt ending with ">"
-                    if byte[0] == b'>' {
-                        let response = String::from_utf8_lossy(&buf);
-                        for line in response.lines() {
-                            let trimmed = line.trim();
-                            if !trimmed.is_empty() {
-                                debug!("Login response: {}", trimmed);
-                            }
-                        }
-                        info!("Login complete");
-                        return Ok(());
-                    }
-
-                    // Safety limit
-                    if buf.len() > 4096 {
-                        // Assume login succeeded if we got this far
-                        debug!("No command prompt found, assuming login succeeded");
-                        return Ok(());
-                    }
-                }
+                Ok(Ok(n)) => received += n,
                 Ok(Err(e)) => {
                     return Err(e).context("Read error after login");
                 }
@@ -301,6 +692,147 @@ impl RbnClient {
             }
         }
     }
+
+    /// Read the login banner until the callsign prompt.
+    async fn wait_for_login_prompt<R, W>(&self, stream: &mut TelnetStream<R, W>) -> Result<()>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        // The server sends "Please enter your call: " without a trailing
+        // newline, so the prompt may only show up as pending text
+        let mut received = 0;
+        loop {
+            let mut prompt = None;
+            while let Some(line) = stream.decoder().next_line() {
+                if is_login_prompt(&line) {
+                    prompt = Some(line);
+                    break;
+                }
+                debug!("Login banner: {}", line.trim());
+            }
+            if prompt.is_none() && is_login_prompt(&stream.decoder().pending()) {
+                prompt = Some(stream.decoder().take_pending());
+            }
+            if let Some(prompt) = prompt {
+                debug!("Login prompt received: {}", prompt.trim());
+                return Ok(());
+            }
+
+            // Safety limit to avoid reading forever
+            if received > 4096 {
+                return Err(anyhow::anyhow!("No login prompt found in initial data"));
+            }
+
+            match timeout(self.config.connect_timeout, stream.fill()).await {
+                Ok(Ok(0)) => {
+                    return Err(anyhow::anyhow!("Connection closed during login"));
+                }
+                Ok(Ok(n)) => received += n,
+                Ok(Err(e)) => {
+                    return Err(e).context("Read error during login");
+                }
+                Err(_) => {
+                    return Err(anyhow::anyhow!("Timeout waiting for login prompt"));
+                }
+            }
+        }
+    }
+
+    /// Run one login script step: wait for its prompt, then send its line.
+    async fn run_login_step<R, W>(
+        &self,
+        stream: &mut TelnetStream<R, W>,
+        number: usize,
+        step: &LoginStep,
+    ) -> Result<()>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        if let Some(ref pattern) = step.expect {
+            let wait = step
+                .timeout_secs
+                .map_or(self.config.connect_timeout, Duration::from_secs);
+            match timeout(wait, wait_for_text(stream, pattern)).await {
+                Ok(Ok(true)) => {}
+                Ok(Ok(false)) => {
+                    anyhow::bail!(
+                        "Login script step {}: connection closed while waiting for {:?}",
+                        number,
+                        pattern
+                    );
+                }
+                Ok(Err(e)) => {
+                    return Err(e).with_context(|| {
+                        format!(
+                            "Login script step {}: read error waiting for {:?}",
+                            number, pattern
+                        )
+                    });
+                }
+                Err(_) => {
+                    anyhow::bail!(
+                        "Login script step {}: no {:?} received within {:?}",
+                        number,
+                        pattern,
+                        wait
+                    );
+                }
+            }
+        }
+
+        let line = step
+            .resolve(&|var| std::env::var(var).ok())
+            .map_err(|e| anyhow::anyhow!("Login script step {}: {}", number, e))?;
+        let Some(line) = line else {
+            return Ok(());
+        };
+        match step.send_env {
+            Some(ref var) => info!("Login script step {}: sending ${}", number, var),
+            None => info!("Login script step {}: sending {:?}", number, line),
+        }
+        stream
+            .send_line(&line)
+            .await
+            .with_context(|| format!("Login script step {}: failed to send", number))
+    }
+}
+
+/// Read until a line or unterminated prompt contains `pattern`
+/// (case-insensitive), consuming everything up to it.
+///
+/// Returns false if the connection closes first.
+async fn wait_for_text<R, W>(
+    stream: &mut TelnetStream<R, W>,
+    pattern: &str,
+) -> std::io::Result<bool>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let pattern = pattern.to_lowercase();
+    loop {
+        while let Some(line) = stream.decoder().next_line() {
+            debug!("Login response: {}", line.trim());
+            if line.to_lowercase().contains(&pattern) {
+                return Ok(true);
+            }
+        }
+        if stream.decoder().pending().to_lowercase().contains(&pattern) {
+            let prompt = stream.decoder().take_pending();
+            debug!("Login response: {}", prompt.trim());
+            return Ok(true);
+        }
+        if stream.fill().await? == 0 {
+            return Ok(false);
+        }
+    }
+}
+
+/// Check for the callsign prompt, e.g. "Please enter your call:".
+fn is_login_prompt(text: &str) -> bool {
+    text.trim_end().to_ascii_lowercase().ends_with("call:")
 }
 
 #[cfg(test)]
@@ -312,15 +844,725 @@ mod tests {
         let config = RbnClientConfig::default();
         assert_eq!(config.host, RBN_HOST);
         assert_eq!(config.port, RBN_PORT_CW);
+        assert_eq!(config.name, DEFAULT_SOURCE_NAME);
         assert!(config.auto_reconnect);
+        assert_eq!(config.max_line_length, DEFAULT_MAX_LINE_LENGTH);
     }
 
     #[test]
     fn test_config_builder() {
-        let config = RbnClientConfig::with_callsign("W6JSV").with_server("test.example.com", 1234);
+        let config = RbnClientConfig::with_callsign("W6JSV")
+            .with_server("test.example.com", 1234)
+            .with_name("skimmer");
 
+        assert_eq!(config.name, "skimmer");
         assert_eq!(config.callsign, "W6JSV");
         assert_eq!(config.host, "test.example.com");
         assert_eq!(config.port, 1234);
     }
+
+    /// Accept one connection and play the server side of a login that
+    /// negotiates echo, terminal type and window size, checking every reply
+    /// the client sends.
+    async fn scripted_server(listener: tokio::net::TcpListener) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        const IAC: u8 = 255;
+        const DONT: u8 = 254;
+        const DO: u8 = 253;
+        const WILL: u8 = 251;
+        const SB: u8 = 250;
+        const SE: u8 = 240;
+        const ECHO: u8 = 1;
+        const TERMINAL_TYPE: u8 = 24;
+        const NAWS: u8 = 31;
+
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut received = Vec::new();
+        let mut expect = async |socket: &mut TcpStream, len: usize| {
+            let mut buf = vec![0; len];
+            socket.read_exact(&mut buf).await.unwrap();
+            received.push(buf);
+        };
+
+        socket
+            .write_all(&[IAC, WILL, ECHO, IAC, DO, TERMINAL_TYPE, IAC, DO, NAWS])
+            .await
+            .unwrap();
+        // DONT ECHO, WILL TTYPE, WILL NAWS and the window size
+        expect(&mut socket, 9 + 9).await;
+
+        socket
+            .write_all(&[IAC, SB, TERMINAL_TYPE, 1, IAC, SE])
+            .await
+            .unwrap();
+        expect(&mut socket, 6 + 4).await;
+
+        // Asking again must not be acknowledged
+        socket
+            .write_all(&[IAC, DO, NAWS, IAC, WILL, ECHO])
+            .await
+            .unwrap();
+        expect(&mut socket, 3).await;
+
+        socket
+            .write_all(b"Welcome to RELAY\r\nPlease enter your call: ")
+            .await
+            .unwrap();
+        expect(&mut socket, 7).await;
+
+        socket
+            .write_all(
+                b"W6JSV de RELAY 08-Jan-2026 03:13Z >\r\n\
+                  DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z\r\n",
+            )
+            .await
+            .unwrap();
+        socket.shutdown().await.unwrap();
+
+        assert_eq!(
+            received,
+            vec![
+                vec![
+                    IAC,
+                    DONT,
+                    ECHO,
+                    IAC,
+                    WILL,
+                    TERMINAL_TYPE,
+                    IAC,
+                    WILL,
+                    NAWS,
+                    IAC,
+                    SB,
+                    NAWS,
+                    0,
+                    80,
+                    0,
+                    24,
+                    IAC,
+                    SE
+                ],
+                [&[IAC, SB, TERMINAL_TYPE, 0][..], b"DUMB", &[IAC, SE]].concat(),
+                vec![IAC, DONT, ECHO],
+                b"W6JSV\r\n".to_vec(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_login_with_option_negotiation() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(scripted_server(listener));
+
+        let config = RbnClientConfig {
+            auto_reconnect: false,
+            connect_timeout: Duration::from_secs(5),
+            ..RbnClientConfig::with_callsign("W6JSV").with_server("127.0.0.1", port)
+        };
+        let mut rx = RbnClient::new(config).connect().await.unwrap();
+
+        let mut events = Vec::new();
+        while let Ok(Some(event)) = timeout(Duration::from_secs(5), rx.recv()).await {
+            events.push(event);
+        }
+        server.await.unwrap();
+
+        assert!(
+            matches!(
+                &events[..],
+                [RbnEvent::Connected(_), RbnEvent::Line { line, .. }, RbnEvent::Disconnected(_)]
+                    if line.starts_with("DX de EA5WU-#:")
+            ),
+            "{:?}",
+            events
+        );
+    }
+
+    /// Accept one connection, log it in and send a single line.
+    async fn one_line_server(listener: tokio::net::TcpListener, line: &'static str) {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let (socket, _) = listener.accept().await.unwrap();
+        let (reader, mut writer) = socket.into_split();
+        writer.write_all(b"Please enter your call: ").await.unwrap();
+        let mut callsign = String::new();
+        BufReader::new(reader)
+            .read_line(&mut callsign)
+            .await
+            .unwrap();
+        writer
+            .write_all(format!("{} de TEST >\r\n{}\r\n", callsign.trim(), line).as_bytes())
+            .await
+            .unwrap();
+        writer.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_connect_all_tags_events_by_source() {
+        let mut configs = Vec::new();
+        for (name, line) in [
+            (
+                "rbn-cw",
+                "DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z",
+            ),
+            (
+                "rbn-digital",
+                "DX de DL8LAS-#:  14074.0  PY2XB          FT8   -14 dB  CQ      1207Z",
+            ),
+        ] {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            tokio::spawn(one_line_server(listener, line));
+            configs.push(RbnClientConfig {
+                auto_reconnect: false,
+                ..RbnClientConfig::with_callsign("W6JSV")
+                    .with_server("127.0.0.1", port)
+                    .with_name(name)
+            });
+        }
+
+        let mut rx = connect_all(configs).await.unwrap();
+        let mut lines = Vec::new();
+        // The merged stream ends once both clients have stopped
+        while let Some(SourceEvent { source, event, .. }) =
+            timeout(Duration::from_secs(5), rx.recv()).await.unwrap()
+        {
+            if let RbnEvent::Line { line, .. } = event {
+                lines.push((source.to_string(), line[..9].to_string()));
+            }
+        }
+        lines.sort();
+
+        assert_eq!(
+            lines,
+            vec![
+                ("rbn-cw".to_string(), "DX de EA5".to_string()),
+                ("rbn-digital".to_string(), "DX de DL8".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_login_over_tls() {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let (pem, _, acceptor) = crate::tls::tests::self_signed();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let tls = acceptor.accept(socket).await.unwrap();
+            let (reader, mut writer) = tokio::io::split(tls);
+            writer.write_all(b"Please enter your call: ").await.unwrap();
+            let mut callsign = String::new();
+            BufReader::new(reader)
+                .read_line(&mut callsign)
+                .await
+                .unwrap();
+            writer
+                .write_all(
+                    b"W6JSV de NODE >\r\n\
+                      DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z\r\n",
+                )
+                .await
+                .unwrap();
+            writer.shutdown().await.unwrap();
+            callsign
+        });
+
+        let config = RbnClientConfig {
+            auto_reconnect: false,
+            ..RbnClientConfig::with_callsign("W6JSV")
+                .with_server("127.0.0.1", port)
+                .with_tls(TlsConfig {
+                    server_name: Some("localhost".to_string()),
+                    ca_file: Some(crate::tls::tests::temp_file("client-ca.pem", &pem)),
+                    ..Default::default()
+                })
+        };
+        let mut rx = RbnClient::new(config).connect().await.unwrap();
+
+        let mut events = Vec::new();
+        while let Ok(Some(event)) = timeout(Duration::from_secs(5), rx.recv()).await {
+            events.push(event);
+        }
+
+        assert_eq!(server.await.unwrap(), "W6JSV\r\n");
+        assert!(
+            matches!(
+                &events[..],
+                [RbnEvent::Connected(_), RbnEvent::Line { line, .. }, RbnEvent::Disconnected(_)]
+                    if line.starts_with("DX de EA5WU-#:")
+            ),
+            "{:?}",
+            events
+        );
+    }
+
+    #[tokio::test]
+    async fn test_connect_rejects_bad_tls_settings() {
+        let config = RbnClientConfig::with_callsign("W6JSV").with_tls(TlsConfig {
+            ca_file: Some("/nonexistent/ca.pem".into()),
+            ..Default::default()
+        });
+        let err = RbnClient::new(config).connect().await.unwrap_err();
+        assert!(
+            err.to_string().contains("Invalid TLS settings for rbn"),
+            "{}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn test_probe_completes_tls_handshake() {
+        use crate::tls::fingerprint;
+        use tokio::io::AsyncWriteExt;
+
+        let (_, der, acceptor) = crate::tls::tests::self_signed();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let healthy = Endpoint::new("127.0.0.1", listener.local_addr().unwrap().port());
+        let server = tokio::spawn(async move {
+            loop {
+                let (socket, _) = listener.accept().await.unwrap();
+                if let Ok(mut tls) = acceptor.accept(socket).await {
+                    let _ = tls.write_all(b"Please enter your call: ").await;
+                    let _ = tls.flush().await;
+                }
+            }
+        });
+
+        // Accepts connections but presents a certificate that isn't pinned
+        let (_, _, other) = crate::tls::tests::self_signed();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let broken = Endpoint::new("127.0.0.1", listener.local_addr().unwrap().port());
+        let broken_server = tokio::spawn(async move {
+            loop {
+                let (socket, _) = listener.accept().await.unwrap();
+                let _ = other.accept(socket).await;
+            }
+        });
+
+        let tls = TlsConfig {
+            pin_sha256: vec![fingerprint(&der)],
+            ..Default::default()
+        };
+        let client = RbnClient {
+            tls: Some(tls.connector().unwrap()),
+            config: RbnClientConfig {
+                connect_timeout: Duration::from_millis(500),
+                ..RbnClientConfig::with_callsign("W6JSV").with_tls(tls)
+            },
+        };
+
+        assert!(client.probe(&healthy).await.is_ok());
+        let err = client.probe(&broken).await.unwrap_err();
+        assert!(format!("{:#}", err).contains("is not pinned"), "{:#}", err);
+
+        server.abort();
+        broken_server.abort();
+    }
+
+    /// Accept one connection and play a cluster node that asks for a
+    /// password and needs commands to start the skimmer feed. Returns the
+    /// lines the client sent.
+    async fn password_server(listener: tokio::net::TcpListener) -> Vec<String> {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let (socket, _) = listener.accept().await.unwrap();
+        let (reader, mut writer) = socket.into_split();
+        let mut reader = BufReader::new(reader);
+        let mut received = Vec::new();
+
+        for prompt in [
+            &b"Please enter your call: "[..],
+            b"Password: ",
+            b"Hello W6JSV\r\nW6JSV de NODE >",
+            b"Skimmer spots enabled\r\nW6JSV de NODE >",
+        ] {
+            writer.write_all(prompt).await.unwrap();
+            let mut line = String::new();
+            reader.read_line(&mut line).await.unwrap();
+            received.push(line.trim_end().to_string());
+        }
+        writer
+            .write_all(
+                b"\r\nDX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z\r\n",
+            )
+            .await
+            .unwrap();
+        writer.shutdown().await.unwrap();
+        received
+    }
+
+    fn step(expect: &str, send: &str) -> LoginStep {
+        LoginStep {
+            expect: Some(expect.to_string()),
+            send: Some(send.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_login_script() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(password_server(listener));
+
+        let config = RbnClientConfig {
+            auto_reconnect: false,
+            login_script: vec![
+                step("PASSWORD:", "s3cret"),
+                step(">", "set/skimmer"),
+                step("skimmer spots enabled", "sh/dx 1"),
+            ],
+            ..RbnClientConfig::with_callsign("W6JSV").with_server("127.0.0.1", port)
+        };
+        let mut rx = RbnClient::new(config).connect().await.unwrap();
+
+        let mut events = Vec::new();
+        while let Ok(Some(event)) = timeout(Duration::from_secs(5), rx.recv()).await {
+            events.push(event);
+        }
+
+        assert_eq!(
+            server.await.unwrap(),
+            vec!["W6JSV", "s3cret", "set/skimmer", "sh/dx 1"]
+        );
+        let lines: Vec<_> = events
+            .iter()
+            .filter_map(|e| match e {
+                RbnEvent::Line { line, .. } => Some(line.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(matches!(events[0], RbnEvent::Connected(_)), "{:?}", events);
+        // The rest of the last prompt's line is streamed, then the spot
+        assert_eq!(lines.len(), 2, "{:?}", events);
+        assert!(lines[1].starts_with("DX de EA5WU-#:"));
+    }
+
+    #[tokio::test]
+    async fn test_login_script_reports_missing_prompt() {
+        use tokio::io::AsyncWriteExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            socket.write_all(b"Please enter your call: ").await.unwrap();
+            // Hold the connection open without ever asking for a password
+            tokio::time::sleep(Duration::from_secs(2)).await;
+        });
+
+        let config = RbnClientConfig {
+            auto_reconnect: false,
+            login_script: vec![step("password:", "s3cret")],
+            connect_timeout: Duration::from_millis(200),
+            ..RbnClientConfig::with_callsign("W6JSV").with_server("127.0.0.1", port)
+        };
+        let mut rx = RbnClient::new(config).connect().await.unwrap();
+
+        let mut errors = Vec::new();
+        while let Ok(Some(event)) = timeout(Duration::from_secs(5), rx.recv()).await {
+            if let RbnEvent::Error(e) = event {
+                errors.push(e);
+            }
+        }
+        server.abort();
+
+        assert_eq!(
+            errors,
+            vec!["Login script step 1: no \"password:\" received within 200ms"]
+        );
+    }
+
+    #[test]
+    fn test_login_step_validate() {
+        assert!(step("password:", "s3cret").validate().is_ok());
+        assert!(LoginStep::default().validate().is_err());
+        assert!(
+            LoginStep {
+                send: Some("set/skimmer".to_string()),
+                send_env: Some("CLUSTER_PASSWORD".to_string()),
+                ..Default::default()
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(step(" ", "s3cret").validate().is_err());
+    }
+
+    #[test]
+    fn test_login_step_resolve() {
+        let env = |var: &str| (var == "CLUSTER_PASSWORD").then(|| "s3cret".to_string());
+        let password = |var: &str| LoginStep {
+            expect: Some("password:".to_string()),
+            send_env: Some(var.to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            password("CLUSTER_PASSWORD").resolve(&env),
+            Ok(Some("s3cret".to_string()))
+        );
+        assert_eq!(
+            password("OTHER_PASSWORD").resolve(&env),
+            Err("environment variable OTHER_PASSWORD is not set".to_string())
+        );
+        assert_eq!(
+            step(">", "set/skimmer").resolve(&env),
+            Ok(Some("set/skimmer".to_string()))
+        );
+        let wait = LoginStep {
+            expect: Some(">".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(wait.resolve(&env), Ok(None));
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_gives_up_after_budget() {
+        // Bind and drop a listener so the port refuses connections
+        let port = {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            listener.local_addr().unwrap().port()
+        };
+        let config = RbnClientConfig {
+            reconnect_delay: Duration::from_millis(10),
+            max_reconnect_delay: Duration::from_millis(20),
+            max_reconnect_attempts: Some(2),
+            ..RbnClientConfig::with_callsign("W6JSV").with_server("127.0.0.1", port)
+        };
+        let mut rx = RbnClient::new(config).connect().await.unwrap();
+
+        let mut events = Vec::new();
+        while let Ok(Some(event)) = timeout(Duration::from_secs(5), rx.recv()).await {
+            events.push(event);
+        }
+
+        let reconnects: Vec<_> = events
+            .iter()
+            .filter_map(|e| match e {
+                RbnEvent::Reconnecting { attempt, delay } => Some((*attempt, *delay)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(reconnects.len(), 2, "{:?}", events);
+        assert_eq!(reconnects[0].0, 1);
+        assert!(reconnects[0].1 <= Duration::from_millis(10));
+        assert_eq!(reconnects[1].0, 2);
+        assert!(reconnects[1].1 <= Duration::from_millis(20));
+        assert!(
+            matches!(events.last(), Some(RbnEvent::GaveUp { attempts: 2 })),
+            "{:?}",
+            events
+        );
+    }
+
+    #[tokio::test]
+    async fn test_backoff_reset_after_stable_connection() {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        // Every login is dropped after 150ms
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            loop {
+                let (socket, _) = listener.accept().await.unwrap();
+                let (reader, mut writer) = socket.into_split();
+                writer.write_all(b"Please enter your call: ").await.unwrap();
+                let mut callsign = String::new();
+                BufReader::new(reader)
+                    .read_line(&mut callsign)
+                    .await
+                    .unwrap();
+                writer.write_all(b"W6JSV de TEST >\r\n").await.unwrap();
+                tokio::time::sleep(Duration::from_millis(150)).await;
+            }
+        });
+
+        let config = RbnClientConfig {
+            reconnect_delay: Duration::from_millis(10),
+            max_reconnect_delay: Duration::from_millis(20),
+            reconnect_reset_after: Duration::from_millis(100),
+            ..RbnClientConfig::with_callsign("W6JSV").with_server("127.0.0.1", port)
+        };
+        let mut rx = RbnClient::new(config).connect().await.unwrap();
+
+        let mut reconnects = 0;
+        let events = events_until(&mut rx, Duration::from_secs(5), |event| {
+            if matches!(event, RbnEvent::Reconnecting { .. }) {
+                reconnects += 1;
+            }
+            reconnects == 2
+        })
+        .await;
+        drop(rx);
+        server.abort();
+
+        // Only a reconnect that was backed off gets a reset
+        let kinds: Vec<_> = events
+            .iter()
+            .map(|e| match e {
+                RbnEvent::Connected(_) => "connected",
+                RbnEvent::Disconnected(_) => "disconnected",
+                RbnEvent::Reconnecting { attempt: 1, .. } => "reconnecting 1",
+                RbnEvent::BackoffReset => "reset",
+                _ => "other",
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "connected",
+                "disconnected",
+                "reconnecting 1",
+                "connected",
+                "disconnected",
+                "reset",
+                "reconnecting 1",
+            ]
+        );
+    }
+
+    /// Play a cluster node on `listener` that prompts for a callsign,
+    /// answers with the command prompt and then holds the connection.
+    /// Connections that close without logging in (health probes) are
+    /// ignored.
+    async fn prompting_server(listener: tokio::net::TcpListener) {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            tokio::spawn(async move {
+                let (reader, mut writer) = socket.into_split();
+                writer.write_all(b"Please enter your call: ").await?;
+                let mut callsign = String::new();
+                if BufReader::new(reader).read_line(&mut callsign).await? == 0 {
+                    return Ok(());
+                }
+                writer.write_all(b"W6JSV de TEST >\r\n").await?;
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok::<_, std::io::Error>(())
+            });
+        }
+    }
+
+    /// Collect events until `done` matches one, or none arrive for `idle`.
+    async fn events_until(
+        rx: &mut mpsc::Receiver<RbnEvent>,
+        idle: Duration,
+        mut done: impl FnMut(&RbnEvent) -> bool,
+    ) -> Vec<RbnEvent> {
+        let mut events = Vec::new();
+        while let Ok(Some(event)) = timeout(idle, rx.recv()).await {
+            let stop = done(&event);
+            events.push(event);
+            if stop {
+                break;
+            }
+        }
+        events
+    }
+
+    fn failover_config(preferred_port: u16, fallback_port: u16) -> RbnClientConfig {
+        RbnClientConfig {
+            connect_timeout: Duration::from_millis(200),
+            failback_after: Duration::from_millis(300),
+            failback_probe_interval: Duration::from_millis(100),
+            ..RbnClientConfig::with_callsign("W6JSV")
+                .with_server("127.0.0.1", preferred_port)
+                .with_fallback("127.0.0.1", fallback_port)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failover_without_failback_to_silent_server() {
+        // The preferred endpoint accepts connections but never prompts, so
+        // health probes must not count it as healthy
+        let preferred = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let preferred_port = preferred.local_addr().unwrap().port();
+
+        let fallback = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let fallback_port = fallback.local_addr().unwrap().port();
+        let server = tokio::spawn(prompting_server(fallback));
+
+        let config = failover_config(preferred_port, fallback_port);
+        let mut rx = RbnClient::new(config).connect().await.unwrap();
+
+        // Several failback periods pass without a switch
+        let events = events_until(&mut rx, Duration::from_millis(1500), |_| false).await;
+        drop(rx);
+        server.abort();
+        drop(preferred);
+
+        assert!(
+            matches!(
+                &events[..],
+                [
+                    RbnEvent::Error(_),
+                    RbnEvent::Disconnected(_),
+                    RbnEvent::Connected(endpoint),
+                ] if endpoint.port == fallback_port
+            ),
+            "{:?}",
+            events
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failback_to_recovered_server() {
+        use tokio::io::AsyncWriteExt;
+
+        // The preferred endpoint drops the first connection, then recovers
+        let preferred = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let preferred_port = preferred.local_addr().unwrap().port();
+        let preferred_server = tokio::spawn(async move {
+            let (mut socket, _) = preferred.accept().await.unwrap();
+            socket.shutdown().await.unwrap();
+            drop(socket);
+            prompting_server(preferred).await;
+        });
+
+        let fallback = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let fallback_port = fallback.local_addr().unwrap().port();
+        let fallback_server = tokio::spawn(prompting_server(fallback));
+
+        let config = failover_config(preferred_port, fallback_port);
+        let mut rx = RbnClient::new(config).connect().await.unwrap();
+
+        let events = events_until(&mut rx, Duration::from_secs(5), |event| {
+            matches!(event, RbnEvent::Connected(endpoint) if endpoint.port == preferred_port)
+        })
+        .await;
+        drop(rx);
+        preferred_server.abort();
+        fallback_server.abort();
+
+        assert!(
+            matches!(
+                &events[..],
+                [
+                    RbnEvent::Error(_),
+                    RbnEvent::Disconnected(_),
+                    RbnEvent::Connected(fallback),
+                    RbnEvent::Disconnected(reason),
+                    RbnEvent::Connected(preferred),
+                ] if fallback.port == fallback_port
+                    && preferred.port == preferred_port
+                    && *reason == format!("Switching back to 127.0.0.1:{}", preferred_port)
+            ),
+            "{:?}",
+            events
+        );
+    }
+
+    #[test]
+    fn test_is_login_prompt() {
+        assert!(is_login_prompt("Please enter your call: "));
+        assert!(is_login_prompt("login CALL:"));
+        assert!(!is_login_prompt("Welcome to the Reverse Beacon Network"));
+    }
 }
@@ -7,9 +7,82 @@ use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::fs;
 use std::path::PathBuf;
+use std::time::Duration;
 
-use crate::client::{RBN_HOST, RBN_PORT_CW};
+use crate::band::{BandEdges, BandPlan, IaruRegion};
+use crate::client::{DEFAULT_SOURCE_NAME, LoginStep, RBN_HOST, RBN_PORT_CW, RbnClientConfig};
+use crate::failover::Endpoint;
 use crate::filter::SpotFilter;
+use crate::telnet::DEFAULT_MAX_LINE_LENGTH;
+use crate::tls::TlsConfig;
+
+/// A server to stream spots from, listed under `[[sources]]`.
+///
+/// Settings not given here are taken from the top level of the config.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SourceConfig {
+    /// Name used to tag the source's spots, and in stats, metrics and
+    /// filters.
+    pub name: String,
+
+    /// Server hostname.
+    pub host: String,
+
+    /// Server port.
+    pub port: u16,
+
+    /// Endpoints to fail over to, in order of preference.
+    #[serde(default)]
+    pub fallbacks: Vec<Endpoint>,
+
+    /// Connect over TLS with these settings.
+    #[serde(default)]
+    pub tls: Option<TlsConfig>,
+
+    /// Callsign to log in with, if not the top-level `callsign`.
+    #[serde(default)]
+    pub callsign: Option<String>,
+
+    /// Overrides the top-level `cw_only` for this source.
+    #[serde(default)]
+    pub cw_only: Option<bool>,
+
+    /// Overrides the top-level `login_script` for this source.
+    #[serde(default)]
+    pub login_script: Option<Vec<LoginStep>>,
+
+    /// Overrides the top-level `connect_timeout` for this source.
+    #[serde(default)]
+    pub connect_timeout: Option<u64>,
+
+    /// Overrides the top-level `read_timeout` for this source.
+    #[serde(default)]
+    pub read_timeout: Option<u64>,
+
+    /// Overrides the top-level `reconnect` for this source.
+    #[serde(default)]
+    pub reconnect: Option<bool>,
+
+    /// Overrides the top-level `reconnect_delay` for this source.
+    #[serde(default)]
+    pub reconnect_delay: Option<u64>,
+
+    /// Overrides the top-level `max_reconnect_delay` for this source.
+    #[serde(default)]
+    pub max_reconnect_delay: Option<u64>,
+
+    /// Overrides the top-level `max_reconnect_attempts` for this source.
+    #[serde(default)]
+    pub max_reconnect_attempts: Option<u32>,
+
+    /// Overrides the top-level `reconnect_reset_after` for this source.
+    #[serde(default)]
+    pub reconnect_reset_after: Option<u64>,
+
+    /// Overrides the top-level `max_line_length` for this source.
+    #[serde(default)]
+    pub max_line_length: Option<usize>,
+}
 
 /// Configuration for spot storage.
 #[derive(Debug, Clone, Deserialize)]
@@ -32,6 +105,75 @@ impl Default for StorageConfig {
     }
 }
 
+/// Configuration for the band plan used to identify spot bands.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct BandPlanConfig {
+    /// IARU region whose band edges are used (1, 2 or 3; default 2).
+    pub region: IaruRegion,
+
+    /// Band edges that replace the region's defaults, or add bands.
+    pub bands: Vec<BandEdges>,
+
+    /// Segment data file replacing the bundled sub-band segments.
+    pub segment_file: Option<PathBuf>,
+}
+
+impl BandPlanConfig {
+    /// Build the band plan for the region with the overrides applied.
+    ///
+    /// Returns an error if the segment file can't be read or parsed.
+    pub fn build(&self) -> Result<BandPlan> {
+        let plan = self
+            .bands
+            .iter()
+            .fold(BandPlan::for_region(self.region), |plan, edges| {
+                plan.with_edges(*edges)
+            });
+        match self.segment_file {
+            Some(ref path) => plan.with_segment_file(path),
+            None => Ok(plan),
+        }
+    }
+}
+
+/// Configuration for per-skimmer frequency offset estimation.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CalibrationConfig {
+    /// How far apart in time (seconds) two spots of a signal may be and
+    /// still be compared.
+    pub window_secs: u64,
+
+    /// Other skimmers that must have heard a signal to form a consensus.
+    pub min_spotters: usize,
+
+    /// Deviations from the consensus larger than this (kHz) are ignored.
+    pub max_deviation_khz: f64,
+
+    /// Comparisons needed before a skimmer's offset is reported or applied.
+    pub min_samples: u64,
+
+    /// Number of recent comparisons the running offset averages over.
+    pub smoothing_samples: u64,
+
+    /// Add corrected frequencies to stored spots.
+    pub correct_stored_spots: bool,
+}
+
+impl Default for CalibrationConfig {
+    fn default() -> Self {
+        Self {
+            window_secs: 60,
+            min_spotters: 2,
+            max_deviation_khz: 0.5,
+            min_samples: 10,
+            smoothing_samples: 100,
+            correct_stored_spots: false,
+        }
+    }
+}
+
 /// Deserialize a human-readable size string like "10MB" into bytes.
 fn deserialize_size<'de, D>(deserializer: D) -> Result<usize, D::Error>
 where
@@ -81,12 +223,34 @@ pub struct Config {
     /// Callsign to use for RBN login.
     pub callsign: String,
 
-    /// RBN server hostname.
+    /// RBN server hostname, used when no `sources` are listed.
     pub host: String,
 
-    /// RBN server port.
+    /// RBN server port, used when no `sources` are listed.
     pub port: u16,
 
+    /// Endpoints to fail over to when `host` is down, used when no
+    /// `sources` are listed.
+    pub fallbacks: Vec<Endpoint>,
+
+    /// Seconds a more preferred endpoint must pass health probes before
+    /// switching back to it from a fallback.
+    pub failback_after: u64,
+
+    /// Seconds between health probes while on a fallback.
+    pub failback_probe_interval: u64,
+
+    /// Connect to `host` over TLS with these settings, used when no
+    /// `sources` are listed.
+    pub tls: Option<TlsConfig>,
+
+    /// Servers to stream from at once, merged into one feed.
+    pub sources: Vec<SourceConfig>,
+
+    /// Steps run after sending the callsign (expect a prompt, send a
+    /// command or password).
+    pub login_script: Vec<LoginStep>,
+
     /// Connection timeout in seconds.
     pub connect_timeout: u64,
 
@@ -96,6 +260,22 @@ pub struct Config {
     /// Whether to automatically reconnect on disconnect.
     pub reconnect: bool,
 
+    /// Initial delay before reconnecting, in seconds; doubles after each
+    /// failed attempt.
+    pub reconnect_delay: u64,
+
+    /// Maximum delay between reconnection attempts, in seconds.
+    pub max_reconnect_delay: u64,
+
+    /// Give up after this many reconnection attempts in a row (0 = never).
+    pub max_reconnect_attempts: u32,
+
+    /// Seconds a connection must stay up to reset the backoff.
+    pub reconnect_reset_after: u64,
+
+    /// Lines longer than this many bytes are dropped.
+    pub max_line_length: usize,
+
     /// Only count CW spots (ignore RTTY/digital).
     pub cw_only: bool,
 
@@ -113,6 +293,15 @@ pub struct Config {
 
     /// Optional storage configuration for keeping recent matched spots.
     pub storage: Option<StorageConfig>,
+
+    /// Path to a cty.dat or cty.csv country file for DXCC lookups.
+    pub cty_file: Option<PathBuf>,
+
+    /// Band plan region and band edge overrides.
+    pub band_plan: BandPlanConfig,
+
+    /// Optional per-skimmer frequency offset estimation.
+    pub calibration: Option<CalibrationConfig>,
 }
 
 impl Default for Config {
@@ -121,15 +310,29 @@ impl Default for Config {
             callsign: "N0CALL".to_string(),
             host: RBN_HOST.to_string(),
             port: RBN_PORT_CW,
+            fallbacks: Vec::new(),
+            failback_after: 300,
+            failback_probe_interval: 30,
+            tls: None,
+            sources: Vec::new(),
+            login_script: Vec::new(),
             connect_timeout: 30,
             read_timeout: 120,
             reconnect: true,
+            reconnect_delay: 5,
+            max_reconnect_delay: 300,
+            max_reconnect_attempts: 0,
+            reconnect_reset_after: 60,
+            max_line_length: DEFAULT_MAX_LINE_LENGTH,
             cw_only: true,
             stats_interval: 30,
             server_enabled: false,
             server_port: 9090,
             filters: Vec::new(),
             storage: None,
+            cty_file: None,
+            band_plan: BandPlanConfig::default(),
+            calibration: None,
         }
     }
 }
@@ -156,14 +359,225 @@ impl Config {
         dirs::config_dir().map(|p| p.join("rbn-parser/config.toml"))
     }
 
+    /// The sources to stream from: the `sources` list, or the top-level
+    /// `host`, `port` and `fallbacks` as a single source if none are listed.
+    pub fn effective_sources(&self) -> Vec<SourceConfig> {
+        if !self.sources.is_empty() {
+            return self.sources.clone();
+        }
+        vec![SourceConfig {
+            name: DEFAULT_SOURCE_NAME.to_string(),
+            host: self.host.clone(),
+            port: self.port,
+            fallbacks: self.fallbacks.clone(),
+            tls: self.tls.clone(),
+            callsign: None,
+            cw_only: None,
+            login_script: None,
+            connect_timeout: None,
+            read_timeout: None,
+            reconnect: None,
+            reconnect_delay: None,
+            max_reconnect_delay: None,
+            max_reconnect_attempts: None,
+            reconnect_reset_after: None,
+            max_line_length: None,
+        }]
+    }
+
+    /// Client settings for a source, filling in the top-level settings.
+    pub fn client_config(&self, source: &SourceConfig) -> RbnClientConfig {
+        let max_reconnect_attempts = source
+            .max_reconnect_attempts
+            .unwrap_or(self.max_reconnect_attempts);
+        RbnClientConfig {
+            name: source.name.clone(),
+            host: source.host.clone(),
+            port: source.port,
+            fallbacks: source.fallbacks.clone(),
+            failback_after: Duration::from_secs(self.failback_after),
+            failback_probe_interval: Duration::from_secs(self.failback_probe_interval),
+            tls: source.tls.clone(),
+            callsign: source
+                .callsign
+                .clone()
+                .unwrap_or_else(|| self.callsign.clone()),
+            connect_timeout: Duration::from_secs(
+                source.connect_timeout.unwrap_or(self.connect_timeout),
+            ),
+            read_timeout: Duration::from_secs(source.read_timeout.unwrap_or(self.read_timeout)),
+            auto_reconnect: source.reconnect.unwrap_or(self.reconnect),
+            reconnect_delay: Duration::from_secs(
+                source.reconnect_delay.unwrap_or(self.reconnect_delay),
+            ),
+            max_reconnect_delay: Duration::from_secs(
+                source
+                    .max_reconnect_delay
+                    .unwrap_or(self.max_reconnect_delay),
+            ),
+            max_reconnect_attempts: (max_reconnect_attempts > 0).then_some(max_reconnect_attempts),
+            reconnect_reset_after: Duration::from_secs(
+                source
+                    .reconnect_reset_after
+                    .unwrap_or(self.reconnect_reset_after),
+            ),
+            max_line_length: source.max_line_length.unwrap_or(self.max_line_length),
+            login_script: source
+                .login_script
+                .clone()
+                .unwrap_or_else(|| self.login_script.clone()),
+        }
+    }
+
+    /// Validate what is needed to connect to the sources, beyond
+    /// [`Config::validate`].
+    ///
+    /// Returns an error if a TLS CA file is missing, or a login script
+    /// password variable is not set.
+    pub fn validate_sources(&self) -> Result<()> {
+        for source in self.effective_sources() {
+            if let Some(path) = source.tls.as_ref().and_then(|tls| tls.ca_file.as_ref())
+                && !path.exists()
+            {
+                anyhow::bail!(
+                    "Invalid tls for {}: ca_file {} not found",
+                    source.name,
+                    path.display()
+                );
+            }
+
+            let script = source.login_script.as_ref().unwrap_or(&self.login_script);
+            for (j, step) in script.iter().enumerate() {
+                if let Some(ref var) = step.send_env
+                    && std::env::var_os(var).is_none()
+                {
+                    anyhow::bail!(
+                        "Invalid login_script step {} for {}: environment variable {} is not set",
+                        j + 1,
+                        source.name,
+                        var
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Validate all configuration settings.
     ///
-    /// Returns an error if any filters have invalid patterns.
+    /// Returns an error if any filters have invalid patterns, or match on
+    /// DXCC data without a `cty_file`, if any band plan override has
+    /// inverted edges, if source names are missing or repeated, if a
+    /// fallback has no host, if TLS settings are invalid, if a login script
+    /// step is invalid, or if the reconnect, line length, failback or
+    /// calibration settings (top-level or per source) are out of range.
+    ///
+    /// Settings only needed to connect are checked by
+    /// [`Config::validate_sources`], so offline commands work without them.
     pub fn validate(&self) -> Result<()> {
+        let sources = self.effective_sources();
+        for (i, source) in sources.iter().enumerate() {
+            if source.name.trim().is_empty() {
+                anyhow::bail!("Invalid source [{}]: name must not be empty", i);
+            }
+            if sources[..i].iter().any(|s| s.name == source.name) {
+                anyhow::bail!("Duplicate source name '{}'", source.name);
+            }
+            if let Some(j) = source
+                .fallbacks
+                .iter()
+                .position(|e| e.host.trim().is_empty())
+            {
+                anyhow::bail!(
+                    "Invalid fallback {} for {}: host must not be empty",
+                    j + 1,
+                    source.name
+                );
+            }
+            if let Some(ref tls) = source.tls {
+                tls.validate()
+                    .map_err(|e| anyhow::anyhow!("Invalid tls for {}: {}", source.name, e))?;
+            }
+
+            let script = source.login_script.as_ref().unwrap_or(&self.login_script);
+            for (j, step) in script.iter().enumerate() {
+                step.validate().map_err(|e| {
+                    anyhow::anyhow!(
+                        "Invalid login_script step {} for {}: {}",
+                        j + 1,
+                        source.name,
+                        e
+                    )
+                })?;
+            }
+
+            let reconnect_delay = source.reconnect_delay.unwrap_or(self.reconnect_delay);
+            let max_reconnect_delay = source
+                .max_reconnect_delay
+                .unwrap_or(self.max_reconnect_delay);
+            if reconnect_delay == 0 {
+                anyhow::bail!(
+                    "Invalid reconnect_delay for {}: must be at least 1 second",
+                    source.name
+                );
+            }
+            if max_reconnect_delay < reconnect_delay {
+                anyhow::bail!(
+                    "Invalid max_reconnect_delay for {}: {} is below reconnect_delay ({})",
+                    source.name,
+                    max_reconnect_delay,
+                    reconnect_delay
+                );
+            }
+            if source.max_line_length.unwrap_or(self.max_line_length) == 0 {
+                anyhow::bail!(
+                    "Invalid max_line_length for {}: must be at least 1 byte",
+                    source.name
+                );
+            }
+        }
+        if self.failback_after == 0 || self.failback_probe_interval == 0 {
+            anyhow::bail!("failback_after and failback_probe_interval must be at least 1 second");
+        }
+
+        if let Some(ref calibration) = self.calibration {
+            if calibration.min_spotters == 0 || calibration.smoothing_samples == 0 {
+                anyhow::bail!("calibration.min_spotters and smoothing_samples must be at least 1");
+            }
+            if calibration.max_deviation_khz <= 0.0 {
+                anyhow::bail!("calibration.max_deviation_khz must be positive");
+            }
+        }
+
+        for edges in &self.band_plan.bands {
+            if edges.start.hz() == 0 || edges.start >= edges.end {
+                anyhow::bail!(
+                    "Invalid band_plan edges for {}: {} - {} kHz",
+                    edges.band,
+                    edges.start,
+                    edges.end
+                );
+            }
+        }
+
         for (i, filter) in self.filters.iter().enumerate() {
             filter
                 .validate()
                 .map_err(|e| anyhow::anyhow!("Invalid filter [{}]: {}", i, e))?;
+            if let Some(name) = filter
+                .sources
+                .iter()
+                .flatten()
+                .find(|name| !sources.iter().any(|s| &s.name == *name))
+            {
+                anyhow::bail!("Invalid filter [{}]: unknown source '{}'", i, name);
+            }
+            if filter.uses_dxcc() && self.cty_file.is_none() {
+                anyhow::bail!(
+                    "Invalid filter [{}]: continent, entity and zone filters require cty_file",
+                    i
+                );
+            }
         }
         Ok(())
     }
@@ -204,6 +618,10 @@ mod tests {
             connect_timeout = 60
             read_timeout = 180
             reconnect = false
+            reconnect_delay = 10
+            max_reconnect_delay = 600
+            max_reconnect_attempts = 20
+            reconnect_reset_after = 120
             cw_only = false
             stats_interval = 60
             server_enabled = true
@@ -216,6 +634,10 @@ mod tests {
         assert_eq!(config.connect_timeout, 60);
         assert_eq!(config.read_timeout, 180);
         assert!(!config.reconnect);
+        assert_eq!(config.reconnect_delay, 10);
+        assert_eq!(config.max_reconnect_delay, 600);
+        assert_eq!(config.max_reconnect_attempts, 20);
+        assert_eq!(config.reconnect_reset_after, 120);
         assert!(!config.cw_only);
         assert_eq!(config.stats_interval, 60);
         assert!(config.server_enabled);
@@ -245,11 +667,376 @@ mod tests {
         );
         assert_eq!(
             config.filters[1].bands,
-            Some(vec!["20m".to_string(), "40m".to_string()])
+            Some(vec![crate::band::Band::B20m, crate::band::Band::B40m])
         );
         assert_eq!(config.filters[1].min_snr, Some(15));
     }
 
+    #[test]
+    fn test_dxcc_filters_require_cty_file() {
+        let toml = r#"
+            [[filters]]
+            dx_continents = ["EU"]
+        "#;
+        let mut config: Config = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_err());
+
+        config.cty_file = Some(PathBuf::from("/usr/share/cty/cty.dat"));
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_default_source() {
+        let config: Config = toml::from_str("callsign = \"W6JSV\"\nport = 7001").unwrap();
+        let sources = config.effective_sources();
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].name, DEFAULT_SOURCE_NAME);
+        assert_eq!(sources[0].host, RBN_HOST);
+
+        let client = config.client_config(&sources[0]);
+        assert_eq!(client.port, 7001);
+        assert_eq!(client.callsign, "W6JSV");
+    }
+
+    #[test]
+    fn test_parse_sources() {
+        let toml = r#"
+            callsign = "W6JSV"
+            reconnect_delay = 10
+
+            [[sources]]
+            name = "rbn-cw"
+            host = "telnet.reversebeacon.net"
+            port = 7000
+
+            [[sources]]
+            name = "rbn-digital"
+            host = "telnet.reversebeacon.net"
+            port = 7001
+            cw_only = false
+
+            [[sources]]
+            name = "skimmer"
+            host = "192.168.1.20"
+            port = 7300
+            callsign = "W6JSV-1"
+            fallbacks = [{ host = "192.168.1.21", port = 7300 }]
+
+            [[filters]]
+            sources = ["skimmer"]
+        "#;
+        let mut config: Config = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_ok());
+
+        let sources = config.effective_sources();
+        assert_eq!(sources.len(), 3);
+        assert_eq!(sources[1].cw_only, Some(false));
+
+        let skimmer = config.client_config(&sources[2]);
+        assert_eq!(skimmer.name, "skimmer");
+        assert_eq!(skimmer.host, "192.168.1.20");
+        assert_eq!(skimmer.callsign, "W6JSV-1");
+        assert_eq!(skimmer.reconnect_delay, Duration::from_secs(10));
+        assert_eq!(skimmer.fallbacks, vec![Endpoint::new("192.168.1.21", 7300)]);
+        assert_eq!(config.client_config(&sources[0]).callsign, "W6JSV");
+
+        config.filters[0].sources = Some(vec!["cluster".to_string()]);
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("unknown source 'cluster'"), "{}", err);
+
+        config.filters.clear();
+        config.sources[2].name = "rbn-cw".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_source_overrides() {
+        let toml = r#"
+            read_timeout = 120
+            reconnect_delay = 5
+            max_reconnect_attempts = 10
+
+            [[sources]]
+            name = "rbn"
+            host = "telnet.reversebeacon.net"
+            port = 7000
+
+            [[sources]]
+            name = "skimmer"
+            host = "192.168.1.20"
+            port = 7300
+            connect_timeout = 5
+            read_timeout = 600
+            reconnect = false
+            reconnect_delay = 1
+            max_reconnect_delay = 10
+            max_reconnect_attempts = 0
+            reconnect_reset_after = 30
+            max_line_length = 4096
+        "#;
+        let mut config: Config = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_ok());
+
+        let sources = config.effective_sources();
+        let rbn = config.client_config(&sources[0]);
+        assert_eq!(rbn.read_timeout, Duration::from_secs(120));
+        assert_eq!(rbn.reconnect_delay, Duration::from_secs(5));
+        assert_eq!(rbn.max_reconnect_attempts, Some(10));
+        assert_eq!(rbn.max_line_length, DEFAULT_MAX_LINE_LENGTH);
+        assert!(rbn.auto_reconnect);
+
+        let skimmer = config.client_config(&sources[1]);
+        assert_eq!(skimmer.connect_timeout, Duration::from_secs(5));
+        assert_eq!(skimmer.read_timeout, Duration::from_secs(600));
+        assert!(!skimmer.auto_reconnect);
+        assert_eq!(skimmer.reconnect_delay, Duration::from_secs(1));
+        assert_eq!(skimmer.max_reconnect_delay, Duration::from_secs(10));
+        assert_eq!(skimmer.max_reconnect_attempts, None);
+        assert_eq!(skimmer.reconnect_reset_after, Duration::from_secs(30));
+        assert_eq!(skimmer.max_line_length, 4096);
+
+        // Overrides are validated against the inherited settings
+        config.sources[1].max_reconnect_delay = None;
+        config.sources[1].reconnect_delay = Some(600);
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("max_reconnect_delay for skimmer"), "{}", err);
+
+        config.sources[1].reconnect_delay = Some(0);
+        assert!(config.validate().is_err());
+
+        config.sources[1].reconnect_delay = None;
+        config.sources[1].max_line_length = Some(0);
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("max_line_length for skimmer"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_fallbacks() {
+        let toml = r#"
+            callsign = "W6JSV"
+            fallbacks = [
+                { host = "rbn-mirror.example.net", port = 7000 },
+                { host = "192.168.1.20", port = 7300 },
+            ]
+            failback_after = 600
+        "#;
+        let mut config: Config = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_ok());
+
+        let client = config.client_config(&config.effective_sources()[0]);
+        assert_eq!(
+            client.endpoints(),
+            vec![
+                Endpoint::new(RBN_HOST, RBN_PORT_CW),
+                Endpoint::new("rbn-mirror.example.net", 7000),
+                Endpoint::new("192.168.1.20", 7300),
+            ]
+        );
+        assert_eq!(client.failback_after, Duration::from_secs(600));
+        assert_eq!(client.failback_probe_interval, Duration::from_secs(30));
+
+        config.fallbacks[1].host = String::new();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("Invalid fallback 2 for rbn"), "{}", err);
+
+        config.fallbacks.clear();
+        config.failback_probe_interval = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_parse_tls() {
+        let toml = r#"
+            callsign = "W6JSV"
+
+            [[sources]]
+            name = "rbn"
+            host = "telnet.reversebeacon.net"
+            port = 7000
+
+            [[sources]]
+            name = "node"
+            host = "192.168.1.30"
+            port = 7373
+            fallbacks = [{ host = "192.168.1.31", port = 7373 }]
+
+            [sources.tls]
+            server_name = "dxc.example.net"
+            pin_sha256 = ["AB:CD:EF:01:23:45:67:89:AB:CD:EF:01:23:45:67:89:AB:CD:EF:01:23:45:67:89:AB:CD:EF:01:23:45:67:89"]
+        "#;
+        let mut config: Config = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_ok());
+
+        let sources = config.effective_sources();
+        assert!(config.client_config(&sources[0]).tls.is_none());
+        let tls = config.client_config(&sources[1]).tls.unwrap();
+        assert_eq!(tls.server_name.as_deref(), Some("dxc.example.net"));
+        assert_eq!(tls.pin_sha256.len(), 1);
+
+        config.sources[1].tls.as_mut().unwrap().pin_sha256 = vec!["abcd".to_string()];
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("Invalid tls for node"), "{}", err);
+
+        config.sources[1].tls = Some(TlsConfig {
+            ca_file: Some(PathBuf::from("/nonexistent/ca.pem")),
+            ..Default::default()
+        });
+        assert!(config.validate().is_ok());
+        let err = config.validate_sources().unwrap_err().to_string();
+        assert!(
+            err.contains("ca_file /nonexistent/ca.pem not found"),
+            "{}",
+            err
+        );
+
+        // An empty table turns TLS on with the defaults
+        let config: Config = toml::from_str("[tls]").unwrap();
+        assert_eq!(
+            config.effective_sources()[0].tls,
+            Some(TlsConfig::default())
+        );
+    }
+
+    #[test]
+    fn test_parse_login_script() {
+        let toml = r#"
+            callsign = "W6JSV"
+
+            [[login_script]]
+            expect = "password:"
+            send_env = "RBN_PARSER_UNSET_TEST_PASSWORD"
+            timeout_secs = 10
+
+            [[login_script]]
+            expect = ">"
+            send = "set/skimmer"
+
+            [[sources]]
+            name = "rbn"
+            host = "telnet.reversebeacon.net"
+            port = 7000
+            login_script = []
+
+            [[sources]]
+            name = "node"
+            host = "dxc.example.net"
+            port = 7300
+        "#;
+        let mut config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.login_script.len(), 2);
+        assert_eq!(config.login_script[0].timeout_secs, Some(10));
+
+        let sources = config.effective_sources();
+        assert!(config.client_config(&sources[0]).login_script.is_empty());
+        assert_eq!(
+            config.client_config(&sources[1]).login_script,
+            config.login_script
+        );
+
+        // A missing password only matters when connecting
+        assert!(config.validate().is_ok());
+        let err = config.validate_sources().unwrap_err().to_string();
+        assert!(
+            err.contains("step 1 for node: environment variable RBN_PARSER_UNSET_TEST_PASSWORD"),
+            "{}",
+            err
+        );
+
+        config.login_script[0].send_env = None;
+        assert!(config.validate_sources().is_ok());
+        config.login_script[1].expect = Some(String::new());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_reconnect_delays() {
+        let mut config = Config::default();
+        assert!(config.validate().is_ok());
+
+        config.max_reconnect_delay = 2;
+        assert!(config.validate().is_err());
+
+        config.reconnect_delay = 0;
+        config.max_reconnect_delay = 300;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_parse_calibration() {
+        let config = Config::default();
+        assert!(config.calibration.is_none());
+
+        let toml = r#"
+            [calibration]
+            min_spotters = 3
+            correct_stored_spots = true
+        "#;
+        let mut config: Config = toml::from_str(toml).unwrap();
+        let calibration = config.calibration.as_ref().unwrap();
+        assert_eq!(calibration.min_spotters, 3);
+        assert_eq!(calibration.window_secs, 60);
+        assert!(calibration.correct_stored_spots);
+        assert!(config.validate().is_ok());
+
+        config.calibration.as_mut().unwrap().max_deviation_khz = 0.0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_parse_band_plan() {
+        use crate::band::Band;
+        use crate::frequency::Frequency;
+
+        let config = Config::default();
+        assert_eq!(
+            config
+                .band_plan
+                .build()
+                .unwrap()
+                .band_for(Frequency::from_khz(3950.0)),
+            Some(Band::B80m)
+        );
+
+        let toml = r#"
+            [band_plan]
+            region = 1
+
+            [[band_plan.bands]]
+            band = "40m"
+            start_khz = 7000
+            end_khz = 7300
+        "#;
+        let config: Config = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_ok());
+        let plan = config.band_plan.build().unwrap();
+        assert_eq!(plan.band_for(Frequency::from_khz(3950.0)), None);
+        assert_eq!(plan.band_for(Frequency::from_khz(7250.0)), Some(Band::B40m));
+
+        let toml = r#"
+            [[band_plan.bands]]
+            band = "40m"
+            start_khz = 7300
+            end_khz = 7000
+        "#;
+        let config: Config = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_err());
+
+        assert!(toml::from_str::<Config>("[band_plan]\nregion = 4").is_err());
+
+        let toml = r#"
+            [band_plan]
+            segment_file = "/nonexistent/segments.toml"
+        "#;
+        let config: Config = toml::from_str(toml).unwrap();
+        assert!(config.band_plan.build().is_err());
+        assert!(
+            toml::from_str::<Config>(
+                "[[band_plan.bands]]\nband = \"11m\"\nstart_khz = 26965\nend_khz = 27405"
+            )
+            .is_err()
+        );
+    }
+
     #[test]
     fn test_default_metrics_disabled() {
         let config = Config::default();
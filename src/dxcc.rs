@@ -0,0 +1,525 @@
+//! DXCC entity, continent and zone lookup from a local country file.
+//!
+//! Loads the standard `cty.dat` or `cty.csv` country files maintained by
+//! AD1C (<https://www.country-files.com>), including exact-call entries
+//! (`=W1AW`) and per-prefix overrides of CQ zone `(n)`, ITU zone `[n]`,
+//! lat/lon `<lat/lon>` and continent `{CC}`.
+//!
+//! Callsigns are resolved by exact match first, then by longest prefix.
+//! Portable calls resolve by their operating location (`EA8/DL1ABC` is in
+//! the Canary Islands), and `/MM` and `/AM` stations resolve to nothing.
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::spot::{Callsign, CallsignModifier};
+
+/// A continent as used in DX cluster and country files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Continent {
+    /// Africa
+    Af,
+    /// Antarctica
+    An,
+    /// Asia
+    As,
+    /// Europe
+    Eu,
+    /// North America
+    Na,
+    /// Oceania
+    Oc,
+    /// South America
+    Sa,
+}
+
+impl Continent {
+    /// Parse a two-letter continent code, case-insensitively.
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim().to_ascii_uppercase().as_str() {
+            "AF" => Some(Continent::Af),
+            "AN" => Some(Continent::An),
+            "AS" => Some(Continent::As),
+            "EU" => Some(Continent::Eu),
+            "NA" => Some(Continent::Na),
+            "OC" => Some(Continent::Oc),
+            "SA" => Some(Continent::Sa),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Continent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Continent::Af => write!(f, "AF"),
+            Continent::An => write!(f, "AN"),
+            Continent::As => write!(f, "AS"),
+            Continent::Eu => write!(f, "EU"),
+            Continent::Na => write!(f, "NA"),
+            Continent::Oc => write!(f, "OC"),
+            Continent::Sa => write!(f, "SA"),
+        }
+    }
+}
+
+/// Where a callsign is, as resolved from the country file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DxccInfo {
+    /// Entity name, e.g. "Fed. Rep. of Germany".
+    pub entity: Arc<str>,
+
+    /// Primary prefix of the entity, e.g. "DL".
+    pub prefix: Arc<str>,
+
+    /// ADIF DXCC entity number (only present in cty.csv).
+    pub dxcc: Option<u16>,
+
+    /// Continent.
+    pub continent: Continent,
+
+    /// CQ zone.
+    pub cq_zone: u8,
+
+    /// ITU zone.
+    pub itu_zone: u8,
+
+    /// Approximate latitude in degrees, north positive.
+    pub latitude: f64,
+
+    /// Approximate longitude in degrees, east positive.
+    pub longitude: f64,
+}
+
+impl DxccInfo {
+    /// Whether the entity name or primary prefix equals `name`
+    /// (case-insensitive).
+    pub fn is_entity(&self, name: &str) -> bool {
+        self.entity.eq_ignore_ascii_case(name) || self.prefix.eq_ignore_ascii_case(name)
+    }
+}
+
+/// An entity header from the country file.
+#[derive(Debug)]
+struct Entity {
+    name: Arc<str>,
+    prefix: Arc<str>,
+    dxcc: Option<u16>,
+}
+
+/// The values a prefix or exact call resolves to, after overrides.
+#[derive(Debug, Clone, Copy)]
+struct PrefixEntry {
+    entity: usize,
+    continent: Continent,
+    cq_zone: u8,
+    itu_zone: u8,
+    latitude: f64,
+    longitude: f64,
+}
+
+/// A loaded country file.
+#[derive(Debug, Default)]
+pub struct DxccDatabase {
+    entities: Vec<Entity>,
+    prefixes: HashMap<String, PrefixEntry>,
+    exact_calls: HashMap<String, PrefixEntry>,
+    max_prefix_len: usize,
+}
+
+impl DxccDatabase {
+    /// Load a country file from disk.
+    ///
+    /// Files ending in `.csv` are read as cty.csv, anything else as cty.dat.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read country file {}", path.display()))?;
+
+        let is_csv = path
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("csv"));
+        let result = if is_csv {
+            Self::parse_csv(&contents)
+        } else {
+            Self::parse_dat(&contents)
+        };
+        result.with_context(|| format!("Failed to parse country file {}", path.display()))
+    }
+
+    /// Parse the contents of a cty.dat file.
+    ///
+    /// Each entity is a colon-separated header followed by a comma-separated
+    /// prefix list terminated by `;`:
+    ///
+    /// ```text
+    /// Canary Islands:           33:  36:  AF:   28.32:    15.85:     0.0:  EA8:
+    ///     AM8,AN8,EA8,EB8,EC8,ED8,EE8,EF8,EG8,EH8;
+    /// ```
+    pub fn parse_dat(contents: &str) -> Result<Self> {
+        let mut db = Self::default();
+
+        for record in contents.split(';') {
+            let record = record.trim();
+            if record.is_empty() {
+                continue;
+            }
+
+            let fields: Vec<&str> = record.splitn(9, ':').collect();
+            if fields.len() != 9 {
+                bail!("Malformed entity: {}", first_line(record));
+            }
+
+            let (entity, base) = db
+                .push_entity(fields[0], fields[7], None, &fields[1..7])
+                .with_context(|| format!("In entity {}", fields[0].trim()))?;
+            for alias in fields[8].split(',') {
+                db.add_alias(alias, entity, base)?;
+            }
+        }
+
+        Ok(db)
+    }
+
+    /// Parse the contents of a cty.csv file.
+    ///
+    /// Each line holds one entity with a space-separated prefix list:
+    ///
+    /// ```text
+    /// EA8,Canary Islands,29,AF,33,36,28.32,15.85,0.0,AM8 AN8 EA8 EB8;
+    /// ```
+    pub fn parse_csv(contents: &str) -> Result<Self> {
+        let mut db = Self::default();
+
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.splitn(10, ',').collect();
+            if fields.len() != 10 {
+                bail!("Malformed line {}: {}", i + 1, line);
+            }
+
+            let dxcc = fields[2].trim().parse().ok();
+            // Continent comes before the zones here, unlike cty.dat
+            let header = [
+                fields[4], fields[5], fields[3], fields[6], fields[7], fields[8],
+            ];
+            let (entity, base) = db
+                .push_entity(fields[1], fields[0], dxcc, &header)
+                .with_context(|| format!("On line {}", i + 1))?;
+            for alias in fields[9].trim_end_matches(';').split_whitespace() {
+                db.add_alias(alias, entity, base)?;
+            }
+        }
+
+        Ok(db)
+    }
+
+    /// Number of entities loaded.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Whether no entities are loaded.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Resolve a callsign (DX call or spotter base call) to its entity.
+    pub fn resolve(&self, call: &str) -> Option<DxccInfo> {
+        let call = call.trim().to_ascii_uppercase();
+        if let Some(entry) = self.exact_calls.get(&call) {
+            return Some(self.info(entry));
+        }
+
+        let callsign = Callsign::parse(&call);
+        if callsign.has_modifier(CallsignModifier::MaritimeMobile)
+            || callsign.has_modifier(CallsignModifier::AeronauticalMobile)
+        {
+            return None;
+        }
+
+        // A bare call area digit (W6JSV/7) doesn't change the entity
+        let target = match callsign.location {
+            Some(location) if !(location.len() == 1 && location.as_bytes()[0].is_ascii_digit()) => {
+                location
+            }
+            _ => callsign.home,
+        };
+
+        if let Some(entry) = self.exact_calls.get(target) {
+            return Some(self.info(entry));
+        }
+
+        (1..=target.len().min(self.max_prefix_len))
+            .rev()
+            .filter(|&len| target.is_char_boundary(len))
+            .find_map(|len| self.prefixes.get(&target[..len]))
+            .map(|entry| self.info(entry))
+    }
+
+    fn info(&self, entry: &PrefixEntry) -> DxccInfo {
+        let entity = &self.entities[entry.entity];
+        DxccInfo {
+            entity: Arc::clone(&entity.name),
+            prefix: Arc::clone(&entity.prefix),
+            dxcc: entity.dxcc,
+            continent: entry.continent,
+            cq_zone: entry.cq_zone,
+            itu_zone: entry.itu_zone,
+            latitude: entry.latitude,
+            longitude: entry.longitude,
+        }
+    }
+
+    /// Add an entity from its name, primary prefix and the six
+    /// CQ, ITU, continent, lat, lon, UTC offset fields.
+    fn push_entity(
+        &mut self,
+        name: &str,
+        prefix: &str,
+        dxcc: Option<u16>,
+        fields: &[&str],
+    ) -> Result<(usize, PrefixEntry)> {
+        let number = |s: &str, what: &str| -> Result<f64> {
+            s.trim()
+                .parse()
+                .map_err(|_| anyhow!("Invalid {}: {}", what, s.trim()))
+        };
+
+        let continent = Continent::from_code(fields[2])
+            .ok_or_else(|| anyhow!("Invalid continent: {}", fields[2].trim()))?;
+        let base = PrefixEntry {
+            entity: self.entities.len(),
+            continent,
+            cq_zone: number(fields[0], "CQ zone")? as u8,
+            itu_zone: number(fields[1], "ITU zone")? as u8,
+            latitude: number(fields[3], "latitude")?,
+            // Country files give longitude west positive
+            longitude: -number(fields[4], "longitude")?,
+        };
+
+        // A leading '*' marks entities that only count for some awards
+        let prefix = prefix.trim().trim_start_matches('*');
+        self.entities.push(Entity {
+            name: name.trim().into(),
+            prefix: prefix.into(),
+            dxcc,
+        });
+        Ok((base.entity, base))
+    }
+
+    /// Add one prefix or `=CALL` alias with its overrides.
+    fn add_alias(&mut self, alias: &str, entity: usize, base: PrefixEntry) -> Result<()> {
+        let alias = alias.trim();
+        if alias.is_empty() {
+            return Ok(());
+        }
+
+        let (exact, alias) = match alias.strip_prefix('=') {
+            Some(call) => (true, call),
+            None => (false, alias),
+        };
+        let key_end = alias.find(['(', '[', '<', '{', '~']).unwrap_or(alias.len());
+        let key = alias[..key_end].to_ascii_uppercase();
+        let entry = apply_overrides(&alias[key_end..], PrefixEntry { entity, ..base })
+            .with_context(|| format!("Invalid alias {}", alias))?;
+
+        if exact {
+            self.exact_calls.insert(key, entry);
+        } else {
+            self.max_prefix_len = self.max_prefix_len.max(key.len());
+            self.prefixes.insert(key, entry);
+        }
+        Ok(())
+    }
+}
+
+/// Apply `(cq)`, `[itu]`, `<lat/lon>`, `{continent}` and `~utc~` overrides.
+fn apply_overrides(mut overrides: &str, mut entry: PrefixEntry) -> Result<PrefixEntry> {
+    while let Some(open) = overrides.chars().next() {
+        let close = match open {
+            '(' => ')',
+            '[' => ']',
+            '<' => '>',
+            '{' => '}',
+            '~' => '~',
+            _ => bail!("unexpected '{}'", open),
+        };
+        let end = overrides[1..]
+            .find(close)
+            .ok_or_else(|| anyhow!("unterminated '{}'", open))?
+            + 1;
+        let value = &overrides[1..end];
+
+        match open {
+            '(' => entry.cq_zone = value.parse()?,
+            '[' => entry.itu_zone = value.parse()?,
+            '<' => {
+                let (lat, lon) = value
+                    .split_once('/')
+                    .ok_or_else(|| anyhow!("invalid lat/lon"))?;
+                entry.latitude = lat.parse()?;
+                entry.longitude = -lon.parse::<f64>()?;
+            }
+            '{' => {
+                entry.continent =
+                    Continent::from_code(value).ok_or_else(|| anyhow!("invalid continent"))?;
+            }
+            // UTC offset isn't tracked
+            _ => {}
+        }
+
+        overrides = &overrides[end + 1..];
+    }
+
+    Ok(entry)
+}
+
+/// First line of a record, for error messages.
+fn first_line(record: &str) -> &str {
+    record.lines().next().unwrap_or(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CTY_DAT: &str = "\
+Fed. Rep. of Germany:     14:  28:  EU:   51.00:   -10.00:    -1.0:  DL:
+    DA,DB,DC,DD,DE,DF,DG,DH,DI,DJ,DK,DL,DM,DN,DO,DP,DQ,DR,Y2,Y3,Y4,Y5,Y6,Y7,Y8,Y9,
+    =DL0XYZ(15)[29];
+United States:            05:  08:  NA:   37.53:    91.67:     5.0:  K:
+    AA,AB,AC,AD,AE,AF,AG,AI,AJ,AK,K,N,W,
+    AA6(3)[6],K6(3)[6],N6(3)[6],W6(3)[6]<37.0/120.0>,
+    =W1AW(5)[8];
+Hawaii:                   31:  61:  OC:   21.12:   157.48:    10.0:  KH6:
+    AH6,AH7,KH6,KH7,NH6,NH7,WH6,WH7;
+Spain:                    14:  37:  EU:   40.37:     4.88:    -1.0:  EA:
+    AM,AN,AO,EA,EB,EC,ED,EE,EF,EG,EH;
+Canary Islands:           33:  36:  AF:   28.32:    15.85:     0.0:  EA8:
+    AM8,AN8,EA8,EB8,EC8,ED8,EE8,EF8,EG8,EH8;
+Antarctica:               13:  74:  SA:  -90.00:     0.00:     0.0:  CE9:
+    3Y[73],CE9,KC4~-12.0~,=VP8PJ{SA};
+";
+
+    const CTY_CSV: &str = "\
+DL,Fed. Rep. of Germany,230,EU,14,28,51.00,-10.00,-1.0,DA DB DC DD DE DF DG DH DI DJ DK DL DM DN DO DP DQ DR =DL0XYZ(15)[29];
+EA8,Canary Islands,29,AF,33,36,28.32,15.85,0.0,AM8 AN8 EA8 EB8 EC8 ED8 EE8 EF8 EG8 EH8;
+*TA1,European Turkey,390,EU,20,39,41.02,-28.97,-2.0,TA1 TB1 TC1 YM1;
+";
+
+    fn db() -> DxccDatabase {
+        DxccDatabase::parse_dat(CTY_DAT).unwrap()
+    }
+
+    #[test]
+    fn test_parse_dat() {
+        let db = db();
+        assert_eq!(db.len(), 6);
+
+        let info = db.resolve("DK8NE").unwrap();
+        assert_eq!(&*info.entity, "Fed. Rep. of Germany");
+        assert_eq!(&*info.prefix, "DL");
+        assert_eq!(info.continent, Continent::Eu);
+        assert_eq!(info.cq_zone, 14);
+        assert_eq!(info.itu_zone, 28);
+        assert_eq!(info.latitude, 51.0);
+        assert_eq!(info.longitude, 10.0);
+        assert_eq!(info.dxcc, None);
+    }
+
+    #[test]
+    fn test_longest_prefix_wins() {
+        let db = db();
+        assert_eq!(&*db.resolve("EA5WU").unwrap().entity, "Spain");
+        assert_eq!(&*db.resolve("EA8AH").unwrap().entity, "Canary Islands");
+        assert_eq!(&*db.resolve("KH6XX").unwrap().entity, "Hawaii");
+        assert_eq!(&*db.resolve("K1ABC").unwrap().entity, "United States");
+        assert!(db.resolve("JA1XYZ").is_none());
+    }
+
+    #[test]
+    fn test_zone_and_location_overrides() {
+        let db = db();
+
+        let w6 = db.resolve("W6JSV").unwrap();
+        assert_eq!((w6.cq_zone, w6.itu_zone), (3, 6));
+        assert_eq!((w6.latitude, w6.longitude), (37.0, -120.0));
+
+        let k1 = db.resolve("K1ABC").unwrap();
+        assert_eq!((k1.cq_zone, k1.itu_zone), (5, 8));
+
+        let antarctica = db.resolve("3Y0X").unwrap();
+        assert_eq!(antarctica.itu_zone, 73);
+        assert_eq!(db.resolve("KC4AAA").unwrap().itu_zone, 74);
+    }
+
+    #[test]
+    fn test_exact_call_overrides() {
+        let db = db();
+
+        let info = db.resolve("DL0XYZ").unwrap();
+        assert_eq!((info.cq_zone, info.itu_zone), (15, 29));
+        // A longer call with the same start is a regular prefix match
+        assert_eq!(db.resolve("DL0XYZA").unwrap().cq_zone, 14);
+
+        let info = db.resolve("vp8pj").unwrap();
+        assert_eq!(&*info.entity, "Antarctica");
+        assert_eq!(info.continent, Continent::Sa);
+    }
+
+    #[test]
+    fn test_portable_calls() {
+        let db = db();
+
+        assert_eq!(&*db.resolve("EA8/DL1ABC").unwrap().entity, "Canary Islands");
+        assert_eq!(
+            &*db.resolve("DL1ABC/EA8/P").unwrap().entity,
+            "Canary Islands"
+        );
+        assert_eq!(&*db.resolve("W6JSV/P").unwrap().entity, "United States");
+        assert_eq!(db.resolve("W6JSV/7").unwrap().cq_zone, 3);
+        assert!(db.resolve("DL1ABC/MM").is_none());
+        assert!(db.resolve("G4XYZ/AM").is_none());
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let db = DxccDatabase::parse_csv(CTY_CSV).unwrap();
+        assert_eq!(db.len(), 3);
+
+        let info = db.resolve("EA8AH").unwrap();
+        assert_eq!(&*info.entity, "Canary Islands");
+        assert_eq!(info.dxcc, Some(29));
+        assert_eq!(info.continent, Continent::Af);
+        assert_eq!(info.longitude, -15.85);
+
+        assert_eq!(db.resolve("DL0XYZ").unwrap().cq_zone, 15);
+        assert_eq!(&*db.resolve("TA1ABC").unwrap().prefix, "TA1");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(DxccDatabase::parse_dat("Nowhere: 1: 2: XX: 0: 0: 0: X:\n X;").is_err());
+        assert!(DxccDatabase::parse_dat("Nowhere: 1: 2:\n X;").is_err());
+        assert!(DxccDatabase::parse_dat("Nowhere: 1: 2: EU: 0: 0: 0: X:\n X(1;").is_err());
+        assert!(DxccDatabase::parse_csv("X,Nowhere,1,EU\n").is_err());
+    }
+
+    #[test]
+    fn test_is_entity() {
+        let info = db().resolve("EA8AH").unwrap();
+        assert!(info.is_entity("canary islands"));
+        assert!(info.is_entity("EA8"));
+        assert!(!info.is_entity("EA"));
+    }
+}
@@ -0,0 +1,206 @@
+//! Ordered endpoint failover with health tracking.
+//!
+//! A source can list mirrors or a local aggregator after its preferred
+//! server. [`Endpoints`] keeps track of which one is in use: a failed
+//! connect or login moves on to the next endpoint in order, and an endpoint
+//! ahead of the active one that has passed health probes for long enough
+//! is switched back to.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// A server address.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct Endpoint {
+    /// Hostname or IP address.
+    pub host: String,
+
+    /// Port number.
+    pub port: u16,
+}
+
+impl Endpoint {
+    /// Create an endpoint.
+    pub fn new(host: impl Into<String>, port: u16) -> Self {
+        Self {
+            host: host.into(),
+            port,
+        }
+    }
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.host, self.port)
+    }
+}
+
+/// An ordered list of endpoints, most preferred first, with the one in use.
+#[derive(Debug, Clone)]
+pub struct Endpoints {
+    endpoints: Vec<Endpoint>,
+    /// When each endpoint last became reachable, None after a failure.
+    healthy_since: Vec<Option<Instant>>,
+    active: usize,
+    /// Connect or login failures in a row, across all endpoints.
+    failures: usize,
+}
+
+impl Endpoints {
+    /// Create a list starting on the first (preferred) endpoint.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `endpoints` is empty.
+    pub fn new(endpoints: Vec<Endpoint>) -> Self {
+        assert!(!endpoints.is_empty(), "at least one endpoint is required");
+        Self {
+            healthy_since: vec![None; endpoints.len()],
+            endpoints,
+            active: 0,
+            failures: 0,
+        }
+    }
+
+    /// The endpoint in use.
+    pub fn active(&self) -> &Endpoint {
+        &self.endpoints[self.active]
+    }
+
+    /// Position of the endpoint in use, 0 for the preferred one.
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    /// The endpoints more preferred than the one in use.
+    pub fn preferred(&self) -> &[Endpoint] {
+        &self.endpoints[..self.active]
+    }
+
+    /// Record a successful login to the endpoint in use.
+    pub fn record_success(&mut self, now: Instant) {
+        self.failures = 0;
+        self.healthy_since[self.active].get_or_insert(now);
+    }
+
+    /// Record a connect or login failure of the endpoint in use and move
+    /// on to the next one, wrapping around to the preferred endpoint.
+    ///
+    /// Returns true once every endpoint has been tried since the last
+    /// successful login, i.e. it is time to back off rather than fail over.
+    pub fn record_failure(&mut self) -> bool {
+        self.healthy_since[self.active] = None;
+        self.active = (self.active + 1) % self.endpoints.len();
+        self.failures += 1;
+        self.failures >= self.endpoints.len()
+    }
+
+    /// Record the result of a health probe of the endpoint at `index`.
+    pub fn record_probe(&mut self, index: usize, reachable: bool, now: Instant) {
+        if reachable {
+            self.healthy_since[index].get_or_insert(now);
+        } else {
+            self.healthy_since[index] = None;
+        }
+    }
+
+    /// The most preferred endpoint ahead of the one in use that has been
+    /// healthy for at least `after`.
+    pub fn failback_target(&self, after: Duration, now: Instant) -> Option<usize> {
+        self.healthy_since[..self.active].iter().position(|since| {
+            since.is_some_and(|since| now.saturating_duration_since(since) >= after)
+        })
+    }
+
+    /// Switch to the endpoint at `index`.
+    pub fn switch_to(&mut self, index: usize) {
+        self.active = index;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoints() -> Endpoints {
+        Endpoints::new(vec![
+            Endpoint::new("telnet.reversebeacon.net", 7000),
+            Endpoint::new("mirror.example.net", 7000),
+            Endpoint::new("192.168.1.20", 7300),
+        ])
+    }
+
+    #[test]
+    fn test_endpoint_display() {
+        assert_eq!(
+            Endpoint::new("telnet.reversebeacon.net", 7000).to_string(),
+            "telnet.reversebeacon.net:7000"
+        );
+    }
+
+    #[test]
+    fn test_rotate_on_failure() {
+        let mut endpoints = endpoints();
+        assert_eq!(endpoints.active().host, "telnet.reversebeacon.net");
+
+        assert!(!endpoints.record_failure());
+        assert_eq!(endpoints.active().host, "mirror.example.net");
+        assert!(!endpoints.record_failure());
+        assert_eq!(endpoints.active().host, "192.168.1.20");
+
+        // All three tried: wrap around to the preferred endpoint
+        assert!(endpoints.record_failure());
+        assert_eq!(endpoints.active_index(), 0);
+        assert!(endpoints.record_failure());
+
+        // After a successful login, the others are tried again first
+        endpoints.record_success(Instant::now());
+        assert_eq!(endpoints.active_index(), 1);
+        assert!(!endpoints.record_failure());
+        assert!(!endpoints.record_failure());
+        assert!(endpoints.record_failure());
+    }
+
+    #[test]
+    fn test_single_endpoint() {
+        let mut endpoints = Endpoints::new(vec![Endpoint::new("localhost", 7000)]);
+        assert!(endpoints.record_failure());
+        assert_eq!(endpoints.active_index(), 0);
+        assert!(endpoints.preferred().is_empty());
+    }
+
+    #[test]
+    fn test_failback_after_healthy_period() {
+        let start = Instant::now();
+        let after = Duration::from_secs(300);
+        let mut endpoints = endpoints();
+        endpoints.record_failure();
+        endpoints.record_failure();
+        endpoints.record_success(start);
+        assert_eq!(endpoints.preferred().len(), 2);
+        assert_eq!(endpoints.failback_target(after, start), None);
+
+        // The preferred endpoint comes back, but drops out again
+        endpoints.record_probe(0, true, start);
+        endpoints.record_probe(0, false, start + Duration::from_secs(200));
+        endpoints.record_probe(1, true, start + Duration::from_secs(200));
+        assert_eq!(
+            endpoints.failback_target(after, start + Duration::from_secs(400)),
+            None
+        );
+        assert_eq!(
+            endpoints.failback_target(after, start + Duration::from_secs(500)),
+            Some(1)
+        );
+
+        // Once both are healthy long enough, the preferred one wins
+        endpoints.record_probe(0, true, start + Duration::from_secs(350));
+        assert_eq!(
+            endpoints.failback_target(after, start + Duration::from_secs(650)),
+            Some(0)
+        );
+        endpoints.switch_to(0);
+        assert_eq!(endpoints.failback_target(after, start), None);
+    }
+}
@@ -7,7 +7,10 @@ use serde::Deserialize;
 use serde::de::{self, Deserializer, Visitor};
 use std::fmt;
 
-use crate::spot::{CwSpot, Mode, SpotType};
+use crate::band::{Band, Segment};
+use crate::dxcc::{Continent, DxccInfo};
+use crate::frequency::Frequency;
+use crate::spot::{CallsignModifier, CwSpot, CwSpotRef, Mode, SpotType, SpotTypeRef};
 
 /// A list of patterns that deserializes from either a string or array.
 ///
@@ -85,10 +88,50 @@ pub struct SpotFilter {
     /// Accepts a single string or array of strings (OR logic within array).
     pub dx_call: Option<PatternList>,
 
+    /// DX home callsign patterns, ignoring any location prefix and
+    /// modifiers (`"W6JSV"` matches `W6JSV/P` and `KH6/W6JSV`).
+    pub dx_home_call: Option<PatternList>,
+
+    /// DX operating-location patterns, e.g. `"EA8"` for `EA8/DL1ABC`.
+    /// Calls without a location never match.
+    pub dx_location: Option<PatternList>,
+
+    /// DX callsign modifiers to match (any of), e.g. `["MM"]` for all
+    /// maritime mobile stations.
+    pub dx_modifiers: Option<Vec<CallsignModifier>>,
+
     /// Spotter callsign patterns (supports `*` wildcard for prefix/suffix).
     /// Accepts a single string or array of strings (OR logic within array).
+    /// A pattern matches either the full spotter (`KM3T-2-#`) or its base
+    /// callsign, so `"KM3T"` covers every KM3T skimmer.
     pub spotter: Option<PatternList>,
 
+    /// DX continents to match (e.g., "EU", "NA"). Requires `cty_file`.
+    pub dx_continents: Option<Vec<Continent>>,
+
+    /// DX entities to match, by name or primary prefix (e.g., "Japan",
+    /// "EA8"). Requires `cty_file`.
+    pub dx_entities: Option<Vec<String>>,
+
+    /// DX CQ zones to match. Requires `cty_file`.
+    pub dx_cq_zones: Option<Vec<u8>>,
+
+    /// DX ITU zones to match. Requires `cty_file`.
+    pub dx_itu_zones: Option<Vec<u8>>,
+
+    /// Spotter continents to match. Requires `cty_file`.
+    pub spotter_continents: Option<Vec<Continent>>,
+
+    /// Spotter entities to match, by name or primary prefix.
+    /// Requires `cty_file`.
+    pub spotter_entities: Option<Vec<String>>,
+
+    /// Spotter CQ zones to match. Requires `cty_file`.
+    pub spotter_cq_zones: Option<Vec<u8>>,
+
+    /// Spotter ITU zones to match. Requires `cty_file`.
+    pub spotter_itu_zones: Option<Vec<u8>>,
+
     /// URL to Ham2K PoLo notes file for loading callsigns.
     /// Mutually exclusive with dx_call.
     pub polo_notes_url: Option<String>,
@@ -96,8 +139,19 @@ pub struct SpotFilter {
     /// Refresh interval for PoLo notes in seconds (default 1800 = 30 min, 0 = no refresh).
     pub polo_refresh_secs: Option<u64>,
 
-    /// Bands to match (e.g., "20m", "40m").
-    pub bands: Option<Vec<String>>,
+    /// Bands to match (e.g., "20m", "40m", "70cm"). Unknown names are
+    /// rejected when the filter is parsed.
+    pub bands: Option<Vec<Band>>,
+
+    /// Band-plan segments to match (any of), e.g. `["cw"]` to skip the
+    /// beacon and digital segments.
+    pub segments: Option<Vec<Segment>>,
+
+    /// Lowest frequency to match, in kHz.
+    pub min_freq_khz: Option<Frequency>,
+
+    /// Highest frequency to match, in kHz.
+    pub max_freq_khz: Option<Frequency>,
 
     /// Modes to match.
     pub modes: Option<Vec<Mode>>,
@@ -105,18 +159,28 @@ pub struct SpotFilter {
     /// Spot types to match.
     pub spot_types: Option<Vec<SpotType>>,
 
-    /// Minimum SNR in dB.
+    /// Minimum SNR in dB (spots without an SNR never match).
     pub min_snr: Option<i32>,
 
-    /// Maximum SNR in dB.
+    /// Maximum SNR in dB (spots without an SNR never match).
     pub max_snr: Option<i32>,
 
-    /// Minimum WPM.
+    /// Minimum WPM (spots without a WPM speed never match).
     pub min_wpm: Option<u16>,
 
-    /// Maximum WPM.
+    /// Maximum WPM (spots without a WPM speed never match).
     pub max_wpm: Option<u16>,
 
+    /// Minimum BPS (spots without a BPS speed never match).
+    pub min_bps: Option<u16>,
+
+    /// Maximum BPS (spots without a BPS speed never match).
+    pub max_bps: Option<u16>,
+
+    /// Source names to match (any of), from `[[sources]]`. Untagged spots
+    /// never match.
+    pub sources: Option<Vec<String>>,
+
     /// Maximum number of spots to keep in storage for this filter.
     /// Overrides `default_max_kept_entries` from `[storage]` config.
     pub max_kept_entries: Option<usize>,
@@ -127,64 +191,131 @@ impl SpotFilter {
     ///
     /// All specified fields must match (AND logic).
     pub fn matches(&self, spot: &CwSpot) -> bool {
+        self.matches_ref(&spot.as_spot_ref())
+    }
+
+    /// Check if a borrowed spot matches this filter.
+    ///
+    /// Same as [`SpotFilter::matches`], but works on the zero-copy form
+    /// returned by [`parse_spot_ref`](crate::parser::parse_spot_ref), so
+    /// non-matching spots never need to be allocated.
+    pub fn matches_ref(&self, spot: &CwSpotRef<'_>) -> bool {
         // Check dx_call patterns (OR logic within array)
         if let Some(ref patterns) = self.dx_call
             && !patterns.is_empty()
-            && !patterns.matches_any(&spot.dx_call)
+            && !patterns.matches_any(spot.dx_call)
         {
             return false;
         }
 
-        // Check spotter patterns (OR logic within array)
+        // Check the parts of the DX call
+        if self.dx_home_call.is_some() || self.dx_location.is_some() || self.dx_modifiers.is_some()
+        {
+            let callsign = spot.dx_callsign();
+
+            if let Some(ref patterns) = self.dx_home_call
+                && !patterns.is_empty()
+                && !patterns.matches_any(callsign.home)
+            {
+                return false;
+            }
+
+            if let Some(ref patterns) = self.dx_location
+                && !patterns.is_empty()
+                && !callsign.location.is_some_and(|l| patterns.matches_any(l))
+            {
+                return false;
+            }
+
+            if let Some(ref modifiers) = self.dx_modifiers
+                && !modifiers.iter().any(|m| callsign.has_modifier(*m))
+            {
+                return false;
+            }
+        }
+
+        // Check spotter patterns against the full call or the base station
         if let Some(ref patterns) = self.spotter
             && !patterns.is_empty()
-            && !patterns.matches_any(&spot.spotter)
+            && !patterns.matches_any(spot.spotter)
+            && !patterns.matches_any(spot.spotter_id().base)
         {
             return false;
         }
 
+        // Check where the DX station and spotter are
+        if !matches_dxcc(
+            spot.dx_dxcc,
+            &self.dx_continents,
+            &self.dx_entities,
+            &self.dx_cq_zones,
+            &self.dx_itu_zones,
+        ) || !matches_dxcc(
+            spot.spotter_dxcc,
+            &self.spotter_continents,
+            &self.spotter_entities,
+            &self.spotter_cq_zones,
+            &self.spotter_itu_zones,
+        ) {
+            return false;
+        }
+
         // Check bands
         if let Some(ref bands) = self.bands {
             match spot.band() {
-                Some(band) if bands.iter().any(|b| b.eq_ignore_ascii_case(band)) => {}
+                Some(band) if bands.contains(&band) => {}
                 _ => return false,
             }
         }
 
+        // Check band-plan segments
+        if let Some(ref segments) = self.segments
+            && !spot.segments().iter().any(|s| segments.contains(s))
+        {
+            return false;
+        }
+
+        // Check frequency range
+        if !in_range(Some(spot.frequency), self.min_freq_khz, self.max_freq_khz) {
+            return false;
+        }
+
         // Check modes
         if let Some(ref modes) = self.modes
-            && !modes.contains(&spot.mode)
+            && !modes.iter().any(|mode| *mode == spot.mode)
         {
             return false;
         }
 
         // Check spot types
         if let Some(ref spot_types) = self.spot_types
-            && !spot_types.contains(&spot.spot_type)
+            && !spot_types
+                .iter()
+                .any(|spot_type| matches_spot_type(spot_type, &spot.spot_type))
         {
             return false;
         }
 
         // Check SNR range
-        if let Some(min_snr) = self.min_snr
-            && spot.snr_db < min_snr
-        {
+        if !in_range(spot.snr_db, self.min_snr, self.max_snr) {
             return false;
         }
-        if let Some(max_snr) = self.max_snr
-            && spot.snr_db > max_snr
-        {
+
+        // Check WPM range
+        if !in_range(spot.wpm(), self.min_wpm, self.max_wpm) {
             return false;
         }
 
-        // Check WPM range
-        if let Some(min_wpm) = self.min_wpm
-            && spot.wpm < min_wpm
-        {
+        // Check BPS range
+        if !in_range(spot.bps(), self.min_bps, self.max_bps) {
             return false;
         }
-        if let Some(max_wpm) = self.max_wpm
-            && spot.wpm > max_wpm
+
+        // Check the feed the spot came from
+        if let Some(ref sources) = self.sources
+            && !spot
+                .source
+                .is_some_and(|s| sources.iter().any(|name| name == s))
         {
             return false;
         }
@@ -230,6 +361,18 @@ impl SpotFilter {
         self.matches(spot) && self.matches_polo(spot, polo_manager)
     }
 
+    /// Whether this filter matches on DXCC data, which needs a country file.
+    pub fn uses_dxcc(&self) -> bool {
+        self.dx_continents.is_some()
+            || self.dx_entities.is_some()
+            || self.dx_cq_zones.is_some()
+            || self.dx_itu_zones.is_some()
+            || self.spotter_continents.is_some()
+            || self.spotter_entities.is_some()
+            || self.spotter_cq_zones.is_some()
+            || self.spotter_itu_zones.is_some()
+    }
+
     /// Validate the filter configuration.
     ///
     /// Returns an error if any patterns are invalid.
@@ -249,6 +392,15 @@ impl SpotFilter {
             return Err(format!("polo_notes_url must be an HTTP(S) URL: {}", url));
         }
 
+        if let (Some(min), Some(max)) = (self.min_freq_khz, self.max_freq_khz)
+            && min > max
+        {
+            return Err(format!(
+                "min_freq_khz ({}) is above max_freq_khz ({})",
+                min, max
+            ));
+        }
+
         if let Some(ref patterns) = self.dx_call {
             for pattern in patterns.patterns() {
                 validate_wildcard_pattern(pattern)?;
@@ -259,6 +411,14 @@ impl SpotFilter {
                 validate_wildcard_pattern(pattern)?;
             }
         }
+        for patterns in [&self.dx_home_call, &self.dx_location]
+            .into_iter()
+            .flatten()
+        {
+            for pattern in patterns.patterns() {
+                validate_wildcard_pattern(pattern)?;
+            }
+        }
         Ok(())
     }
 }
@@ -271,6 +431,62 @@ pub fn any_filter_matches(filters: &[SpotFilter], spot: &CwSpot) -> bool {
     filters.iter().any(|f| f.matches(spot))
 }
 
+/// Check an optional spot value against optional min/max bounds.
+///
+/// A spot without the value (e.g., no WPM on an RTTY spot, or no SNR on a
+/// human cluster spot) fails any configured bound.
+fn in_range<T: PartialOrd + Copy>(value: Option<T>, min: Option<T>, max: Option<T>) -> bool {
+    if min.is_none() && max.is_none() {
+        return true;
+    }
+    let Some(value) = value else {
+        return false;
+    };
+    min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max)
+}
+
+/// Check resolved DXCC data against optional continent, entity and zone lists.
+///
+/// An unresolved callsign fails any configured list.
+fn matches_dxcc(
+    info: Option<&DxccInfo>,
+    continents: &Option<Vec<Continent>>,
+    entities: &Option<Vec<String>>,
+    cq_zones: &Option<Vec<u8>>,
+    itu_zones: &Option<Vec<u8>>,
+) -> bool {
+    if continents.is_none() && entities.is_none() && cq_zones.is_none() && itu_zones.is_none() {
+        return true;
+    }
+    let Some(info) = info else {
+        return false;
+    };
+
+    continents
+        .as_ref()
+        .is_none_or(|c| c.contains(&info.continent))
+        && entities
+            .as_ref()
+            .is_none_or(|e| e.iter().any(|name| info.is_entity(name)))
+        && cq_zones.as_ref().is_none_or(|z| z.contains(&info.cq_zone))
+        && itu_zones
+            .as_ref()
+            .is_none_or(|z| z.contains(&info.itu_zone))
+}
+
+/// Match a configured spot type against a spot's type.
+///
+/// `OTHER` matches any type token this crate doesn't know; any other
+/// unrecognized name matches only that token.
+fn matches_spot_type(wanted: &SpotType, spot_type: &SpotTypeRef<'_>) -> bool {
+    match wanted {
+        SpotType::Other(name) if name.eq_ignore_ascii_case("OTHER") => {
+            matches!(spot_type, SpotTypeRef::Other(_))
+        }
+        _ => *wanted == *spot_type,
+    }
+}
+
 /// Match a string against a wildcard pattern.
 ///
 /// Supports `*` as prefix or suffix wildcard (not both).
@@ -317,18 +533,25 @@ fn validate_wildcard_pattern(pattern: &str) -> Result<(), String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::spot::Speed;
     use chrono::NaiveTime;
 
     fn make_spot(dx_call: &str, spotter: &str, freq: f64, snr: i32, wpm: u16) -> CwSpot {
         CwSpot {
             spotter: spotter.to_string(),
-            frequency_khz: freq,
+            frequency: Frequency::from_khz(freq),
             dx_call: dx_call.to_string(),
             mode: Mode::Cw,
-            snr_db: snr,
-            wpm,
+            snr_db: Some(snr),
+            speed: Some(Speed::Wpm(wpm)),
             spot_type: SpotType::Cq,
             time: NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+            comment: None,
+            received_at: None,
+            timestamp: None,
+            dx_dxcc: None,
+            spotter_dxcc: None,
+            source: None,
         }
     }
 
@@ -373,10 +596,37 @@ mod tests {
         assert!(!filter.matches(&make_spot("K1ABC", "EA5WU-#", 14025.0, 15, 20)));
     }
 
+    #[test]
+    fn test_filter_matches_ref() {
+        use crate::parser::parse_spot_ref;
+
+        let filter: SpotFilter = toml::from_str(
+            r#"
+            dx_call = "RW*"
+            bands = ["40m"]
+            min_snr = 15
+        "#,
+        )
+        .unwrap();
+
+        let hit = parse_spot_ref(
+            "DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z",
+        )
+        .unwrap();
+        let miss = parse_spot_ref(
+            "DX de LZ4UX-#:    7018.3  RW1M           CW    13 dB  18 WPM  CQ      2259Z",
+        )
+        .unwrap();
+
+        assert!(filter.matches_ref(&hit));
+        assert!(!filter.matches_ref(&miss));
+        assert!(filter.matches(&hit.into_owned()));
+    }
+
     #[test]
     fn test_filter_band() {
         let filter = SpotFilter {
-            bands: Some(vec!["20m".to_string(), "40m".to_string()]),
+            bands: Some(vec![Band::B20m, Band::B40m]),
             ..Default::default()
         };
 
@@ -388,6 +638,103 @@ mod tests {
         assert!(!filter.matches(&make_spot("W6JSV", "EA5WU-#", 21025.0, 15, 20)));
     }
 
+    #[test]
+    fn test_filter_band_names() {
+        let filter: SpotFilter = toml::from_str(r#"bands = ["20M", "70cm"]"#).unwrap();
+        assert_eq!(filter.bands, Some(vec![Band::B20m, Band::B70cm]));
+        assert!(filter.matches(&make_spot("W6JSV", "EA5WU-#", 14025.0, 15, 20)));
+        assert!(filter.matches(&make_spot("W6JSV", "EA5WU-#", 432100.0, 15, 20)));
+
+        let err = toml::from_str::<SpotFilter>(r#"bands = ["20m", "21m"]"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown band '21m'"), "{}", err);
+    }
+
+    #[test]
+    fn test_filter_segments() {
+        let filter: SpotFilter = toml::from_str(r#"segments = ["cw", "contest_free"]"#).unwrap();
+
+        assert!(filter.matches(&make_spot("W6JSV", "EA5WU-#", 14025.0, 15, 20)));
+        assert!(filter.matches(&make_spot("W6JSV", "EA5WU-#", 18130.0, 15, 20)));
+        // NCDXF beacon segment
+        assert!(!filter.matches(&make_spot("4U1UN", "EA5WU-#", 14100.0, 15, 20)));
+        assert!(!filter.matches(&make_spot("W6JSV", "EA5WU-#", 14074.0, 15, 20)));
+
+        assert!(toml::from_str::<SpotFilter>(r#"segments = ["ssb"]"#).is_err());
+    }
+
+    #[test]
+    fn test_filter_unknown_mode_and_type() {
+        let filter: SpotFilter = toml::from_str(
+            r#"modes = ["cw", "JT65"]
+spot_types = ["QSO"]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            filter.modes,
+            Some(vec![Mode::Cw, Mode::Other("JT65".to_string())])
+        );
+
+        let mut spot = make_spot("W6JSV", "EA5WU-#", 14076.0, 15, 20);
+        spot.mode = Mode::Other("JT65".to_string());
+        spot.spot_type = SpotType::Other("QSO".to_string());
+        assert!(filter.matches(&spot));
+
+        spot.mode = Mode::Other("JT9".to_string());
+        assert!(!filter.matches(&spot));
+    }
+
+    #[test]
+    fn test_filter_other_spot_types() {
+        let filter: SpotFilter = toml::from_str(r#"spot_types = ["other"]"#).unwrap();
+
+        let mut spot = make_spot("W6JSV", "EA5WU-#", 14025.0, 15, 20);
+        spot.spot_type = SpotType::Other("QSO".to_string());
+        assert!(filter.matches(&spot));
+        assert!(filter.matches_ref(&spot.as_spot_ref()));
+
+        spot.spot_type = SpotType::Other("TEST".to_string());
+        assert!(filter.matches(&spot));
+
+        spot.spot_type = SpotType::Cq;
+        assert!(!filter.matches(&spot));
+
+        spot.spot_type = SpotType::Unknown;
+        assert!(!filter.matches(&spot));
+    }
+
+    #[test]
+    fn test_filter_frequency_range() {
+        let filter: SpotFilter =
+            toml::from_str("min_freq_khz = 7000\nmax_freq_khz = 7040.5").unwrap();
+        assert!(filter.validate().is_ok());
+
+        assert!(filter.matches(&make_spot("W6JSV", "EA5WU-#", 7000.0, 15, 20)));
+        assert!(filter.matches(&make_spot("W6JSV", "EA5WU-#", 7040.5, 15, 20)));
+        assert!(!filter.matches(&make_spot("W6JSV", "EA5WU-#", 6999.9, 15, 20)));
+        assert!(!filter.matches(&make_spot("W6JSV", "EA5WU-#", 7040.6, 15, 20)));
+
+        let filter: SpotFilter =
+            toml::from_str("min_freq_khz = 14100\nmax_freq_khz = 14000").unwrap();
+        let err = filter.validate().unwrap_err();
+        assert!(err.contains("min_freq_khz"), "{}", err);
+    }
+
+    #[test]
+    fn test_filter_sources() {
+        let filter: SpotFilter = toml::from_str(r#"sources = ["rbn-digital", "skimmer"]"#).unwrap();
+
+        let spot = make_spot("W6JSV", "EA5WU-#", 14074.0, 15, 20);
+        assert!(!filter.matches(&spot));
+        assert!(filter.matches(&spot.clone().with_source("skimmer")));
+        assert!(!filter.matches(&spot.clone().with_source("rbn-cw")));
+
+        // Checked on the borrowed form too
+        let spot = spot.with_source("rbn-digital");
+        assert!(filter.matches_ref(&spot.as_spot_ref()));
+    }
+
     #[test]
     fn test_filter_snr_range() {
         let filter = SpotFilter {
@@ -401,12 +748,59 @@ mod tests {
         assert!(filter.matches(&make_spot("W6JSV", "EA5WU-#", 14025.0, 20, 20)));
         assert!(!filter.matches(&make_spot("W6JSV", "EA5WU-#", 14025.0, 5, 20)));
         assert!(!filter.matches(&make_spot("W6JSV", "EA5WU-#", 14025.0, 25, 20)));
+
+        // Human cluster spots carry no SNR
+        let human = CwSpot {
+            snr_db: None,
+            ..make_spot("W6JSV", "K1ABC", 14025.0, 15, 20)
+        };
+        assert!(!filter.matches(&human));
+    }
+
+    #[test]
+    fn test_filter_wpm_range() {
+        let filter = SpotFilter {
+            min_wpm: Some(15),
+            max_wpm: Some(25),
+            ..Default::default()
+        };
+
+        assert!(filter.matches(&make_spot("W6JSV", "EA5WU-#", 14025.0, 15, 20)));
+        assert!(!filter.matches(&make_spot("W6JSV", "EA5WU-#", 14025.0, 15, 30)));
+
+        // A BPS-rated spot has no WPM, so it can't satisfy a WPM bound
+        let rtty = CwSpot {
+            mode: Mode::Rtty,
+            speed: Some(Speed::Bps(20)),
+            ..make_spot("W6JSV", "EA5WU-#", 14080.0, 15, 20)
+        };
+        assert!(!filter.matches(&rtty));
+    }
+
+    #[test]
+    fn test_filter_bps_range() {
+        let filter = SpotFilter {
+            min_bps: Some(45),
+            max_bps: Some(50),
+            ..Default::default()
+        };
+
+        let make_rtty = |speed: Option<Speed>| CwSpot {
+            mode: Mode::Rtty,
+            speed,
+            ..make_spot("W6JSV", "EA5WU-#", 14080.0, 15, 20)
+        };
+
+        assert!(filter.matches(&make_rtty(Some(Speed::Bps(45)))));
+        assert!(!filter.matches(&make_rtty(Some(Speed::Bps(75)))));
+        assert!(!filter.matches(&make_rtty(None)));
+        assert!(!filter.matches(&make_spot("W6JSV", "EA5WU-#", 14025.0, 15, 45)));
     }
 
     #[test]
     fn test_filter_combined_and_logic() {
         let filter = SpotFilter {
-            bands: Some(vec!["20m".to_string()]),
+            bands: Some(vec![Band::B20m]),
             min_snr: Some(15),
             ..Default::default()
         };
@@ -506,6 +900,100 @@ mod tests {
         assert!(!filter.matches(&make_spot("W6JSV", "K1ABC-#", 14025.0, 15, 20)));
     }
 
+    #[test]
+    fn test_filter_dx_modifiers() {
+        let toml = r#"
+            dx_modifiers = ["MM", "AM"]
+        "#;
+        let filter: SpotFilter = toml::from_str(toml).unwrap();
+
+        assert!(filter.matches(&make_spot("K1ABC/MM", "EA5WU-#", 14025.0, 15, 20)));
+        assert!(filter.matches(&make_spot("G4XYZ/AM", "EA5WU-#", 14025.0, 15, 20)));
+        assert!(!filter.matches(&make_spot("K1ABC/M", "EA5WU-#", 14025.0, 15, 20)));
+        assert!(!filter.matches(&make_spot("K1ABC", "EA5WU-#", 14025.0, 15, 20)));
+    }
+
+    #[test]
+    fn test_filter_dx_home_call() {
+        let toml = r#"
+            dx_home_call = "W6JSV"
+        "#;
+        let filter: SpotFilter = toml::from_str(toml).unwrap();
+
+        assert!(filter.matches(&make_spot("W6JSV", "EA5WU-#", 14025.0, 15, 20)));
+        assert!(filter.matches(&make_spot("W6JSV/P", "EA5WU-#", 14025.0, 15, 20)));
+        assert!(filter.matches(&make_spot("KH6/W6JSV", "EA5WU-#", 14025.0, 15, 20)));
+        assert!(!filter.matches(&make_spot("W6JSVA", "EA5WU-#", 14025.0, 15, 20)));
+    }
+
+    #[test]
+    fn test_filter_dx_location() {
+        let toml = r#"
+            dx_location = "EA8"
+        "#;
+        let filter: SpotFilter = toml::from_str(toml).unwrap();
+
+        assert!(filter.matches(&make_spot("EA8/DL1ABC", "EA5WU-#", 14025.0, 15, 20)));
+        assert!(filter.matches(&make_spot("DL1ABC/EA8/P", "EA5WU-#", 14025.0, 15, 20)));
+        assert!(!filter.matches(&make_spot("DL1ABC", "EA5WU-#", 14025.0, 15, 20)));
+    }
+
+    #[test]
+    fn test_filter_spotter_base_station() {
+        let toml = r#"
+            spotter = "KM3T"
+        "#;
+        let filter: SpotFilter = toml::from_str(toml).unwrap();
+
+        assert!(filter.matches(&make_spot("W6JSV", "KM3T-#", 14025.0, 15, 20)));
+        assert!(filter.matches(&make_spot("W6JSV", "KM3T-2-#", 14025.0, 15, 20)));
+        assert!(filter.matches(&make_spot("W6JSV", "KM3T", 14025.0, 15, 20)));
+        assert!(!filter.matches(&make_spot("W6JSV", "KM3TA-#", 14025.0, 15, 20)));
+
+        // A full spotter pattern still selects a single instance
+        let toml = r#"
+            spotter = "KM3T-2-#"
+        "#;
+        let filter: SpotFilter = toml::from_str(toml).unwrap();
+        assert!(filter.matches(&make_spot("W6JSV", "KM3T-2-#", 14025.0, 15, 20)));
+        assert!(!filter.matches(&make_spot("W6JSV", "KM3T-#", 14025.0, 15, 20)));
+    }
+
+    #[test]
+    fn test_filter_dxcc() {
+        let dxcc = crate::dxcc::DxccDatabase::parse_dat(
+            "Fed. Rep. of Germany: 14: 28: EU: 51.00: -10.00: -1.0: DL:\n DJ,DK,DL;\n\
+             United States: 05: 08: NA: 37.53: 91.67: 5.0: K:\n K,N,W,W6(3)[6];\n",
+        )
+        .unwrap();
+        let spot =
+            |dx: &str, spotter: &str| make_spot(dx, spotter, 14025.0, 15, 20).with_dxcc(&dxcc);
+
+        // EU stations heard by NA skimmers
+        let toml = r#"
+            dx_continents = ["EU"]
+            spotter_continents = ["NA"]
+        "#;
+        let filter: SpotFilter = toml::from_str(toml).unwrap();
+        assert!(filter.uses_dxcc());
+        assert!(filter.matches(&spot("DK8NE", "KM3T-2-#")));
+        assert!(!filter.matches(&spot("DK8NE", "DL8LAS-#")));
+        assert!(!filter.matches(&spot("W1AW", "KM3T-#")));
+        // Unresolved calls never match
+        assert!(!filter.matches(&spot("JA1XYZ", "KM3T-#")));
+        assert!(!filter.matches(&make_spot("DK8NE", "KM3T-#", 14025.0, 15, 20)));
+
+        let toml = r#"
+            dx_entities = ["united states"]
+            dx_cq_zones = [3]
+            spotter_entities = ["DL"]
+        "#;
+        let filter: SpotFilter = toml::from_str(toml).unwrap();
+        assert!(filter.matches(&spot("W6JSV", "DK9IP-#")));
+        assert!(!filter.matches(&spot("W1AW", "DK9IP-#")));
+        assert!(!filter.matches(&spot("W6JSV", "W3OA-#")));
+    }
+
     #[test]
     fn test_filter_polo_notes_url() {
         let toml = r#"
@@ -0,0 +1,221 @@
+//! Formatting spots back into the RBN telnet column layout.
+//!
+//! [`format_spot`] is the inverse of [`crate::parser::parse_spot`]: it
+//! reproduces the fixed-column line the RBN servers send, so re-emitted
+//! spots can be consumed by loggers and cluster clients that expect it.
+//!
+//! ```text
+//! DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z
+//! DX de KD2OGR-#:  14074.0  JA1XYZ         FT8   -12 dB  CQ      2259Z
+//! DX de K1ABC:     14025.0  JA1XYZ       tnx qso 599                    1234Z
+//! ```
+
+use crate::spot::{CwSpot, Mode, SpotType};
+
+/// Column where the frequency ends.
+const FREQUENCY_END: usize = 24;
+/// Width of the DX call column on skimmer lines.
+const DX_CALL_WIDTH: usize = 14;
+/// Width of the mode and right-aligned SNR value together.
+const MODE_SNR_WIDTH: usize = 8;
+/// The FT8/FT4 feed leaves room for a sign on the SNR.
+const DIGITAL_MODE_SNR_WIDTH: usize = 9;
+/// Width of the spot type column.
+const SPOT_TYPE_WIDTH: usize = 7;
+/// Width of the DX call column on human cluster lines.
+const CLUSTER_DX_CALL_WIDTH: usize = 12;
+/// Width of the comment column on human cluster lines.
+const COMMENT_WIDTH: usize = 30;
+
+/// Format a spot as an RBN telnet line.
+///
+/// Skimmer spots use the skimmer layout; spots without a mode or SNR
+/// (human cluster spots) use the DX cluster layout with their comment.
+/// Fields wider than their column push the rest of the line right, but are
+/// always separated by at least one space so the line still parses.
+pub fn format_spot(spot: &CwSpot) -> String {
+    let mut line = String::with_capacity(80);
+    let prefix = format!("DX de {}:", spot.spotter);
+    push_right_aligned(
+        &mut line,
+        &prefix,
+        &format!("{:.1}", spot.frequency.khz()),
+        FREQUENCY_END,
+    );
+    line.push_str("  ");
+
+    match spot.snr_db {
+        Some(snr) if spot.mode != Mode::Unknown => {
+            let mode_snr_width = match spot.mode {
+                Mode::Ft8 | Mode::Ft4 => DIGITAL_MODE_SNR_WIDTH,
+                _ => MODE_SNR_WIDTH,
+            };
+            push_left_aligned(&mut line, &spot.dx_call, DX_CALL_WIDTH);
+            push_right_aligned(
+                &mut line,
+                &spot.mode.to_string(),
+                &snr.to_string(),
+                mode_snr_width,
+            );
+            line.push_str(" dB  ");
+
+            if let Some(speed) = spot.speed {
+                line.push_str(&format!("{:>2} {}  ", speed.value(), speed.unit()));
+            }
+            // The type is optional on the wire
+            if spot.spot_type != SpotType::Unknown {
+                push_left_aligned(&mut line, &spot.spot_type.to_string(), SPOT_TYPE_WIDTH);
+            }
+        }
+        _ => {
+            push_left_aligned(&mut line, &spot.dx_call, CLUSTER_DX_CALL_WIDTH);
+            push_left_aligned(
+                &mut line,
+                spot.comment.as_deref().unwrap_or(""),
+                COMMENT_WIDTH,
+            );
+        }
+    }
+
+    line.push_str(&spot.time.format("%H%MZ").to_string());
+    line
+}
+
+/// Append `left`, then `right` right-aligned so that the two end `width`
+/// characters later, with at least one space between them.
+fn push_right_aligned(line: &mut String, left: &str, right: &str, width: usize) {
+    let pad = width.saturating_sub(left.len() + right.len()).max(1);
+    line.push_str(left);
+    line.extend(std::iter::repeat_n(' ', pad));
+    line.push_str(right);
+}
+
+/// Append `text` padded to `width`, followed by a separating space.
+fn push_left_aligned(line: &mut String, text: &str, width: usize) {
+    line.push_str(&format!("{:<width$} ", text, width = width));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frequency::Frequency;
+    use crate::parser::{parse_cluster_spot, parse_spot};
+    use crate::spot::Speed;
+    use chrono::NaiveTime;
+    use proptest::prelude::*;
+
+    /// Real lines in the exact server layout.
+    const CANONICAL_LINES: &[&str] = &[
+        "DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z",
+        "DX de HB9JCB-#:   3516.9  RA1AFT         CW     9 dB  26 WPM  CQ      2259Z",
+        "DX de K9LC-#:    28169.9  VA3XCD/B       CW     9 dB  10 WPM  BEACON  2259Z",
+        "DX de KM3T-2-#:  14100.0  CS3B           CW    24 dB  22 WPM  NCDXF B 2259Z",
+        "DX de DK9IP-#:   14070.2  OH2XX          PSK31  8 dB  31 BPS  CQ      0102Z",
+        "DX de DL8LAS-#:  14074.0  PY2XB          FT8   -14 dB  CQ      1207Z",
+        "DX de W3OA-#:     7047.5  K1ABC          FT4    -3 dB  CQ      0014Z",
+        "DX de VE2WU-#:   28074.0  EA8AH          FT8     3 dB  CQ      1938Z",
+    ];
+
+    #[test]
+    fn test_format_reproduces_canonical_lines() {
+        for line in CANONICAL_LINES {
+            let spot = parse_spot(line).unwrap();
+            assert_eq!(format_spot(&spot), *line);
+        }
+    }
+
+    #[test]
+    fn test_format_cluster_spot() {
+        let line = "DX de K1ABC:     14025.0  JA1XYZ       tnx qso 599                    1234Z";
+        let spot = parse_cluster_spot(line).unwrap();
+        assert_eq!(format_spot(&spot), line);
+    }
+
+    #[test]
+    fn test_format_keeps_wide_fields_separated() {
+        let mut spot = parse_spot(CANONICAL_LINES[4]).unwrap();
+        spot.spotter = "VERYLONGCALL-12-#".to_string();
+        spot.dx_call = "VP2V/AA1ABCDEF/P".to_string();
+        spot.snr_db = Some(-25);
+
+        let line = format_spot(&spot);
+        assert!(line.contains("-#: 14070.2"), "{}", line);
+        assert!(line.contains("PSK31 -25 dB"), "{}", line);
+        assert_eq!(parse_spot(&line).unwrap(), spot);
+    }
+
+    fn arb_mode_and_speed() -> impl Strategy<Value = (Mode, Option<Speed>)> {
+        prop_oneof![
+            (5u16..=99).prop_map(|wpm| (Mode::Cw, Some(Speed::Wpm(wpm)))),
+            (
+                prop_oneof![Just(Mode::Rtty), Just(Mode::Psk31)],
+                10u16..=300
+            )
+                .prop_map(|(mode, bps)| (mode, Some(Speed::Bps(bps)))),
+            prop_oneof![
+                Just(Mode::Cw),
+                Just(Mode::Rtty),
+                Just(Mode::Ft8),
+                Just(Mode::Ft4),
+                Just(Mode::Psk31),
+                "[A-Z][A-Z0-9]{1,5}".prop_map(|token| Mode::from_name(&token))
+            ]
+            .prop_filter("Unknown has no token", |mode| *mode != Mode::Unknown)
+            .prop_map(|mode| (mode, None)),
+        ]
+    }
+
+    fn arb_spot_type() -> impl Strategy<Value = SpotType> {
+        prop_oneof![
+            Just(SpotType::Cq),
+            Just(SpotType::NcdxfBeacon),
+            Just(SpotType::Beacon),
+            Just(SpotType::Dx),
+            Just(SpotType::Unknown),
+            "[A-Z][A-Z0-9]{1,6}"
+                .prop_map(|token| SpotType::from_name(&token))
+                .prop_filter("UNKNOWN is never on the wire", |t| {
+                    *t != SpotType::Unknown
+                }),
+        ]
+    }
+
+    fn arb_spot() -> impl Strategy<Value = CwSpot> {
+        (
+            "[A-Z0-9]{1,3}[0-9][A-Z]{1,3}(-[1-9])?-#",
+            1u32..=5_000_000,
+            "[A-Z0-9]{1,3}[0-9][A-Z]{1,4}(/[A-Z0-9]{1,3})?",
+            arb_mode_and_speed(),
+            -40i32..=99,
+            arb_spot_type(),
+            0u32..24,
+            0u32..60,
+        )
+            .prop_map(
+                |(spotter, tenths, dx_call, (mode, speed), snr, spot_type, hour, minute)| CwSpot {
+                    spotter,
+                    frequency: Frequency::from_hz(u64::from(tenths) * 100),
+                    dx_call,
+                    mode,
+                    snr_db: Some(snr),
+                    speed,
+                    spot_type,
+                    time: NaiveTime::from_hms_opt(hour, minute, 0).unwrap(),
+                    comment: None,
+                    received_at: None,
+                    timestamp: None,
+                    dx_dxcc: None,
+                    spotter_dxcc: None,
+                    source: None,
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parse_format_round_trip(spot in arb_spot()) {
+            let line = format_spot(&spot);
+            prop_assert_eq!(parse_spot(&line).unwrap(), spot, "{}", line);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! Fixed-point radio frequencies.
+//!
+//! Spots report frequencies in kHz with a decimal fraction ("7018.3").
+//! Keeping them as `f64` made band edges and equality checks depend on
+//! rounding, so [`Frequency`] stores a whole number of hertz instead. It
+//! still reads and writes plain kHz numbers, so JSON and config files look
+//! the same as before.
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// A frequency, stored as a whole number of hertz.
+///
+/// Serializes as a kHz number (`7018.3`), and deserializes from a kHz
+/// number or a decimal kHz string.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Frequency(u64);
+
+impl Frequency {
+    /// A frequency of `hz` hertz.
+    pub const fn from_hz(hz: u64) -> Self {
+        Self(hz)
+    }
+
+    /// A frequency in kHz, rounded to the nearest hertz.
+    ///
+    /// Negative and NaN values become zero.
+    pub const fn from_khz(khz: f64) -> Self {
+        if khz.is_nan() || khz <= 0.0 {
+            return Self(0);
+        }
+        Self((khz * 1000.0 + 0.5) as u64)
+    }
+
+    /// The frequency in hertz.
+    pub const fn hz(self) -> u64 {
+        self.0
+    }
+
+    /// The frequency in kHz.
+    pub fn khz(self) -> f64 {
+        self.0 as f64 / 1000.0
+    }
+}
+
+/// Parses a decimal kHz string such as "7018.3" exactly, without going
+/// through `f64`. Digits past 1 Hz are rounded.
+impl FromStr for Frequency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid frequency '{}'", s);
+        let (whole, fraction) = s.trim().split_once('.').unwrap_or((s.trim(), ""));
+        let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if whole.is_empty() || !is_digits(whole) || !is_digits(fraction) {
+            return Err(invalid());
+        }
+
+        let khz: u64 = whole.parse().map_err(|_| invalid())?;
+        let mut hz = fraction
+            .bytes()
+            .take(3)
+            .fold(0, |hz, digit| hz * 10 + u64::from(digit - b'0'));
+        hz *= 10u64.pow(3 - fraction.len().min(3) as u32);
+        if fraction
+            .as_bytes()
+            .get(3)
+            .is_some_and(|&digit| digit >= b'5')
+        {
+            hz += 1;
+        }
+
+        khz.checked_mul(1000)
+            .and_then(|khz| khz.checked_add(hz))
+            .map(Frequency)
+            .ok_or_else(invalid)
+    }
+}
+
+/// Displays the frequency in kHz with as many decimals as it needs, but
+/// at least one: "7018.3", "14025.0", "1840.125".
+impl fmt::Display for Frequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fraction = format!("{:03}", self.0 % 1000);
+        let fraction = fraction.trim_end_matches('0');
+        let fraction = if fraction.is_empty() { "0" } else { fraction };
+        f.pad(&format!("{}.{}", self.0 / 1000, fraction))
+    }
+}
+
+impl Serialize for Frequency {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.khz())
+    }
+}
+
+impl<'de> Deserialize<'de> for Frequency {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KhzVisitor;
+
+        impl Visitor<'_> for KhzVisitor {
+            type Value = Frequency;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a frequency in kHz")
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<Frequency, E> {
+                if value.is_finite() && value >= 0.0 {
+                    Ok(Frequency::from_khz(value))
+                } else {
+                    Err(E::custom(format!("Invalid frequency {}", value)))
+                }
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Frequency, E> {
+                value
+                    .checked_mul(1000)
+                    .map(Frequency)
+                    .ok_or_else(|| E::custom(format!("Invalid frequency {}", value)))
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Frequency, E> {
+                u64::try_from(value)
+                    .map_err(|_| E::custom(format!("Invalid frequency {}", value)))
+                    .and_then(|value| self.visit_u64(value))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Frequency, E> {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(KhzVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_khz_rounds_to_hz() {
+        assert_eq!(Frequency::from_khz(7018.3).hz(), 7_018_300);
+        assert_eq!(Frequency::from_khz(1799.95).hz(), 1_799_950);
+        assert_eq!(Frequency::from_khz(0.1 + 0.2).hz(), 300);
+        assert_eq!(Frequency::from_khz(-1.0).hz(), 0);
+        assert_eq!(Frequency::from_khz(14025.0), Frequency::from_hz(14_025_000));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("7018.3".parse(), Ok(Frequency::from_hz(7_018_300)));
+        assert_eq!("14025".parse(), Ok(Frequency::from_hz(14_025_000)));
+        assert_eq!("1840.125".parse(), Ok(Frequency::from_hz(1_840_125)));
+        assert_eq!("1840.1255".parse(), Ok(Frequency::from_hz(1_840_126)));
+        assert_eq!("1840.12549".parse(), Ok(Frequency::from_hz(1_840_125)));
+        assert!("".parse::<Frequency>().is_err());
+        assert!(".5".parse::<Frequency>().is_err());
+        assert!("7018.3x".parse::<Frequency>().is_err());
+        assert!("-7018.3".parse::<Frequency>().is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Frequency::from_hz(7_018_300).to_string(), "7018.3");
+        assert_eq!(Frequency::from_hz(14_025_000).to_string(), "14025.0");
+        assert_eq!(Frequency::from_hz(1_840_125).to_string(), "1840.125");
+        assert_eq!(format!("{:>8}", Frequency::from_hz(7_018_300)), "  7018.3");
+    }
+
+    #[test]
+    fn test_serde_as_khz() {
+        let freq = Frequency::from_hz(7_018_300);
+        assert_eq!(serde_json::to_string(&freq).unwrap(), "7018.3");
+
+        for json in ["7018.3", "\"7018.3\""] {
+            assert_eq!(serde_json::from_str::<Frequency>(json).unwrap(), freq);
+        }
+        assert_eq!(
+            serde_json::from_str::<Frequency>("14025").unwrap(),
+            Frequency::from_hz(14_025_000)
+        );
+        assert!(serde_json::from_str::<Frequency>("-1").is_err());
+        assert!(serde_json::from_str::<Frequency>("\"abc\"").is_err());
+    }
+}
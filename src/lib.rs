@@ -19,20 +19,47 @@
 //! println!("{}", stats.summary());
 //! ```
 
+pub mod archive;
+pub mod backoff;
+pub mod band;
+pub mod calibration;
+pub mod capture;
 pub mod client;
 pub mod config;
+pub mod dxcc;
+pub mod failover;
 pub mod filter;
+pub mod format;
+pub mod frequency;
+pub mod line;
 pub mod metrics;
 pub mod parser;
 pub mod polo;
 pub mod spot;
 pub mod stats;
 pub mod storage;
+pub mod telnet;
+pub mod tls;
 
-pub use client::{RbnClient, RbnClientConfig, RbnEvent};
-pub use config::{Config, StorageConfig};
+pub use band::{Band, BandPlan, IaruRegion, Segment};
+pub use calibration::{FrequencyCalibrator, SpotterOffset};
+pub use capture::{CaptureWriter, ReplaySpeed, replay};
+pub use client::{LoginStep, RbnClient, RbnClientConfig, RbnEvent, SourceEvent, connect_all};
+pub use config::{CalibrationConfig, Config, SourceConfig, StorageConfig};
+pub use dxcc::{Continent, DxccDatabase, DxccInfo};
+pub use failover::Endpoint;
 pub use filter::{SpotFilter, any_filter_matches};
-pub use parser::{ParseError, is_cw_spot, looks_like_spot, parse_spot};
-pub use spot::{CwSpot, Mode, SpotType};
+pub use format::format_spot;
+pub use frequency::Frequency;
+pub use line::{Announcement, PropagationReport, PropagationSource, RbnLine};
+pub use parser::{
+    ParseError, SpotField, is_cw_spot, looks_like_spot, parse_any_spot, parse_cluster_spot,
+    parse_line, parse_spot, parse_spot_ref,
+};
+pub use spot::{
+    Callsign, CallsignModifier, CwSpot, CwSpotRef, Mode, ModeRef, Speed, SpotType, SpotTypeRef,
+    SpotterId,
+};
 pub use stats::{SpotStats, StatsSummary};
 pub use storage::SpotStorage;
+pub use tls::TlsConfig;
@@ -0,0 +1,118 @@
+//! Data structures representing every kind of line the RBN server sends.
+//!
+//! Besides spots, a cluster session carries propagation bulletins,
+//! announcements, the login banner and command prompts. [`RbnLine`]
+//! classifies each received line into one of these kinds.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::spot::CwSpot;
+
+/// The bulletin service a propagation report came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum PropagationSource {
+    /// NOAA WWV geomagnetic/solar bulletin
+    Wwv,
+    /// DK0WCY beacon bulletin
+    Wcy,
+}
+
+impl fmt::Display for PropagationSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropagationSource::Wwv => write!(f, "WWV"),
+            PropagationSource::Wcy => write!(f, "WCY"),
+        }
+    }
+}
+
+/// A parsed WWV or WCY propagation bulletin.
+///
+/// # Example
+///
+/// ```text
+/// WWV de W0MU <18>:   SFI=70, A=5, K=1, No Storms -> No Storms
+/// WCY de DK0WCY-1 <12> : K=2 expK=3 A=8 R=15 SFI=70 SA=qui GMF=qui Au=no
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropagationReport {
+    /// Which bulletin service sent the report.
+    pub source: PropagationSource,
+
+    /// Callsign of the station that posted the bulletin.
+    pub reporter: String,
+
+    /// UTC hour the bulletin applies to, if given.
+    pub hour: Option<u8>,
+
+    /// Solar flux index.
+    pub sfi: Option<u16>,
+
+    /// Planetary A index.
+    pub a_index: Option<u16>,
+
+    /// Planetary K index.
+    pub k_index: Option<u8>,
+
+    /// Expected K index (WCY only).
+    pub expected_k: Option<u8>,
+
+    /// Sunspot number (WCY only).
+    pub sunspot_number: Option<u16>,
+
+    /// Free-text forecast remarks (e.g., "No Storms -> No Storms").
+    pub remarks: Option<String>,
+}
+
+/// A "To ALL" style announcement broadcast by a cluster user or sysop.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Announcement {
+    /// Audience of the announcement (usually "ALL").
+    pub target: String,
+
+    /// Callsign of the sender.
+    pub from: String,
+
+    /// Announcement text.
+    pub message: String,
+}
+
+/// A classified line from the RBN (or DX cluster) telnet feed.
+// Nearly every line is a spot, so boxing it would only add an allocation
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum RbnLine {
+    /// A skimmer or human spot.
+    Spot(CwSpot),
+
+    /// A WWV/WCY propagation bulletin.
+    Propagation(PropagationReport),
+
+    /// A "To ALL" announcement.
+    Announcement(Announcement),
+
+    /// Part of the login banner (greeting, callsign prompt, etc.).
+    Banner(String),
+
+    /// A command prompt, e.g. "W6JSV de RELAY 08-Jan-2026 03:13Z >".
+    Prompt(String),
+
+    /// Anything else.
+    Unknown(String),
+}
+
+impl RbnLine {
+    /// Returns a short label for the kind of line, used in stats and metrics.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RbnLine::Spot(_) => "spot",
+            RbnLine::Propagation(_) => "propagation",
+            RbnLine::Announcement(_) => "announcement",
+            RbnLine::Banner(_) => "banner",
+            RbnLine::Prompt(_) => "prompt",
+            RbnLine::Unknown(_) => "unknown",
+        }
+    }
+}
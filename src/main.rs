@@ -1,16 +1,24 @@
 //! RBN Parser CLI - Stream and analyze CW spots from the Reverse Beacon Network.
 
 use anyhow::Result;
-use clap::Parser;
+use chrono::{DateTime, Utc};
+use clap::{Parser, Subcommand};
 use rbn_parser::{
     Config,
-    client::{RbnClient, RbnClientConfig, RbnEvent},
+    archive::read_archive,
+    calibration::FrequencyCalibrator,
+    capture::{CaptureWriter, ReplaySpeed, replay},
+    client::{RbnEvent, SourceEvent, connect_all},
+    dxcc::DxccDatabase,
+    line::RbnLine,
     metrics::start_metrics_server,
-    parser::{is_cw_spot, looks_like_spot, parse_spot},
+    parser::{is_cw_spot, parse_line},
     polo::PoloNotesManager,
     stats::SpotStats,
     storage::SpotStorage,
 };
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::signal;
@@ -33,6 +41,41 @@ struct Args {
     /// Maximum runtime in seconds (0 = unlimited)
     #[arg(long, default_value_t = 0)]
     max_runtime: u64,
+
+    /// Append every received line, with its receive time, to this file
+    #[arg(long, value_name = "FILE")]
+    capture: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Import RBN daily CSV archives (zip or plain) instead of connecting
+    ///
+    /// Spots are run through the configured filters and statistics, and a
+    /// report is printed at the end. With --verbose, matching spots are printed.
+    Import {
+        /// Archive files to read
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+
+    /// Replay a file written with --capture instead of connecting
+    ///
+    /// Lines go through the same pipeline as live traffic (statistics,
+    /// filters, storage and the HTTP server), keeping their original receive
+    /// times and source names.
+    Replay {
+        /// Capture file to read
+        file: PathBuf,
+
+        /// Replay speed: 1x keeps the original timing, 10x is ten times
+        /// faster, max sends lines as fast as they are processed
+        #[arg(long, default_value = "1x")]
+        speed: ReplaySpeed,
+    },
 }
 
 #[tokio::main]
@@ -40,6 +83,10 @@ async fn main() -> Result<()> {
     let args = Args::parse();
     let config = Config::load()?;
     config.validate()?;
+    // Before anything looks up a band, which would fix the default plan
+    if !config.band_plan.build()?.install() {
+        anyhow::bail!("band plan already initialized");
+    }
 
     // Initialize logging
     let filter =
@@ -50,12 +97,47 @@ async fn main() -> Result<()> {
         .with_target(false)
         .init();
 
+    // Load the country file for DXCC lookups, if configured
+    let dxcc = match config.cty_file {
+        Some(ref path) => {
+            let dxcc = DxccDatabase::load(path)?;
+            info!(
+                "Loaded {} DXCC entities from {}",
+                dxcc.len(),
+                path.display()
+            );
+            Some(dxcc)
+        }
+        None => None,
+    };
+
+    if let Some(Command::Import { files }) = &args.command {
+        return run_import(files, &config, dxcc.as_ref(), args.verbose).await;
+    }
+
     info!("RBN Parser starting...");
     if let Some(path) = Config::config_path() {
         info!("Config file: {}", path.display());
     }
-    info!("Callsign: {}", config.callsign);
-    info!("Server: {}:{}", config.host, config.port);
+    let replay_from = match &args.command {
+        Some(Command::Replay { file, speed }) => Some((file, *speed)),
+        _ => None,
+    };
+    let sources = config.effective_sources();
+    match replay_from {
+        Some((file, speed)) => info!("Replaying {} at {}", file.display(), speed),
+        None => {
+            config.validate_sources()?;
+            info!("Callsign: {}", config.callsign);
+            for source in &sources {
+                let tls = if source.tls.is_some() { " (TLS)" } else { "" };
+                info!(
+                    "Source {}: {}:{}{}",
+                    source.name, source.host, source.port, tls
+                );
+            }
+        }
+    }
     if !config.filters.is_empty() {
         info!("Filters: {} configured", config.filters.len());
     }
@@ -73,8 +155,21 @@ async fn main() -> Result<()> {
         });
     }
 
+    // Create the frequency calibrator if configured
+    let calibrator = config
+        .calibration
+        .as_ref()
+        .map(|c| Arc::new(FrequencyCalibrator::new(c)));
+    if calibrator.is_some() {
+        info!("Skimmer frequency calibration enabled");
+    }
+
     // Create shared statistics
-    let stats = Arc::new(SpotStats::new());
+    let stats = match calibrator {
+        Some(ref calibrator) => SpotStats::new().with_calibrator(Arc::clone(calibrator)),
+        None => SpotStats::new(),
+    };
+    let stats = Arc::new(stats);
 
     // Create spot storage if configured
     let storage = config.storage.as_ref().map(|storage_config| {
@@ -83,8 +178,17 @@ async fn main() -> Result<()> {
         } else {
             Some(Arc::clone(&polo_manager))
         };
-        Arc::new(SpotStorage::new(storage_config, config.filters.clone(), pm))
+        let storage = SpotStorage::new(storage_config, config.filters.clone(), pm);
+        let correct = config
+            .calibration
+            .as_ref()
+            .is_some_and(|c| c.correct_stored_spots);
+        match calibrator {
+            Some(ref calibrator) if correct => storage.with_calibrator(Arc::clone(calibrator)),
+            _ => storage,
+        }
     });
+    let storage = storage.map(Arc::new);
 
     if storage.is_some() {
         info!(
@@ -144,20 +248,33 @@ async fn main() -> Result<()> {
         });
     }
 
-    // Configure and start RBN client
-    let cw_only = config.cw_only;
-    let client_config = RbnClientConfig {
-        host: config.host,
-        port: config.port,
-        callsign: config.callsign,
-        connect_timeout: Duration::from_secs(config.connect_timeout),
-        read_timeout: Duration::from_secs(config.read_timeout),
-        auto_reconnect: config.reconnect,
-        ..Default::default()
+    let mut capture = match args.capture {
+        Some(ref path) => {
+            info!("Capturing received lines to {}", path.display());
+            Some(CaptureWriter::create(path)?)
+        }
+        None => None,
     };
 
-    let client = RbnClient::new(client_config);
-    let mut events = client.connect().await?;
+    // Configure and start one RBN client per source, merged into one feed,
+    // or replay a capture in their place
+    let mut line_sources: HashMap<String, LineSource> = sources
+        .iter()
+        .map(|source| {
+            let line_source = LineSource {
+                name: source.name.clone(),
+                cw_only: source.cw_only.unwrap_or(config.cw_only),
+            };
+            (source.name.clone(), line_source)
+        })
+        .collect();
+    let mut events = match replay_from {
+        Some((file, speed)) => replay(file, speed).await?,
+        None => {
+            let client_configs = sources.iter().map(|s| config.client_config(s)).collect();
+            connect_all(client_configs).await?
+        }
+    };
 
     // Main event loop
     loop {
@@ -171,22 +288,53 @@ async fn main() -> Result<()> {
 
             // Process RBN events
             event = events.recv() => {
+                let Some(SourceEvent { source, event }) = event else {
+                    // Every source has stopped
+                    break;
+                };
                 match event {
-                    Some(RbnEvent::Line(line)) => {
-                        process_line(&line, &stats, cw_only, args.verbose, storage.as_deref());
+                    RbnEvent::Line { line, received_at } => {
+                        if let Some(ref mut writer) = capture
+                            && let Err(e) = writer.write_line(received_at, &source, &line)
+                        {
+                            error!("Failed to write capture, stopping capture: {}", e);
+                            capture = None;
+                        }
+                        // A capture may name sources that aren't configured
+                        let line_source = line_sources
+                            .entry(source.to_string())
+                            .or_insert_with(|| LineSource {
+                                name: source.to_string(),
+                                cw_only: config.cw_only,
+                            });
+                        process_line(
+                            &line,
+                            received_at,
+                            &stats,
+                            dxcc.as_ref(),
+                            line_source,
+                            args.verbose,
+                            storage.as_deref(),
+                        );
+                    }
+                    RbnEvent::Connected(endpoint) => {
+                        info!("Connected to {} via {}", source, endpoint);
+                        stats.record_connected(&source, &endpoint.to_string());
+                    }
+                    RbnEvent::Disconnected(reason) => {
+                        warn!("Disconnected from {}: {}", source, reason);
                     }
-                    Some(RbnEvent::Connected) => {
-                        info!("Connected to RBN");
+                    RbnEvent::Reconnecting { attempt, delay } => {
+                        stats.record_reconnect(&source, attempt, delay);
                     }
-                    Some(RbnEvent::Disconnected(reason)) => {
-                        warn!("Disconnected: {}", reason);
+                    RbnEvent::BackoffReset => {
+                        stats.record_backoff_reset(&source);
                     }
-                    Some(RbnEvent::Error(e)) => {
-                        error!("Error: {}", e);
+                    RbnEvent::GaveUp { attempts } => {
+                        error!("Gave up reconnecting to {} after {} attempts", source, attempts);
                     }
-                    None => {
-                        // Channel closed
-                        break;
+                    RbnEvent::Error(e) => {
+                        error!("Error from {}: {}", source, e);
                     }
                 }
             }
@@ -200,32 +348,117 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Import archive files, feeding each spot through the stats and filters.
+async fn run_import(
+    files: &[PathBuf],
+    config: &Config,
+    dxcc: Option<&DxccDatabase>,
+    verbose: bool,
+) -> Result<()> {
+    // PoLo filters need their callsign lists before anything can match
+    let polo_manager = PoloNotesManager::from_filters(&config.filters);
+    if !polo_manager.is_empty() {
+        info!("PoLo notes: fetching callsigns...");
+        polo_manager.refresh_all().await;
+    }
+    let polo = (!polo_manager.is_empty()).then_some(&polo_manager);
+
+    let stats = match config.calibration {
+        Some(ref calibration) => {
+            SpotStats::new().with_calibrator(Arc::new(FrequencyCalibrator::new(calibration)))
+        }
+        None => SpotStats::new(),
+    };
+    let mut filter_matches = vec![0u64; config.filters.len()];
+
+    for path in files {
+        info!("Importing {}", path.display());
+        let rows = read_archive(path, |result| match result {
+            Ok(spot) => {
+                if config.cw_only && !is_cw_spot(&spot) {
+                    return;
+                }
+                let spot = match dxcc {
+                    Some(dxcc) => spot.with_dxcc(dxcc),
+                    None => spot,
+                };
+                stats.record_spot(&spot);
+
+                let mut matched = false;
+                for (count, filter) in filter_matches.iter_mut().zip(&config.filters) {
+                    if filter.matches_with_polo(&spot, polo) {
+                        *count += 1;
+                        matched = true;
+                    }
+                }
+                if verbose && (matched || config.filters.is_empty()) {
+                    println!("{}", spot);
+                }
+            }
+            Err(e) => {
+                stats.record_parse_error(&e);
+                debug!("Skipped archive row: {}", e);
+            }
+        })?;
+        info!("Read {} rows from {}", rows, path.display());
+    }
+
+    println!("{}", stats.summary());
+
+    if !config.filters.is_empty() {
+        println!("Filter matches:");
+        for (i, (filter, count)) in config.filters.iter().zip(&filter_matches).enumerate() {
+            let name = filter
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("filter_{}", i));
+            println!("  {}: {}", name, count);
+        }
+    }
+
+    Ok(())
+}
+
+/// The feed a line was received from.
+struct LineSource {
+    /// Source name its spots are tagged with
+    name: String,
+    /// Drop spots that aren't CW
+    cw_only: bool,
+}
+
 /// Process a single line from the RBN feed.
+///
+/// `received_at` is used to reconstruct the full date of spot times.
 fn process_line(
     line: &str,
+    received_at: DateTime<Utc>,
     stats: &SpotStats,
-    cw_only: bool,
+    dxcc: Option<&DxccDatabase>,
+    source: &LineSource,
     verbose: bool,
     storage: Option<&SpotStorage>,
 ) {
     stats.record_bytes(line.len() as u64);
 
-    // Quick filter for non-spot lines
-    if !looks_like_spot(line) {
-        stats.record_non_spot();
-        debug!("Non-spot line: {}", line);
-        return;
-    }
+    // Classify the line (spot, bulletin, banner, etc.)
+    match parse_line(line) {
+        Ok(RbnLine::Spot(spot)) => {
+            let spot = spot
+                .with_received_at(received_at)
+                .with_source(source.name.as_str());
 
-    // Try to parse the spot
-    match parse_spot(line) {
-        Ok(spot) => {
             // Filter for CW-only if requested
-            if cw_only && !is_cw_spot(&spot) {
+            if source.cw_only && !is_cw_spot(&spot) {
                 debug!("Filtered non-CW spot: {:?}", spot.mode);
                 return;
             }
 
+            let spot = match dxcc {
+                Some(dxcc) => spot.with_dxcc(dxcc),
+                None => spot,
+            };
+
             stats.record_spot(&spot);
 
             // Store in spot storage if configured (storage has its own filters)
@@ -238,8 +471,12 @@ fn process_line(
                 println!("{}", spot);
             }
         }
+        Ok(other) => {
+            stats.record_non_spot_line(&other);
+            debug!("Non-spot line ({}): {}", other.kind(), line);
+        }
         Err(e) => {
-            stats.record_parse_failure();
+            stats.record_parse_error(&e);
             debug!("Parse error for '{}': {}", line, e);
         }
     }
@@ -249,12 +486,91 @@ fn process_line(
 mod tests {
     use super::*;
 
+    fn source(cw_only: bool) -> LineSource {
+        LineSource {
+            name: "rbn".to_string(),
+            cw_only,
+        }
+    }
+
     #[test]
     fn test_process_line_valid_spot() {
         let stats = SpotStats::new();
         let line = "DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z";
 
-        process_line(line, &stats, true, false, None);
+        process_line(line, Utc::now(), &stats, None, &source(true), false, None);
+
+        assert_eq!(
+            stats.total_spots.load(std::sync::atomic::Ordering::Relaxed),
+            1
+        );
+    }
+
+    #[test]
+    fn test_process_line_stamps_stored_spot() {
+        use rbn_parser::{SpotFilter, StorageConfig};
+
+        let stats = SpotStats::new();
+        let storage =
+            SpotStorage::new(&StorageConfig::default(), vec![SpotFilter::default()], None);
+        let line = "DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2359Z";
+        let received_at = "2026-01-09T00:00:20Z".parse::<DateTime<Utc>>().unwrap();
+
+        process_line(
+            line,
+            received_at,
+            &stats,
+            None,
+            &source(true),
+            false,
+            Some(&storage),
+        );
+
+        let fs = storage
+            .get_filter_by_name("filter_0")
+            .unwrap()
+            .read()
+            .unwrap();
+        let stored = &fs.get_spots_since(0)[0].spot;
+        assert_eq!(stored.received_at, Some(received_at));
+        assert_eq!(stored.source.as_deref(), Some("rbn"));
+        assert_eq!(
+            stored.timestamp,
+            Some("2026-01-08T23:59:00Z".parse::<DateTime<Utc>>().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_process_line_rtty_spot() {
+        let stats = SpotStats::new();
+        let line = "DX de W3OA-#:    14080.0  K1ABC          RTTY  12 dB  45 BPS  CQ      2259Z";
+
+        // Dropped when cw_only is set
+        process_line(line, Utc::now(), &stats, None, &source(true), false, None);
+        assert_eq!(
+            stats.total_spots.load(std::sync::atomic::Ordering::Relaxed),
+            0
+        );
+
+        process_line(line, Utc::now(), &stats, None, &source(false), false, None);
+        assert_eq!(
+            stats.total_spots.load(std::sync::atomic::Ordering::Relaxed),
+            1
+        );
+        assert_eq!(
+            stats
+                .parse_failures
+                .load(std::sync::atomic::Ordering::Relaxed),
+            0
+        );
+    }
+
+    #[test]
+    fn test_process_line_cluster_spot() {
+        let stats = SpotStats::new();
+        let line = "DX de K1ABC:     14025.0  JA1XYZ       tnx qso 599                    1234Z";
+
+        process_line(line, Utc::now(), &stats, None, &source(false), false, None);
 
         assert_eq!(
             stats.total_spots.load(std::sync::atomic::Ordering::Relaxed),
@@ -267,7 +583,7 @@ mod tests {
         let stats = SpotStats::new();
         let line = "Welcome to the Reverse Beacon Network";
 
-        process_line(line, &stats, true, false, None);
+        process_line(line, Utc::now(), &stats, None, &source(true), false, None);
 
         assert_eq!(
             stats
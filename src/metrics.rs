@@ -3,6 +3,7 @@
 //! Exposes RBN statistics in Prometheus text format via HTTP endpoint,
 //! plus REST API endpoints for retrieving stored spots.
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::sync::atomic::Ordering::Relaxed;
@@ -18,6 +19,8 @@ use serde::{Deserialize, Serialize};
 use tokio::net::TcpListener;
 use tracing::info;
 
+use crate::calibration::SpotterOffset;
+use crate::line::PropagationReport;
 use crate::stats::SpotStats;
 use crate::storage::{SpotStorage, StoredSpot};
 
@@ -45,6 +48,7 @@ pub async fn start_metrics_server(
         .route("/health", get(health_handler))
         .route("/spots/filters", get(list_filters_handler))
         .route("/spots/filters/{name}", get(get_spots_handler))
+        .route("/calibration", get(calibration_handler))
         .with_state(state);
 
     let listener = TcpListener::bind(addr).await?;
@@ -52,6 +56,7 @@ pub async fn start_metrics_server(
     info!("  Metrics:    /metrics");
     info!("  Health:     /health");
     info!("  Spot API:   /spots/filters, /spots/filters/{{name}}");
+    info!("  Offsets:    /calibration");
 
     axum::serve(listener, app)
         .await
@@ -78,6 +83,8 @@ async fn metrics_handler(State(state): State<MetricsState>) -> impl IntoResponse
 struct GetSpotsQuery {
     /// Return spots with sequence > this value.
     since: Option<u64>,
+    /// Only return spots from this base station (all of its skimmers).
+    station: Option<String>,
 }
 
 /// Response for the get spots endpoint.
@@ -132,7 +139,10 @@ async fn get_spots_handler(
 
     let filter_storage = filter_storage_lock.read().unwrap();
     let since = query.since.unwrap_or(0);
-    let spots = filter_storage.get_spots_since(since);
+    let spots = match &query.station {
+        Some(station) => filter_storage.get_station_spots_since(since, station),
+        None => filter_storage.get_spots_since(since),
+    };
     let latest_seq = filter_storage.latest_seq();
     let overflow_count = filter_storage.overflow_count.load(Relaxed);
 
@@ -146,6 +156,22 @@ async fn get_spots_handler(
     (StatusCode::OK, Json(response)).into_response()
 }
 
+/// Get the estimated frequency offset of every calibrated skimmer.
+async fn calibration_handler(State(state): State<MetricsState>) -> impl IntoResponse {
+    match state.stats.calibrator() {
+        Some(calibrator) => (StatusCode::OK, Json(calibrator.offsets())).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "Calibration not configured"})),
+        )
+            .into_response(),
+    }
+}
+
+/// Parse failure reasons exported as 0 before the first such failure, so the
+/// `rbn_parse_failures_total` series exist from startup.
+const PRESEEDED_PARSE_FAILURE_REASONS: &[&str] = &["invalid_format", "incomplete", "unknown"];
+
 /// Format statistics as Prometheus text format.
 fn format_prometheus_metrics(stats: &SpotStats, storage: Option<&SpotStorage>) -> String {
     let summary = stats.summary();
@@ -162,7 +188,11 @@ fn format_prometheus_metrics(stats: &SpotStats, storage: Option<&SpotStorage>) -
     output.push_str("# HELP rbn_spots_total Total number of spots parsed\n");
     output.push_str("# TYPE rbn_spots_total counter\n");
     for (mode, count) in &summary.spots_by_mode {
-        output.push_str(&format!("rbn_spots_total{{mode=\"{}\"}} {}\n", mode, count));
+        output.push_str(&format!(
+            "rbn_spots_total{{mode=\"{}\"}} {}\n",
+            escape_label_value(mode),
+            count
+        ));
     }
     // Also output total if modes map is empty
     if summary.spots_by_mode.is_empty() {
@@ -172,10 +202,21 @@ fn format_prometheus_metrics(stats: &SpotStats, storage: Option<&SpotStorage>) -
     // Parse failures
     output.push_str("# HELP rbn_parse_failures_total Number of lines that failed to parse\n");
     output.push_str("# TYPE rbn_parse_failures_total counter\n");
-    output.push_str(&format!(
-        "rbn_parse_failures_total {}\n",
-        summary.parse_failures
-    ));
+    for (reason, count) in &summary.parse_failures_by_reason {
+        output.push_str(&format!(
+            "rbn_parse_failures_total{{reason=\"{}\"}} {}\n",
+            escape_label_value(reason),
+            count
+        ));
+    }
+    for reason in PRESEEDED_PARSE_FAILURE_REASONS {
+        if !summary.parse_failures_by_reason.contains_key(*reason) {
+            output.push_str(&format!(
+                "rbn_parse_failures_total{{reason=\"{}\"}} 0\n",
+                reason
+            ));
+        }
+    }
 
     // Non-spot lines
     output.push_str("# HELP rbn_non_spot_lines_total Number of non-spot lines received\n");
@@ -185,6 +226,19 @@ fn format_prometheus_metrics(stats: &SpotStats, storage: Option<&SpotStorage>) -
         summary.non_spot_lines
     ));
 
+    // Non-spot lines by kind
+    output.push_str(
+        "# HELP rbn_non_spot_lines_by_kind_total Non-spot lines broken down by line kind\n",
+    );
+    output.push_str("# TYPE rbn_non_spot_lines_by_kind_total counter\n");
+    for (kind, count) in &summary.non_spot_by_kind {
+        output.push_str(&format!(
+            "rbn_non_spot_lines_by_kind_total{{kind=\"{}\"}} {}\n",
+            escape_label_value(kind),
+            count
+        ));
+    }
+
     // Bytes processed
     output.push_str("# HELP rbn_bytes_processed_total Total bytes of raw input processed\n");
     output.push_str("# TYPE rbn_bytes_processed_total counter\n");
@@ -193,6 +247,67 @@ fn format_prometheus_metrics(stats: &SpotStats, storage: Option<&SpotStorage>) -
         summary.bytes_processed
     ));
 
+    // Reconnection backoff per source
+    let mut connections: Vec<_> = summary.connections.iter().collect();
+    connections.sort_by(|a, b| a.0.cmp(b.0));
+    output.push_str("# HELP rbn_reconnect_attempts_total Total number of reconnection attempts\n");
+    output.push_str("# TYPE rbn_reconnect_attempts_total counter\n");
+    for (source, c) in &connections {
+        output.push_str(&format!(
+            "rbn_reconnect_attempts_total{{source=\"{}\"}} {}\n",
+            escape_label_value(source),
+            c.reconnect_attempts
+        ));
+    }
+    output.push_str(
+        "# HELP rbn_reconnect_consecutive_attempts Reconnection attempts since the backoff last started over\n",
+    );
+    output.push_str("# TYPE rbn_reconnect_consecutive_attempts gauge\n");
+    for (source, c) in &connections {
+        output.push_str(&format!(
+            "rbn_reconnect_consecutive_attempts{{source=\"{}\"}} {}\n",
+            escape_label_value(source),
+            c.consecutive_reconnects
+        ));
+    }
+    output.push_str(
+        "# HELP rbn_reconnect_backoff_seconds Backoff delay before the latest reconnection attempt\n",
+    );
+    output.push_str("# TYPE rbn_reconnect_backoff_seconds gauge\n");
+    for (source, c) in &connections {
+        output.push_str(&format!(
+            "rbn_reconnect_backoff_seconds{{source=\"{}\"}} {:.3}\n",
+            escape_label_value(source),
+            c.reconnect_backoff_secs
+        ));
+    }
+
+    // Endpoint in use per source
+    output.push_str(
+        "# HELP rbn_active_endpoint Endpoint of the current or last login, labelled by host:port\n",
+    );
+    output.push_str("# TYPE rbn_active_endpoint gauge\n");
+    for (source, c) in &connections {
+        if let Some(ref endpoint) = c.endpoint {
+            output.push_str(&format!(
+                "rbn_active_endpoint{{source=\"{}\",endpoint=\"{}\"}} 1\n",
+                escape_label_value(source),
+                escape_label_value(endpoint)
+            ));
+        }
+    }
+    output.push_str(
+        "# HELP rbn_endpoint_switches_total Logins to a different endpoint than the one before\n",
+    );
+    output.push_str("# TYPE rbn_endpoint_switches_total counter\n");
+    for (source, c) in &connections {
+        output.push_str(&format!(
+            "rbn_endpoint_switches_total{{source=\"{}\"}} {}\n",
+            escape_label_value(source),
+            c.endpoint_switches
+        ));
+    }
+
     // Spots per second rate
     output.push_str("# HELP rbn_spots_per_second Current spot processing rate\n");
     output.push_str("# TYPE rbn_spots_per_second gauge\n");
@@ -207,7 +322,8 @@ fn format_prometheus_metrics(stats: &SpotStats, storage: Option<&SpotStorage>) -
     for (band, count) in &summary.spots_by_band {
         output.push_str(&format!(
             "rbn_spots_by_band_total{{band=\"{}\"}} {}\n",
-            band, count
+            escape_label_value(band),
+            count
         ));
     }
 
@@ -217,7 +333,44 @@ fn format_prometheus_metrics(stats: &SpotStats, storage: Option<&SpotStorage>) -
     for (spot_type, count) in &summary.spots_by_type {
         output.push_str(&format!(
             "rbn_spots_by_type_total{{type=\"{}\"}} {}\n",
-            spot_type, count
+            escape_label_value(spot_type),
+            count
+        ));
+    }
+
+    // Spots by source
+    output.push_str("# HELP rbn_spots_by_source_total Spots broken down by source feed\n");
+    output.push_str("# TYPE rbn_spots_by_source_total counter\n");
+    for (source, count) in &summary.spots_by_source {
+        output.push_str(&format!(
+            "rbn_spots_by_source_total{{source=\"{}\"}} {}\n",
+            escape_label_value(source),
+            count
+        ));
+    }
+
+    // Spots by continent, for spots resolved from the country file
+    output.push_str(
+        "# HELP rbn_spots_by_dx_continent_total Spots broken down by DX station continent\n",
+    );
+    output.push_str("# TYPE rbn_spots_by_dx_continent_total counter\n");
+    for (continent, count) in &summary.spots_by_dx_continent {
+        output.push_str(&format!(
+            "rbn_spots_by_dx_continent_total{{continent=\"{}\"}} {}\n",
+            escape_label_value(continent),
+            count
+        ));
+    }
+
+    output.push_str(
+        "# HELP rbn_spots_by_spotter_continent_total Spots broken down by spotter continent\n",
+    );
+    output.push_str("# TYPE rbn_spots_by_spotter_continent_total counter\n");
+    for (continent, count) in &summary.spots_by_spotter_continent {
+        output.push_str(&format!(
+            "rbn_spots_by_spotter_continent_total{{continent=\"{}\"}} {}\n",
+            escape_label_value(continent),
+            count
         ));
     }
 
@@ -237,7 +390,7 @@ fn format_prometheus_metrics(stats: &SpotStats, storage: Option<&SpotStorage>) -
             "rbn_snr_db{{quantile=\"0.99\"}} {}\n",
             snr.p99 as i64
         ));
-        output.push_str(&format!("rbn_snr_db_count {}\n", summary.total_spots));
+        output.push_str(&format!("rbn_snr_db_count {}\n", snr.count));
     }
 
     // WPM histogram buckets
@@ -247,7 +400,27 @@ fn format_prometheus_metrics(stats: &SpotStats, storage: Option<&SpotStorage>) -
         output.push_str(&format!("rbn_wpm{{quantile=\"0.5\"}} {}\n", wpm.p50));
         output.push_str(&format!("rbn_wpm{{quantile=\"0.9\"}} {}\n", wpm.p90));
         output.push_str(&format!("rbn_wpm{{quantile=\"0.99\"}} {}\n", wpm.p99));
-        output.push_str(&format!("rbn_wpm_count {}\n", summary.total_spots));
+        output.push_str(&format!("rbn_wpm_count {}\n", wpm.count));
+    }
+
+    // BPS histogram buckets
+    if let Some(ref bps) = summary.bps_percentiles
+        && bps.count > 0
+    {
+        output.push_str("# HELP rbn_bps BPS (digital symbol rate) distribution\n");
+        output.push_str("# TYPE rbn_bps summary\n");
+        output.push_str(&format!("rbn_bps{{quantile=\"0.5\"}} {}\n", bps.p50));
+        output.push_str(&format!("rbn_bps{{quantile=\"0.9\"}} {}\n", bps.p90));
+        output.push_str(&format!("rbn_bps{{quantile=\"0.99\"}} {}\n", bps.p99));
+        output.push_str(&format!("rbn_bps_count {}\n", bps.count));
+    }
+
+    // Latest propagation bulletin values
+    format_propagation_metrics(&mut output, &summary.propagation);
+
+    // Per-skimmer frequency offsets (if calibration is enabled)
+    if let Some(calibrator) = stats.calibrator() {
+        format_calibration_metrics(&mut output, &calibrator.offsets());
     }
 
     // Storage metrics (if storage is configured)
@@ -258,6 +431,92 @@ fn format_prometheus_metrics(stats: &SpotStats, storage: Option<&SpotStorage>) -
     output
 }
 
+/// Escape a label value for the Prometheus text format: backslash, double
+/// quote and newline are the characters that need it.
+fn escape_label_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Format the latest WWV/WCY bulletin values in Prometheus text format.
+fn format_propagation_metrics(
+    output: &mut String,
+    propagation: &HashMap<String, PropagationReport>,
+) {
+    output.push_str(
+        "# HELP rbn_solar_flux_index Latest solar flux index from propagation bulletins\n",
+    );
+    output.push_str("# TYPE rbn_solar_flux_index gauge\n");
+    for (source, report) in propagation {
+        if let Some(sfi) = report.sfi {
+            output.push_str(&format!(
+                "rbn_solar_flux_index{{source=\"{}\"}} {}\n",
+                escape_label_value(source),
+                sfi
+            ));
+        }
+    }
+
+    output.push_str("# HELP rbn_a_index Latest planetary A index from propagation bulletins\n");
+    output.push_str("# TYPE rbn_a_index gauge\n");
+    for (source, report) in propagation {
+        if let Some(a_index) = report.a_index {
+            output.push_str(&format!(
+                "rbn_a_index{{source=\"{}\"}} {}\n",
+                escape_label_value(source),
+                a_index
+            ));
+        }
+    }
+
+    output.push_str("# HELP rbn_k_index Latest planetary K index from propagation bulletins\n");
+    output.push_str("# TYPE rbn_k_index gauge\n");
+    for (source, report) in propagation {
+        if let Some(k_index) = report.k_index {
+            output.push_str(&format!(
+                "rbn_k_index{{source=\"{}\"}} {}\n",
+                escape_label_value(source),
+                k_index
+            ));
+        }
+    }
+}
+
+/// Format per-skimmer frequency offsets in Prometheus text format.
+fn format_calibration_metrics(output: &mut String, offsets: &[SpotterOffset]) {
+    output.push_str(
+        "# HELP rbn_spotter_frequency_offset_hz Estimated frequency offset of each skimmer from the consensus\n",
+    );
+    output.push_str("# TYPE rbn_spotter_frequency_offset_hz gauge\n");
+    for offset in offsets {
+        output.push_str(&format!(
+            "rbn_spotter_frequency_offset_hz{{spotter=\"{}\"}} {:.1}\n",
+            escape_label_value(&offset.spotter),
+            offset.offset_khz * 1000.0
+        ));
+    }
+
+    output.push_str(
+        "# HELP rbn_spotter_frequency_offset_samples Consensus comparisons behind each skimmer's offset\n",
+    );
+    output.push_str("# TYPE rbn_spotter_frequency_offset_samples gauge\n");
+    for offset in offsets {
+        output.push_str(&format!(
+            "rbn_spotter_frequency_offset_samples{{spotter=\"{}\"}} {}\n",
+            escape_label_value(&offset.spotter),
+            offset.samples
+        ));
+    }
+}
+
 /// Format storage metrics in Prometheus text format.
 fn format_storage_metrics(output: &mut String, storage: &SpotStorage) {
     // Per-filter metrics
@@ -275,7 +534,7 @@ fn format_storage_metrics(output: &mut String, storage: &SpotStorage) {
 
     for (_, storage_lock) in storage.iter_storages() {
         let fs = storage_lock.read().unwrap();
-        let name = &fs.name;
+        let name = escape_label_value(&fs.name);
 
         output.push_str(&format!(
             "rbn_filter_stored_spots{{filter=\"{}\"}} {}\n",
@@ -333,27 +592,36 @@ mod tests {
         let output = format_prometheus_metrics(&stats, None);
 
         assert!(output.contains("rbn_uptime_seconds"));
-        assert!(output.contains("rbn_parse_failures_total 0"));
+        assert!(output.contains("rbn_parse_failures_total{reason=\"invalid_format\"} 0"));
+        assert!(output.contains("rbn_parse_failures_total{reason=\"unknown\"} 0"));
+        assert!(!output.contains("rbn_parse_failures_total 0"));
         assert!(output.contains("rbn_non_spot_lines_total 0"));
         assert!(output.contains("rbn_bytes_processed_total 0"));
     }
 
     #[test]
     fn test_format_prometheus_metrics_with_data() {
-        use crate::spot::{CwSpot, Mode, SpotType};
+        use crate::frequency::Frequency;
+        use crate::spot::{CwSpot, Mode, Speed, SpotType};
         use chrono::NaiveTime;
 
         let stats = SpotStats::new();
 
         let spot = CwSpot {
             spotter: "TEST-#".to_string(),
-            frequency_khz: 14025.0,
+            frequency: Frequency::from_khz(14025.0),
             dx_call: "W1AW".to_string(),
             mode: Mode::Cw,
-            snr_db: 15,
-            wpm: 22,
+            snr_db: Some(15),
+            speed: Some(Speed::Wpm(22)),
             spot_type: SpotType::Cq,
             time: NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+            comment: None,
+            received_at: None,
+            timestamp: None,
+            dx_dxcc: None,
+            spotter_dxcc: None,
+            source: None,
         };
 
         stats.record_spot(&spot);
@@ -367,6 +635,149 @@ mod tests {
         assert!(output.contains("rbn_spots_by_type_total{type=\"CQ\"} 1"));
     }
 
+    #[test]
+    fn test_format_prometheus_metrics_sources() {
+        use crate::parser::parse_spot;
+
+        let stats = SpotStats::new();
+        let spot = parse_spot(
+            "DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z",
+        )
+        .unwrap();
+
+        stats.record_spot(&spot);
+        stats.record_spot(&spot.clone().with_source("rbn-cw"));
+        stats.record_spot(&spot.clone().with_source("skimmer"));
+        stats.record_spot(&spot.with_source("skimmer"));
+
+        let output = format_prometheus_metrics(&stats, None);
+
+        assert!(output.contains("# TYPE rbn_spots_by_source_total counter"));
+        assert!(output.contains("rbn_spots_by_source_total{source=\"rbn-cw\"} 1"));
+        assert!(output.contains("rbn_spots_by_source_total{source=\"skimmer\"} 2"));
+        // Untagged spots count in the totals only
+        assert!(output.contains("rbn_spots_total{mode=\"CW\"} 4"));
+        assert_eq!(output.matches("rbn_spots_by_source_total{").count(), 2);
+    }
+
+    #[test]
+    fn test_format_prometheus_metrics_parse_failure_reasons() {
+        use crate::parser::parse_line;
+
+        let stats = SpotStats::new();
+        let line = "DX de EA5WU-#:    7018.3  RW1M           C?    19 dB  18 WPM  CQ      2259Z";
+        stats.record_parse_error(&parse_line(line).unwrap_err());
+
+        let output = format_prometheus_metrics(&stats, None);
+
+        assert!(output.contains("rbn_parse_failures_total{reason=\"invalid_mode\"} 1"));
+        assert!(output.contains("rbn_parse_failures_total{reason=\"invalid_format\"} 0"));
+        assert!(!output.contains("rbn_parse_failures_total 0"));
+    }
+
+    #[test]
+    fn test_format_prometheus_metrics_continents() {
+        use crate::dxcc::DxccDatabase;
+        use crate::parser::parse_spot;
+
+        let dxcc =
+            DxccDatabase::parse_dat("Spain: 14: 37: EU: 40.37: 4.88: -1.0: EA:\n EA;").unwrap();
+        let stats = SpotStats::new();
+        let line = "DX de EA5WU-#:    7018.3  EA1ABC         CW    19 dB  18 WPM  CQ      2259Z";
+        stats.record_spot(&parse_spot(line).unwrap().with_dxcc(&dxcc));
+
+        let output = format_prometheus_metrics(&stats, None);
+
+        assert!(output.contains("rbn_spots_by_dx_continent_total{continent=\"EU\"} 1"));
+        assert!(output.contains("rbn_spots_by_spotter_continent_total{continent=\"EU\"} 1"));
+    }
+
+    #[test]
+    fn test_format_prometheus_metrics_propagation() {
+        use crate::parser::parse_line;
+
+        let stats = SpotStats::new();
+        for line in [
+            "WWV de W0MU <18>:   SFI=70, A=5, K=1, No Storms -> No Storms",
+            "WCY de DK0WCY-1 <12> : K=2 expK=3 A=8 R=15 SFI=71 SA=qui GMF=qui Au=no",
+            "W6JSV de RELAY 08-Jan-2026 03:13Z >",
+        ] {
+            stats.record_non_spot_line(&parse_line(line).unwrap());
+        }
+
+        let output = format_prometheus_metrics(&stats, None);
+
+        assert!(output.contains("rbn_solar_flux_index{source=\"WWV\"} 70"));
+        assert!(output.contains("rbn_solar_flux_index{source=\"WCY\"} 71"));
+        assert!(output.contains("rbn_a_index{source=\"WWV\"} 5"));
+        assert!(output.contains("rbn_k_index{source=\"WCY\"} 2"));
+        assert!(output.contains("rbn_non_spot_lines_by_kind_total{kind=\"propagation\"} 2"));
+        assert!(output.contains("rbn_non_spot_lines_by_kind_total{kind=\"prompt\"} 1"));
+    }
+
+    #[test]
+    fn test_format_prometheus_metrics_calibration() {
+        use crate::calibration::FrequencyCalibrator;
+        use crate::config::CalibrationConfig;
+        use crate::parser::parse_spot;
+
+        let calibrator = FrequencyCalibrator::new(&CalibrationConfig {
+            min_samples: 1,
+            ..Default::default()
+        });
+        let stats = SpotStats::new().with_calibrator(Arc::new(calibrator));
+        let received = "2026-01-09T23:00:00Z".parse().unwrap();
+        for line in [
+            "DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z",
+            "DX de KM3T-#:     7018.3  RW1M           CW    12 dB  18 WPM  CQ      2259Z",
+            "DX de W3OA-#:     7018.2  RW1M           CW     8 dB  18 WPM  CQ      2259Z",
+        ] {
+            stats.record_spot(&parse_spot(line).unwrap().with_received_at(received));
+        }
+
+        let output = format_prometheus_metrics(&stats, None);
+
+        assert!(output.contains("rbn_spotter_frequency_offset_hz{spotter=\"W3OA-#\"} -100.0"));
+        assert!(output.contains("rbn_spotter_frequency_offset_samples{spotter=\"W3OA-#\"} 1"));
+        assert!(!output.contains("spotter=\"EA5WU-#\""));
+    }
+
+    #[test]
+    fn test_format_prometheus_metrics_reconnects() {
+        let stats = SpotStats::new();
+        stats.record_connected("rbn-cw", "telnet.reversebeacon.net:7000");
+        stats.record_connected("rbn-cw", "mirror.example.net:7000");
+        stats.record_reconnect("skimmer", 1, std::time::Duration::from_millis(1500));
+
+        let output = format_prometheus_metrics(&stats, None);
+
+        assert!(output.contains("rbn_reconnect_attempts_total{source=\"rbn-cw\"} 0\n"));
+        assert!(output.contains("rbn_reconnect_attempts_total{source=\"skimmer\"} 1\n"));
+        assert!(output.contains("rbn_reconnect_consecutive_attempts{source=\"skimmer\"} 1\n"));
+        assert!(output.contains("rbn_reconnect_backoff_seconds{source=\"skimmer\"} 1.500\n"));
+        assert!(output.contains(
+            "rbn_active_endpoint{source=\"rbn-cw\",endpoint=\"mirror.example.net:7000\"} 1\n"
+        ));
+        assert!(!output.contains("rbn_active_endpoint{source=\"skimmer\""));
+        assert!(output.contains("rbn_endpoint_switches_total{source=\"rbn-cw\"} 1\n"));
+    }
+
+    #[test]
+    fn test_format_prometheus_metrics_escapes_labels() {
+        assert_eq!(escape_label_value("rbn"), "rbn");
+        assert_eq!(escape_label_value("a\\b \"c\"\nd"), "a\\\\b \\\"c\\\"\\nd");
+
+        let stats = SpotStats::new();
+        stats.record_connected("node \"east\"", "dxc\\1:7300");
+
+        let output = format_prometheus_metrics(&stats, None);
+
+        assert!(output.contains(
+            "rbn_active_endpoint{source=\"node \\\"east\\\"\",endpoint=\"dxc\\\\1:7300\"} 1\n"
+        ));
+        assert!(output.contains("rbn_endpoint_switches_total{source=\"node \\\"east\\\"\"} 0\n"));
+    }
+
     #[test]
     fn test_prometheus_format_validity() {
         let stats = SpotStats::new();
@@ -8,49 +8,211 @@
 //!
 //! RBN spots follow this general format:
 //! ```text
-//! DX de SPOTTER:  FREQ  CALLSIGN  MODE  SNR dB  WPM WPM  TYPE  TIMEZ
+//! DX de SPOTTER:  FREQ  CALLSIGN  MODE  SNR dB  SPEED UNIT  TYPE  TIMEZ
 //! ```
 //!
-//! Example:
+//! The speed is reported in WPM for CW and in BPS for RTTY/PSK, and may be
+//! absent entirely.
+//!
+//! Examples:
 //! ```text
 //! DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z
+//! DX de W3OA-#:    14080.0  K1ABC          RTTY  12 dB  45 BPS  CQ      2259Z
+//! ```
+//!
+//! The digital feed (port 7001) uses the same layout for FT8/FT4 spots, but
+//! never includes a speed, commonly reports negative SNRs, and may omit the
+//! type field or use type tokens other than CQ:
+//! ```text
+//! DX de KD2OGR-#:  14074.0  JA1XYZ         FT8   -12 dB  CQ      2259Z
+//! DX de W3OA-#:     7047.5  K1ABC          FT4    -3 dB          0014Z
+//! ```
+//!
+//! Human spots from DXSpider/AR-Cluster nodes have no mode, SNR or speed,
+//! just a free-text comment and optionally the spotter's grid locator:
+//! ```text
+//! DX de K1ABC:     14025.0  JA1XYZ       tnx qso 599                    1234Z
+//! DX de W3LPL:      7005.0  VP8LP        up 2                           0312Z FM19
+//! ```
+//! These are handled by [`parse_cluster_spot`].
+//!
+//! # Other Lines
+//!
+//! [`parse_line`] classifies every line into an [`RbnLine`], including
+//! WWV/WCY propagation bulletins, "To ALL" announcements, the login banner
+//! and command prompts:
+//! ```text
+//! WWV de W0MU <18>:   SFI=70, A=5, K=1, No Storms -> No Storms
+//! WCY de DK0WCY-1 <12> : K=2 expK=3 A=8 R=15 SFI=70 SA=qui GMF=qui Au=no
+//! To ALL de K1ABC: CQ contest this weekend
+//! W6JSV de RELAY 08-Jan-2026 03:13Z >
 //! ```
 
 use chrono::NaiveTime;
 use nom::{
     IResult, Parser,
     branch::alt,
-    bytes::complete::{tag_no_case, take_while1},
-    character::complete::{char, digit1, multispace1, space0, space1},
-    combinator::{map_res, opt, recognize, value},
-    sequence::terminated,
+    bytes::complete::{tag_no_case, take_while, take_while1},
+    character::complete::{char, digit1, multispace1, satisfy, space0, space1},
+    combinator::{all_consuming, map, map_res, opt, recognize, value},
+    sequence::{delimited, terminated},
 };
+use std::fmt;
 use thiserror::Error;
 
-use crate::spot::{CwSpot, Mode, SpotType};
+use crate::frequency::Frequency;
+use crate::line::{Announcement, PropagationReport, PropagationSource, RbnLine};
+use crate::spot::{CwSpot, CwSpotRef, Mode, ModeRef, Speed, SpotType, SpotTypeRef};
+
+/// Lowercase prefixes of lines sent as part of the login banner.
+const BANNER_PREFIXES: &[&str] = &[
+    "please enter your call",
+    "login:",
+    "hello",
+    "welcome",
+    "connected to",
+    "local users",
+];
+
+/// A field of a spot line, used to report where parsing failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpotField {
+    /// The leading "DX de"
+    Prefix,
+    /// Spotter callsign and its trailing colon
+    Spotter,
+    /// Frequency in kHz
+    Frequency,
+    /// DX callsign
+    DxCall,
+    /// Transmission mode
+    Mode,
+    /// Signal-to-noise ratio
+    Snr,
+    /// WPM/BPS speed
+    Speed,
+    /// Spot type (CQ, BEACON, etc.)
+    SpotType,
+    /// HHMMZ time
+    Time,
+}
+
+impl SpotField {
+    /// Returns the field name as used in error messages and metric labels.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpotField::Prefix => "prefix",
+            SpotField::Spotter => "spotter",
+            SpotField::Frequency => "frequency",
+            SpotField::DxCall => "dx_call",
+            SpotField::Mode => "mode",
+            SpotField::Snr => "snr",
+            SpotField::Speed => "speed",
+            SpotField::SpotType => "type",
+            SpotField::Time => "time",
+        }
+    }
+}
+
+impl fmt::Display for SpotField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
 /// Errors that can occur during parsing.
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, PartialEq, Error)]
 pub enum ParseError {
-    #[error("Invalid spot format: {0}")]
+    #[error("Invalid format: {0}")]
     InvalidFormat(String),
 
-    #[error("Invalid frequency: {0}")]
-    InvalidFrequency(String),
-
-    #[error("Invalid time: {0}")]
-    InvalidTime(String),
+    #[error("Invalid {field} at byte {offset}: {text:?}")]
+    InvalidField {
+        /// The field that failed to parse.
+        field: SpotField,
+        /// Byte offset of the field within the input line.
+        offset: usize,
+        /// The offending text (the whitespace-delimited token at `offset`).
+        text: String,
+    },
 
     #[error("Missing required field: {0}")]
-    MissingField(&'static str),
+    MissingField(SpotField),
 
     #[error("Incomplete input")]
     Incomplete,
 }
 
+impl ParseError {
+    /// Returns a short, stable label describing the failure, for metrics.
+    ///
+    /// Examples: `invalid_mode`, `missing_time`, `invalid_format`.
+    pub fn reason(&self) -> String {
+        match self {
+            ParseError::InvalidFormat(_) => "invalid_format".to_string(),
+            ParseError::InvalidField { field, .. } => format!("invalid_{}", field),
+            ParseError::MissingField(field) => format!("missing_{}", field),
+            ParseError::Incomplete => "incomplete".to_string(),
+        }
+    }
+}
+
 /// Result type for parsing operations.
 pub type ParseResult<T> = Result<T, ParseError>;
 
+/// Walks a line field by field, attaching field and position to failures.
+struct FieldCursor<'a> {
+    /// Unparsed remainder of the line.
+    rest: &'a str,
+    /// Length of the (trimmed) line being parsed.
+    len: usize,
+    /// Bytes trimmed from the start of the original line.
+    leading: usize,
+}
+
+impl<'a> FieldCursor<'a> {
+    /// Start a cursor over `line`, skipping leading whitespace.
+    fn new(line: &'a str) -> Self {
+        let trimmed = line.trim();
+        Self {
+            rest: trimmed,
+            len: trimmed.len(),
+            leading: line.len() - line.trim_start().len(),
+        }
+    }
+
+    /// Byte offset of the cursor within the original line.
+    fn offset(&self) -> usize {
+        self.leading + self.len - self.rest.len()
+    }
+
+    /// Run `parser` for `field`, advancing past its output on success.
+    fn field<O, P>(&mut self, field: SpotField, mut parser: P) -> ParseResult<O>
+    where
+        P: Parser<&'a str, Output = O, Error = nom::error::Error<&'a str>>,
+    {
+        match parser.parse(self.rest) {
+            Ok((rest, output)) => {
+                self.rest = rest;
+                Ok(output)
+            }
+            Err(_) => Err(self.error(field)),
+        }
+    }
+
+    /// Build the error for a failure of `field` at the current position.
+    fn error(&self, field: SpotField) -> ParseError {
+        match self.rest.split_whitespace().next() {
+            Some(text) => ParseError::InvalidField {
+                field,
+                offset: self.offset() + (self.rest.len() - self.rest.trim_start().len()),
+                text: text.to_string(),
+            },
+            None => ParseError::MissingField(field),
+        }
+    }
+}
+
 /// Check if a character is valid in a callsign.
 ///
 /// Valid callsign characters are alphanumeric plus `/` for portable designators
@@ -83,64 +245,84 @@ fn parse_spotter(input: &str) -> IResult<&str, &str> {
     terminated(parse_callsign, (char(':'), space0)).parse(input)
 }
 
-/// Parse a floating-point frequency in kHz.
-fn parse_frequency(input: &str) -> IResult<&str, f64> {
+/// Parse a decimal frequency in kHz, exactly.
+fn parse_frequency(input: &str) -> IResult<&str, Frequency> {
     map_res(recognize((digit1, opt((char('.'), digit1)))), |s: &str| {
-        s.parse::<f64>()
+        s.parse::<Frequency>()
     })
     .parse(input)
 }
 
-/// Parse the transmission mode.
-fn parse_mode(input: &str) -> IResult<&str, Mode> {
-    alt((
-        value(Mode::Cw, tag_no_case("CW")),
-        value(Mode::Rtty, tag_no_case("RTTY")),
-        value(Mode::Ft8, tag_no_case("FT8")),
-        value(Mode::Ft4, tag_no_case("FT4")),
-        value(Mode::Psk31, tag_no_case("PSK31")),
+/// Parse a word token: a letter followed by letters and digits.
+///
+/// Mode and spot type tokens look like this, which keeps them apart from
+/// the numbers and times around them.
+fn parse_word(input: &str) -> IResult<&str, &str> {
+    recognize((
+        satisfy(|c| c.is_ascii_alphabetic()),
+        take_while(|c: char| c.is_ascii_alphanumeric()),
     ))
     .parse(input)
 }
 
-/// Parse the signal-to-noise ratio (e.g., "19 dB" or "-5 dB").
+/// Parse the transmission mode, keeping unrecognized modes as
+/// [`ModeRef::Other`].
+fn parse_mode(input: &str) -> IResult<&str, ModeRef<'_>> {
+    map(parse_word, ModeRef::from_name).parse(input)
+}
+
+/// Parse a standalone mode token such as "CW" or "ft8".
+pub(crate) fn parse_mode_token(token: &str) -> Option<Mode> {
+    all_consuming(parse_mode)
+        .parse(token.trim())
+        .ok()
+        .map(|(_, mode)| mode.into_owned())
+}
+
+/// Parse the signal-to-noise ratio (e.g., "19 dB", "-5 dB" or "+3 dB").
 fn parse_snr(input: &str) -> IResult<&str, i32> {
     terminated(
-        map_res(recognize((opt(char('-')), digit1)), |s: &str| {
-            s.parse::<i32>()
-        }),
-        (space1, tag_no_case("dB")),
+        map_res(
+            recognize((opt(alt((char('-'), char('+')))), digit1)),
+            |s: &str| s.parse::<i32>(),
+        ),
+        (space0, tag_no_case("dB")),
     )
     .parse(input)
 }
 
-/// Parse the CW speed in WPM (e.g., "18 WPM").
-fn parse_wpm(input: &str) -> IResult<&str, u16> {
-    terminated(
-        map_res(digit1, |s: &str| s.parse::<u16>()),
-        (space1, tag_no_case("WPM")),
-    )
+/// Parse the reported speed (e.g., "18 WPM" or "45 BPS").
+fn parse_speed(input: &str) -> IResult<&str, Speed> {
+    let (input, speed) = map_res(digit1, |s: &str| s.parse::<u16>()).parse(input)?;
+    let (input, _) = space1(input)?;
+    alt((
+        value(Speed::Wpm(speed), tag_no_case("WPM")),
+        value(Speed::Bps(speed), tag_no_case("BPS")),
+    ))
     .parse(input)
 }
 
-/// Parse the spot type (CQ, BEACON, NCDXF B, etc.).
-fn parse_spot_type(input: &str) -> IResult<&str, SpotType> {
+/// Parse the spot type (CQ, BEACON, NCDXF B, etc.), keeping unrecognized
+/// single-token types as [`SpotTypeRef::Other`].
+fn parse_spot_type(input: &str) -> IResult<&str, SpotTypeRef<'_>> {
     alt((
         value(
-            SpotType::NcdxfBeacon,
+            SpotTypeRef::NcdxfBeacon,
             (tag_no_case("NCDXF"), space1, tag_no_case("B")),
         ),
-        value(SpotType::Beacon, tag_no_case("BEACON")),
-        value(SpotType::Cq, tag_no_case("CQ")),
-        // Catch-all for other types we might not recognize
-        value(
-            SpotType::Other,
-            take_while1(|c: char| c.is_ascii_alphanumeric() || c == ' '),
-        ),
+        map(parse_word, SpotTypeRef::from_name),
     ))
     .parse(input)
 }
 
+/// Parse a standalone spot type token such as "CQ" or "NCDXF B".
+pub(crate) fn parse_spot_type_token(token: &str) -> Option<SpotType> {
+    all_consuming(parse_spot_type)
+        .parse(token.trim())
+        .ok()
+        .map(|(_, spot_type)| spot_type.into_owned())
+}
+
 /// Parse the full UTC time from a 4-digit string like "2259Z".
 fn parse_time_full(input: &str) -> IResult<&str, NaiveTime> {
     map_res(
@@ -157,7 +339,7 @@ fn parse_time_full(input: &str) -> IResult<&str, NaiveTime> {
     .parse(input)
 }
 
-/// Parse a complete RBN CW spot line.
+/// Parse a complete RBN spot line from either the CW/RTTY or the digital feed.
 ///
 /// # Example
 ///
@@ -170,46 +352,369 @@ fn parse_time_full(input: &str) -> IResult<&str, NaiveTime> {
 /// assert_eq!(spot.dx_call, "RW1M");
 /// ```
 pub fn parse_spot(input: &str) -> ParseResult<CwSpot> {
-    let input = input.trim();
+    parse_spot_ref(input).map(|spot| spot.into_owned())
+}
 
-    // Use a parser that handles variable whitespace between fields
-    let result: IResult<&str, CwSpot> = (|input| {
-        let (input, _) = parse_dx_de_prefix(input)?;
-        let (input, spotter) = parse_spotter(input)?;
-        let (input, _) = space0(input)?;
-        let (input, frequency_khz) = parse_frequency(input)?;
-        let (input, _) = space1(input)?;
-        let (input, dx_call) = parse_callsign(input)?;
-        let (input, _) = space1(input)?;
-        let (input, mode) = parse_mode(input)?;
-        let (input, _) = space1(input)?;
-        let (input, snr_db) = parse_snr(input)?;
-        let (input, _) = space1(input)?;
-        let (input, wpm) = parse_wpm(input)?;
-        let (input, _) = space1(input)?;
-        let (input, spot_type) = parse_spot_type(input)?;
-        let (input, _) = space0(input)?;
-        let (input, time) = parse_time_full(input)?;
-
-        Ok((
-            input,
-            CwSpot {
-                spotter: spotter.to_string(),
-                frequency_khz,
-                dx_call: dx_call.to_string(),
-                mode,
-                snr_db,
-                wpm,
-                spot_type,
-                time,
+/// Parse a complete RBN spot line without allocating.
+///
+/// The returned [`CwSpotRef`] borrows its callsigns and any unrecognized
+/// mode or type token from `input`, which makes this the cheaper choice for
+/// bulk offline analysis where most spots are filtered out and never need to
+/// be owned.
+///
+/// # Example
+///
+/// ```
+/// use rbn_parser::parser::parse_spot_ref;
+///
+/// let line = "DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z";
+/// let spot = parse_spot_ref(line).unwrap();
+/// assert_eq!(spot.spotter, "EA5WU-#");
+/// assert_eq!(spot.dx_call, "RW1M");
+/// ```
+pub fn parse_spot_ref(input: &str) -> ParseResult<CwSpotRef<'_>> {
+    let mut cursor = FieldCursor::new(input);
+
+    // Each field consumes the whitespace that follows it, so a field running
+    // into its neighbour (e.g. "7018.3X") is reported against that field
+    cursor.field(SpotField::Prefix, parse_dx_de_prefix)?;
+    let spotter = cursor.field(SpotField::Spotter, parse_spotter)?;
+    let frequency = cursor.field(SpotField::Frequency, terminated(parse_frequency, space1))?;
+    let dx_call = cursor.field(SpotField::DxCall, terminated(parse_callsign, space1))?;
+    let mode = cursor.field(SpotField::Mode, terminated(parse_mode, space1))?;
+    let snr_db = cursor.field(SpotField::Snr, terminated(parse_snr, space1))?;
+    let speed = cursor.field(SpotField::Speed, opt(terminated(parse_speed, space1)))?;
+    // The type may be absent on digital spots; the time always follows
+    let spot_type = cursor.field(
+        SpotField::SpotType,
+        opt(terminated(parse_spot_type, space1)),
+    )?;
+    let time = cursor.field(SpotField::Time, all_consuming(parse_time_full))?;
+
+    Ok(CwSpotRef {
+        spotter,
+        frequency,
+        dx_call,
+        mode,
+        snr_db: Some(snr_db),
+        speed,
+        spot_type: spot_type.unwrap_or(SpotTypeRef::Unknown),
+        time,
+        comment: None,
+        received_at: None,
+        timestamp: None,
+        dx_dxcc: None,
+        spotter_dxcc: None,
+        source: None,
+    })
+}
+
+/// Parse a human DX cluster spot with a free-text comment.
+///
+/// The resulting spot has `Mode::Unknown`, `SpotType::Unknown`, no SNR or
+/// speed, and the comment text (if any) between the DX call and the time.
+/// A trailing grid locator after the time is accepted and ignored.
+///
+/// # Example
+///
+/// ```
+/// use rbn_parser::parser::parse_cluster_spot;
+///
+/// let line = "DX de K1ABC:     14025.0  JA1XYZ       tnx qso 599                    1234Z";
+/// let spot = parse_cluster_spot(line).unwrap();
+/// assert_eq!(spot.dx_call, "JA1XYZ");
+/// assert_eq!(spot.comment.as_deref(), Some("tnx qso 599"));
+/// ```
+pub fn parse_cluster_spot(input: &str) -> ParseResult<CwSpot> {
+    let mut cursor = FieldCursor::new(input);
+
+    cursor.field(SpotField::Prefix, parse_dx_de_prefix)?;
+    let spotter = cursor.field(SpotField::Spotter, parse_spotter)?;
+    let frequency = cursor.field(SpotField::Frequency, terminated(parse_frequency, space1))?;
+    let dx_call = cursor.field(SpotField::DxCall, parse_callsign)?;
+
+    let (comment, time) = split_cluster_remark(cursor.rest).ok_or_else(|| {
+        // Report the last token, which is where the time should have been
+        match split_last_token(cursor.rest) {
+            Some((before, text)) => ParseError::InvalidField {
+                field: SpotField::Time,
+                offset: cursor.offset() + before.len() + 1,
+                text: text.to_string(),
             },
+            None => ParseError::MissingField(SpotField::Time),
+        }
+    })?;
+    let comment = comment.trim();
+
+    Ok(CwSpot {
+        spotter: spotter.to_string(),
+        frequency,
+        dx_call: dx_call.to_string(),
+        mode: Mode::Unknown,
+        snr_db: None,
+        speed: None,
+        spot_type: SpotType::Unknown,
+        time,
+        comment: (!comment.is_empty()).then(|| comment.to_string()),
+        received_at: None,
+        timestamp: None,
+        dx_dxcc: None,
+        spotter_dxcc: None,
+        source: None,
+    })
+}
+
+/// Parse a spot line from either an RBN skimmer or a human DX cluster spotter.
+///
+/// The skimmer format is tried first. The free-text cluster format is only
+/// accepted when the spotter isn't an RBN skimmer (no `-#` suffix), so that
+/// malformed skimmer lines still surface as parse failures. Errors for
+/// non-skimmer lines are reported against the cluster format.
+pub fn parse_any_spot(input: &str) -> ParseResult<CwSpot> {
+    parse_spot(input).or_else(|e| {
+        let skimmer = (|input| {
+            let (input, _) = parse_dx_de_prefix(input)?;
+            parse_spotter(input)
+        })(input.trim())
+        .is_ok_and(|(_, spotter): (&str, &str)| spotter.ends_with("-#"));
+
+        if skimmer {
+            Err(e)
+        } else {
+            parse_cluster_spot(input)
+        }
+    })
+}
+
+/// Split the remainder of a cluster spot into its comment and time.
+///
+/// The time is the last whitespace-separated token, or the one before it
+/// when the line ends with a grid locator.
+fn split_cluster_remark(rest: &str) -> Option<(&str, NaiveTime)> {
+    split_time_token(rest).or_else(|| {
+        let (before, locator) = split_last_token(rest)?;
+        if is_grid_locator(locator) {
+            split_time_token(before)
+        } else {
+            None
+        }
+    })
+}
+
+/// Split off a trailing `HHMMZ` token, returning the text before it.
+fn split_time_token(rest: &str) -> Option<(&str, NaiveTime)> {
+    let (before, token) = split_last_token(rest)?;
+    let (_, time) = all_consuming(parse_time_full).parse(token).ok()?;
+    Some((before, time))
+}
+
+/// Split off the last whitespace-separated token.
+///
+/// Requires whitespace before the token so it can't run into the DX call.
+fn split_last_token(rest: &str) -> Option<(&str, &str)> {
+    let rest = rest.trim_end();
+    let idx = rest.rfind(char::is_whitespace)?;
+    Some((&rest[..idx], &rest[idx + 1..]))
+}
+
+/// Check if a token is a Maidenhead grid locator (e.g., "FN42" or "FN42ab").
+fn is_grid_locator(token: &str) -> bool {
+    let b = token.as_bytes();
+    (b.len() == 4 || b.len() == 6)
+        && b[..2]
+            .iter()
+            .all(|c| (b'A'..=b'R').contains(&c.to_ascii_uppercase()))
+        && b[2..4].iter().all(u8::is_ascii_digit)
+        && b[4..].iter().all(u8::is_ascii_alphabetic)
+}
+
+/// Parse the bulletin hour in angle brackets, e.g. "<18>" or "<18Z>".
+fn parse_bulletin_hour(input: &str) -> IResult<&str, u8> {
+    delimited(
+        char('<'),
+        terminated(
+            map_res(digit1, |s: &str| s.parse::<u8>()),
+            opt(tag_no_case("Z")),
+        ),
+        char('>'),
+    )
+    .parse(input)
+}
+
+/// Parse a WWV or WCY propagation bulletin.
+///
+/// # Example
+///
+/// ```
+/// use rbn_parser::parser::parse_propagation;
+///
+/// let line = "WWV de W0MU <18>:   SFI=70, A=5, K=1, No Storms -> No Storms";
+/// let report = parse_propagation(line).unwrap();
+/// assert_eq!(report.sfi, Some(70));
+/// assert_eq!(report.k_index, Some(1));
+/// ```
+pub fn parse_propagation(input: &str) -> ParseResult<PropagationReport> {
+    let input = input.trim();
+
+    let result: IResult<&str, (PropagationSource, &str, Option<u8>)> = (|input| {
+        let (input, source) = alt((
+            value(PropagationSource::Wwv, tag_no_case("WWV")),
+            value(PropagationSource::Wcy, tag_no_case("WCY")),
         ))
+        .parse(input)?;
+        let (input, _) = (multispace1, tag_no_case("de"), multispace1).parse(input)?;
+        let (input, reporter) = parse_callsign(input)?;
+        let (input, _) = space0(input)?;
+        let (input, hour) = opt(parse_bulletin_hour).parse(input)?;
+        let (input, _) = (space0, char(':'), space0).parse(input)?;
+        Ok((input, (source, reporter, hour)))
+    })(input);
+
+    let (body, (source, reporter, hour)) =
+        result.map_err(|e| ParseError::InvalidFormat(format!("{:?}", e)))?;
+
+    let mut report = PropagationReport {
+        source,
+        reporter: reporter.to_string(),
+        hour,
+        sfi: None,
+        a_index: None,
+        k_index: None,
+        expected_k: None,
+        sunspot_number: None,
+        remarks: None,
+    };
+
+    // WWV separates fields with commas, WCY with spaces; segments without
+    // any key=value pairs are forecast remarks
+    let mut remarks = Vec::new();
+    for segment in body.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        if !segment.contains('=') {
+            remarks.push(segment);
+            continue;
+        }
+        for (key, val) in segment.split_whitespace().filter_map(|t| t.split_once('=')) {
+            match key.to_ascii_uppercase().as_str() {
+                "SFI" => report.sfi = val.parse().ok(),
+                "A" => report.a_index = val.parse().ok(),
+                "K" => report.k_index = val.parse().ok(),
+                "EXPK" => report.expected_k = val.parse().ok(),
+                "R" => report.sunspot_number = val.parse().ok(),
+                _ => {}
+            }
+        }
+    }
+
+    if report.sfi.is_none() && report.a_index.is_none() && report.k_index.is_none() {
+        return Err(ParseError::InvalidFormat(format!(
+            "bulletin has no SFI, A or K values: {}",
+            body
+        )));
+    }
+    if !remarks.is_empty() {
+        report.remarks = Some(remarks.join(", "));
+    }
+
+    Ok(report)
+}
+
+/// Parse a "To ALL" announcement.
+///
+/// # Example
+///
+/// ```
+/// use rbn_parser::parser::parse_announcement;
+///
+/// let announcement = parse_announcement("To ALL de K1ABC: CQ contest this weekend").unwrap();
+/// assert_eq!(announcement.from, "K1ABC");
+/// assert_eq!(announcement.message, "CQ contest this weekend");
+/// ```
+pub fn parse_announcement(input: &str) -> ParseResult<Announcement> {
+    let input = input.trim();
+
+    let result: IResult<&str, (&str, &str)> = (|input| {
+        let (input, _) = (tag_no_case("To"), space1).parse(input)?;
+        let (input, target) = take_while1(|c: char| c.is_ascii_alphanumeric()).parse(input)?;
+        let (input, _) = (space1, tag_no_case("de"), space1).parse(input)?;
+        let (input, from) = parse_callsign(input)?;
+        // AR-Cluster adds a "<1234Z>" timestamp before the colon
+        let (input, _) = (
+            space0,
+            opt(delimited(
+                char('<'),
+                take_while1(|c: char| c != '>'),
+                char('>'),
+            )),
+            space0,
+            char(':'),
+            space0,
+        )
+            .parse(input)?;
+        Ok((input, (target, from)))
     })(input);
 
-    match result {
-        Ok((_, spot)) => Ok(spot),
-        Err(e) => Err(ParseError::InvalidFormat(format!("{:?}", e))),
+    let (message, (target, from)) =
+        result.map_err(|e| ParseError::InvalidFormat(format!("{:?}", e)))?;
+
+    Ok(Announcement {
+        target: target.to_string(),
+        from: from.to_string(),
+        message: message.to_string(),
+    })
+}
+
+/// Parse and classify any line received from the server.
+///
+/// Returns an error only for lines that look like a spot, bulletin or
+/// announcement but can't be parsed as one. Everything else is classified
+/// as a banner, prompt or unknown line.
+///
+/// # Example
+///
+/// ```
+/// use rbn_parser::line::RbnLine;
+/// use rbn_parser::parser::parse_line;
+///
+/// let line = parse_line("W6JSV de RELAY 08-Jan-2026 03:13Z >").unwrap();
+/// assert!(matches!(line, RbnLine::Prompt(_)));
+/// ```
+pub fn parse_line(line: &str) -> ParseResult<RbnLine> {
+    let trimmed = line.trim();
+
+    if looks_like_spot(trimmed) {
+        return parse_any_spot(trimmed).map(RbnLine::Spot);
+    }
+    if starts_with_ignore_case(trimmed, "WWV de ") || starts_with_ignore_case(trimmed, "WCY de ") {
+        return parse_propagation(trimmed).map(RbnLine::Propagation);
+    }
+    if starts_with_ignore_case(trimmed, "To ") && trimmed.contains(" de ") {
+        return parse_announcement(trimmed).map(RbnLine::Announcement);
     }
+    if is_prompt(trimmed) {
+        return Ok(RbnLine::Prompt(trimmed.to_string()));
+    }
+    if is_banner(trimmed) {
+        return Ok(RbnLine::Banner(trimmed.to_string()));
+    }
+
+    Ok(RbnLine::Unknown(trimmed.to_string()))
+}
+
+/// Check if a line is a command prompt like "W6JSV de RELAY 08-Jan-2026 03:13Z >".
+fn is_prompt(line: &str) -> bool {
+    line.ends_with('>') && line.contains(" de ")
+}
+
+/// Check if a line is part of the login banner.
+fn is_banner(line: &str) -> bool {
+    let lower = line.to_ascii_lowercase();
+    BANNER_PREFIXES.iter().any(|p| lower.starts_with(p))
+}
+
+/// ASCII case-insensitive `starts_with`.
+fn starts_with_ignore_case(s: &str, prefix: &str) -> bool {
+    s.get(..prefix.len())
+        .is_some_and(|p| p.eq_ignore_ascii_case(prefix))
 }
 
 /// Check if a line looks like a spot (quick pre-filter).
@@ -233,6 +738,7 @@ pub fn is_cw_spot(spot: &CwSpot) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::band::Band;
 
     #[test]
     fn test_parse_basic_cq_spot() {
@@ -240,11 +746,11 @@ mod tests {
         let spot = parse_spot(line).expect("Should parse successfully");
 
         assert_eq!(spot.spotter, "EA5WU-#");
-        assert!((spot.frequency_khz - 7018.3).abs() < 0.01);
+        assert_eq!(spot.frequency, Frequency::from_khz(7018.3));
         assert_eq!(spot.dx_call, "RW1M");
         assert_eq!(spot.mode, Mode::Cw);
-        assert_eq!(spot.snr_db, 19);
-        assert_eq!(spot.wpm, 18);
+        assert_eq!(spot.snr_db, Some(19));
+        assert_eq!(spot.speed, Some(Speed::Wpm(18)));
         assert_eq!(spot.spot_type, SpotType::Cq);
         assert_eq!(spot.time, NaiveTime::from_hms_opt(22, 59, 0).unwrap());
     }
@@ -255,7 +761,7 @@ mod tests {
         let spot = parse_spot(line).expect("Should parse successfully");
 
         assert_eq!(spot.spotter, "KM3T-2-#");
-        assert!((spot.frequency_khz - 14100.0).abs() < 0.01);
+        assert_eq!(spot.frequency, Frequency::from_khz(14100.0));
         assert_eq!(spot.dx_call, "CS3B");
         assert_eq!(spot.spot_type, SpotType::NcdxfBeacon);
     }
@@ -278,6 +784,396 @@ mod tests {
         assert_eq!(spot.dx_call, "N1NSP/B");
     }
 
+    #[test]
+    fn test_parse_spot_ref_matches_owned() {
+        for line in [
+            "DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z",
+            "DX de KM3T-2-#:  14100.0  CS3B           CW    24 dB  22 WPM  NCDXF B 2259Z",
+            "DX de W3OA-#:    14080.0  K1ABC          RTTY  12 dB  45 BPS  CQ      2259Z",
+        ] {
+            let borrowed = parse_spot_ref(line).expect("Should parse borrowed");
+            let owned = parse_spot(line).expect("Should parse owned");
+
+            assert_eq!(owned.as_spot_ref(), borrowed);
+            assert_eq!(borrowed.into_owned(), owned);
+        }
+    }
+
+    #[test]
+    fn test_parse_spot_ref_borrows_input() {
+        let line = String::from(
+            "DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z",
+        );
+        let spot = parse_spot_ref(&line).unwrap();
+
+        // The callsigns point into the original line rather than new allocations
+        let range = line.as_bytes().as_ptr_range();
+        assert!(range.contains(&spot.spotter.as_ptr()));
+        assert!(range.contains(&spot.dx_call.as_ptr()));
+
+        // So do unrecognized mode and type tokens
+        let line = String::from(
+            "DX de KD2OGR-#:  14076.0  JA1XYZ         JT65  -12 dB         QSO     2259Z",
+        );
+        let spot = parse_spot_ref(&line).unwrap();
+        let range = line.as_bytes().as_ptr_range();
+        let (ModeRef::Other(mode), SpotTypeRef::Other(spot_type)) = (spot.mode, spot.spot_type)
+        else {
+            panic!("Unexpected {:?} {:?}", spot.mode, spot.spot_type);
+        };
+        assert!(range.contains(&mode.as_ptr()));
+        assert!(range.contains(&spot_type.as_ptr()));
+        assert_eq!(spot.mode, Mode::Other("JT65".to_string()));
+    }
+
+    #[test]
+    fn test_parse_rtty_bps_spot() {
+        let line = "DX de W3OA-#:    14080.0  K1ABC          RTTY  12 dB  45 BPS  CQ      2259Z";
+        let spot = parse_spot(line).expect("Should parse RTTY spot");
+
+        assert_eq!(spot.mode, Mode::Rtty);
+        assert_eq!(spot.snr_db, Some(12));
+        assert_eq!(spot.speed, Some(Speed::Bps(45)));
+        assert_eq!(spot.spot_type, SpotType::Cq);
+    }
+
+    #[test]
+    fn test_parse_psk_bps_spot() {
+        let line = "DX de DK9IP-#:   14070.2  OH2XX          PSK31  8 dB  31 BPS  CQ      0102Z";
+        let spot = parse_spot(line).expect("Should parse PSK31 spot");
+
+        assert_eq!(spot.mode, Mode::Psk31);
+        assert_eq!(spot.speed, Some(Speed::Bps(31)));
+    }
+
+    #[test]
+    fn test_parse_spot_without_speed() {
+        let line = "DX de W3OA-#:    14080.0  K1ABC          RTTY  12 dB  CQ      2259Z";
+        let spot = parse_spot(line).expect("Should parse spot without speed");
+
+        assert_eq!(spot.speed, None);
+        assert_eq!(spot.spot_type, SpotType::Cq);
+        assert_eq!(spot.time, NaiveTime::from_hms_opt(22, 59, 0).unwrap());
+    }
+
+    /// Spot lines as sent by the digital feed on port 7001.
+    const DIGITAL_FIXTURES: &[(&str, Mode, i32, SpotType)] = &[
+        (
+            "DX de KD2OGR-#:  14074.0  JA1XYZ         FT8   -12 dB  CQ      2259Z",
+            Mode::Ft8,
+            -12,
+            SpotType::Cq,
+        ),
+        (
+            "DX de DL8LAS-#:  14074.0  PY2XB          FT8   -14 dB  CQ      1207Z",
+            Mode::Ft8,
+            -14,
+            SpotType::Cq,
+        ),
+        (
+            "DX de VE2WU-#:   28074.0  EA8AH          FT8     3 dB  CQ      1938Z",
+            Mode::Ft8,
+            3,
+            SpotType::Cq,
+        ),
+        (
+            "DX de K9IMM-#:    7074.0  KD9LA          FT8    -3 dB          1207Z",
+            Mode::Ft8,
+            -3,
+            SpotType::Unknown,
+        ),
+        (
+            "DX de W3OA-#:     7047.5  K1ABC          FT4    -3 dB  CQ      0014Z",
+            Mode::Ft4,
+            -3,
+            SpotType::Cq,
+        ),
+        (
+            "DX de KM3T-#:    14080.0  OH2XX          FT4   -21 dB  DX      0015Z",
+            Mode::Ft4,
+            -21,
+            SpotType::Dx,
+        ),
+    ];
+
+    #[test]
+    fn test_parse_digital_fixtures() {
+        for (line, mode, snr, spot_type) in DIGITAL_FIXTURES {
+            let spot = parse_spot(line).unwrap_or_else(|e| panic!("Should parse {}: {}", line, e));
+            assert_eq!(spot.mode, *mode, "{}", line);
+            assert_eq!(spot.snr_db, Some(*snr), "{}", line);
+            assert_eq!(spot.speed, None, "{}", line);
+            assert_eq!(spot.spot_type, *spot_type, "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_parse_ft8_spot_fields() {
+        let spot = parse_spot(DIGITAL_FIXTURES[0].0).expect("Should parse FT8 spot");
+
+        assert_eq!(spot.spotter, "KD2OGR-#");
+        assert_eq!(spot.frequency, Frequency::from_khz(14074.0));
+        assert_eq!(spot.dx_call, "JA1XYZ");
+        assert_eq!(spot.band(), Some(Band::B20m));
+        assert_eq!(spot.time, NaiveTime::from_hms_opt(22, 59, 0).unwrap());
+    }
+
+    #[test]
+    fn test_parse_unknown_mode_and_type_keep_token() {
+        let line = "DX de KD2OGR-#:  14076.0  JA1XYZ         JT65  -12 dB         QSO     2259Z";
+        let spot = parse_spot(line).expect("Should parse spot with unknown mode");
+
+        assert_eq!(spot.mode, Mode::Other("JT65".to_string()));
+        assert_eq!(spot.spot_type, SpotType::Other("QSO".to_string()));
+        assert_eq!(spot.snr_db, Some(-12));
+
+        let spot = parse_spot(
+            "DX de EA5WU-#:    7018.3  RW1M           cw    19 dB  18 WPM  dx      2259Z",
+        )
+        .expect("Should parse DX type");
+        assert_eq!(spot.mode, Mode::Cw);
+        assert_eq!(spot.spot_type, SpotType::Dx);
+    }
+
+    #[test]
+    fn test_parse_signed_snr_without_space() {
+        let line = "DX de KD2OGR-#:  14074.0  JA1XYZ  FT8  +5dB  CQ  2259Z";
+        let spot = parse_spot(line).expect("Should parse explicit positive SNR");
+        assert_eq!(spot.snr_db, Some(5));
+    }
+
+    #[test]
+    fn test_parse_cluster_spot() {
+        let line = "DX de K1ABC:     14025.0  JA1XYZ       tnx qso 599                    1234Z";
+        let spot = parse_cluster_spot(line).expect("Should parse cluster spot");
+
+        assert_eq!(spot.spotter, "K1ABC");
+        assert_eq!(spot.frequency, Frequency::from_khz(14025.0));
+        assert_eq!(spot.dx_call, "JA1XYZ");
+        assert_eq!(spot.mode, Mode::Unknown);
+        assert_eq!(spot.snr_db, None);
+        assert_eq!(spot.speed, None);
+        assert_eq!(spot.spot_type, SpotType::Unknown);
+        assert_eq!(spot.time, NaiveTime::from_hms_opt(12, 34, 0).unwrap());
+        assert_eq!(spot.comment.as_deref(), Some("tnx qso 599"));
+    }
+
+    #[test]
+    fn test_parse_cluster_spot_with_locator() {
+        let line =
+            "DX de W3LPL:      7005.0  VP8LP        up 2                           0312Z FM19";
+        let spot = parse_cluster_spot(line).expect("Should parse spot with locator");
+
+        assert_eq!(spot.time, NaiveTime::from_hms_opt(3, 12, 0).unwrap());
+        assert_eq!(spot.comment.as_deref(), Some("up 2"));
+    }
+
+    #[test]
+    fn test_parse_cluster_spot_without_comment() {
+        let line = "DX de DL1ABC-2:  21074.0  ZS6XYZ  1800Z";
+        let spot = parse_cluster_spot(line).expect("Should parse spot without comment");
+
+        assert_eq!(spot.spotter, "DL1ABC-2");
+        assert_eq!(spot.comment, None);
+    }
+
+    #[test]
+    fn test_parse_cluster_spot_missing_time() {
+        let line = "DX de K1ABC:     14025.0  JA1XYZ       tnx qso 599";
+        assert_eq!(
+            parse_any_spot(line).unwrap_err(),
+            ParseError::InvalidField {
+                field: SpotField::Time,
+                offset: line.find("599").unwrap(),
+                text: "599".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_any_spot() {
+        let skimmer = "DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z";
+        assert_eq!(parse_any_spot(skimmer).unwrap().mode, Mode::Cw);
+
+        let human = "DX de K1ABC:     14025.0  JA1XYZ       tnx qso 599                    1234Z";
+        assert_eq!(
+            parse_any_spot(human).unwrap().comment.as_deref(),
+            Some("tnx qso 599")
+        );
+
+        // A broken skimmer line must not be reinterpreted as a human spot
+        let broken = "DX de EA5WU-#:    7018.3  RW1M           C?    19 dB  18 WPM  CQ      2259Z";
+        assert!(parse_any_spot(broken).is_err());
+    }
+
+    #[test]
+    fn test_is_grid_locator() {
+        assert!(is_grid_locator("FN42"));
+        assert!(is_grid_locator("fn42ab"));
+        assert!(!is_grid_locator("ZZ42"));
+        assert!(!is_grid_locator("1234Z"));
+        assert!(!is_grid_locator("FN4"));
+    }
+
+    #[test]
+    fn test_parse_wwv_bulletin() {
+        let line = "WWV de W0MU <18>:   SFI=70, A=5, K=1, No Storms -> No Storms";
+        let report = parse_propagation(line).expect("Should parse WWV bulletin");
+
+        assert_eq!(report.source, PropagationSource::Wwv);
+        assert_eq!(report.reporter, "W0MU");
+        assert_eq!(report.hour, Some(18));
+        assert_eq!(report.sfi, Some(70));
+        assert_eq!(report.a_index, Some(5));
+        assert_eq!(report.k_index, Some(1));
+        assert_eq!(report.remarks.as_deref(), Some("No Storms -> No Storms"));
+    }
+
+    #[test]
+    fn test_parse_wwv_bulletin_ar_cluster() {
+        let line = "WWV de VE7CC <18Z> :   SFI=75, A=4, K=1, No Storms -> No Storms";
+        let report = parse_propagation(line).expect("Should parse AR-Cluster WWV");
+
+        assert_eq!(report.hour, Some(18));
+        assert_eq!(report.sfi, Some(75));
+    }
+
+    #[test]
+    fn test_parse_wcy_bulletin() {
+        let line = "WCY de DK0WCY-1 <12> : K=2 expK=3 A=8 R=15 SFI=70 SA=qui GMF=qui Au=no";
+        let report = parse_propagation(line).expect("Should parse WCY bulletin");
+
+        assert_eq!(report.source, PropagationSource::Wcy);
+        assert_eq!(report.reporter, "DK0WCY-1");
+        assert_eq!(report.k_index, Some(2));
+        assert_eq!(report.expected_k, Some(3));
+        assert_eq!(report.a_index, Some(8));
+        assert_eq!(report.sunspot_number, Some(15));
+        assert_eq!(report.sfi, Some(70));
+        assert_eq!(report.remarks, None);
+    }
+
+    #[test]
+    fn test_parse_propagation_without_values() {
+        assert!(parse_propagation("WWV de W0MU <18>: nothing to report").is_err());
+    }
+
+    #[test]
+    fn test_parse_announcement() {
+        let announcement = parse_announcement("To ALL de K1ABC <1234Z> : node restarting")
+            .expect("Should parse announcement");
+
+        assert_eq!(announcement.target, "ALL");
+        assert_eq!(announcement.from, "K1ABC");
+        assert_eq!(announcement.message, "node restarting");
+    }
+
+    #[test]
+    fn test_parse_line_classification() {
+        let cases = [
+            (
+                "DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z",
+                "spot",
+            ),
+            (
+                "WWV de W0MU <18>:   SFI=70, A=5, K=1, No Storms -> No Storms",
+                "propagation",
+            ),
+            ("To ALL de K1ABC: CQ contest this weekend", "announcement"),
+            ("Please enter your call:", "banner"),
+            ("Hello W6JSV, this is RELAY", "banner"),
+            ("Welcome to the Reverse Beacon Network", "banner"),
+            ("W6JSV de RELAY 08-Jan-2026 03:13Z >", "prompt"),
+            ("", "unknown"),
+            ("some other chatter", "unknown"),
+        ];
+
+        for (line, kind) in cases {
+            let parsed = parse_line(line).unwrap_or_else(|e| panic!("{}: {}", line, e));
+            assert_eq!(parsed.kind(), kind, "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_parse_line_malformed_spot_is_error() {
+        let line = "DX de EA5WU-#:    7018.3  RW1M           C?    19 dB  18 WPM  CQ      2259Z";
+        assert!(parse_line(line).is_err());
+    }
+
+    #[test]
+    fn test_parse_error_reports_field_and_offset() {
+        let cases = [
+            (
+                "DX de EA5WU-#:    7018.3  RW1M           C?    19 dB  18 WPM  CQ      2259Z",
+                SpotField::Mode,
+                "C?",
+            ),
+            (
+                "DX de EA5WU-#:    7O18.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z",
+                SpotField::Frequency,
+                "7O18.3",
+            ),
+            (
+                "DX de EA5WU-#:    7018.3  RW1M           CW    loud  18 WPM  CQ      2259Z",
+                SpotField::Snr,
+                "loud",
+            ),
+            (
+                "DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2561Z",
+                SpotField::Time,
+                "2561Z",
+            ),
+            (
+                "DX EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z",
+                SpotField::Prefix,
+                "DX",
+            ),
+        ];
+
+        for (line, field, text) in cases {
+            let err = parse_spot(line).unwrap_err();
+            assert_eq!(
+                err,
+                ParseError::InvalidField {
+                    field,
+                    offset: line.find(text).unwrap(),
+                    text: text.to_string(),
+                },
+                "{}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_error_offset_includes_leading_whitespace() {
+        let line = "  DX de EA5WU-#:    7018.3  RW1M           C?    19 dB  18 WPM  CQ      2259Z";
+        match parse_spot(line).unwrap_err() {
+            ParseError::InvalidField { offset, .. } => assert_eq!(offset, line.find("C?").unwrap()),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_error_missing_field() {
+        let err = parse_spot("DX de EA5WU-#:").unwrap_err();
+        assert_eq!(err, ParseError::MissingField(SpotField::Frequency));
+        assert_eq!(err.reason(), "missing_frequency");
+    }
+
+    #[test]
+    fn test_parse_error_reason_and_display() {
+        let line = "DX de EA5WU-#:    7018.3  RW1M           C?    19 dB  18 WPM  CQ      2259Z";
+        let err = parse_spot(line).unwrap_err();
+
+        assert_eq!(err.reason(), "invalid_mode");
+        assert_eq!(err.to_string(), "Invalid mode at byte 41: \"C?\"");
+        assert_eq!(
+            ParseError::InvalidFormat("x".to_string()).reason(),
+            "invalid_format"
+        );
+    }
+
     #[test]
     fn test_looks_like_spot() {
         assert!(looks_like_spot(
@@ -295,13 +1191,19 @@ mod tests {
     fn test_is_cw_spot() {
         let cw_spot = CwSpot {
             spotter: "TEST-#".to_string(),
-            frequency_khz: 7018.3,
+            frequency: Frequency::from_khz(7018.3),
             dx_call: "W1AW".to_string(),
             mode: Mode::Cw,
-            snr_db: 10,
-            wpm: 20,
+            snr_db: Some(10),
+            speed: Some(Speed::Wpm(20)),
             spot_type: SpotType::Cq,
             time: NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+            comment: None,
+            received_at: None,
+            timestamp: None,
+            dx_dxcc: None,
+            spotter_dxcc: None,
+            source: None,
         };
 
         assert!(is_cw_spot(&cw_spot));
@@ -326,7 +1228,7 @@ mod tests {
         // Some weak signals have negative SNR
         let line = "DX de TEST-#:    7018.3  W1AW           CW    -5 dB  20 WPM  CQ      1234Z";
         let spot = parse_spot(line).expect("Should parse negative SNR");
-        assert_eq!(spot.snr_db, -5);
+        assert_eq!(spot.snr_db, Some(-5));
     }
 
     #[test]
@@ -342,38 +1244,38 @@ mod tests {
             (
                 "DX de T-#: 1820.0 W1 CW 10 dB 20 WPM CQ 0000Z",
                 1820.0,
-                Some("160m"),
+                Some(Band::B160m),
             ),
             (
                 "DX de T-#: 3525.0 W1 CW 10 dB 20 WPM CQ 0000Z",
                 3525.0,
-                Some("80m"),
+                Some(Band::B80m),
             ),
             (
                 "DX de T-#: 7030.0 W1 CW 10 dB 20 WPM CQ 0000Z",
                 7030.0,
-                Some("40m"),
+                Some(Band::B40m),
             ),
             (
                 "DX de T-#: 14025.0 W1 CW 10 dB 20 WPM CQ 0000Z",
                 14025.0,
-                Some("20m"),
+                Some(Band::B20m),
             ),
             (
                 "DX de T-#: 21025.0 W1 CW 10 dB 20 WPM CQ 0000Z",
                 21025.0,
-                Some("15m"),
+                Some(Band::B15m),
             ),
             (
                 "DX de T-#: 28025.0 W1 CW 10 dB 20 WPM CQ 0000Z",
                 28025.0,
-                Some("10m"),
+                Some(Band::B10m),
             ),
         ];
 
         for (line, expected_freq, expected_band) in test_cases {
             let spot = parse_spot(line).unwrap_or_else(|_| panic!("Should parse: {}", line));
-            assert!((spot.frequency_khz - expected_freq).abs() < 0.1);
+            assert_eq!(spot.frequency, Frequency::from_khz(expected_freq));
             assert_eq!(spot.band(), expected_band);
         }
     }
@@ -3,13 +3,19 @@
 //! This module defines the core types used throughout the application
 //! to represent parsed CW spots from the Reverse Beacon Network.
 
-use chrono::NaiveTime;
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Days, NaiveTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 
+use crate::band::{Band, BandPlan, Segment};
+use crate::dxcc::{DxccDatabase, DxccInfo};
+use crate::frequency::Frequency;
+
 /// The type of CQ or beacon activity detected.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+///
+/// Serializes as the names used in filter configs (`"CQ"`, `"NCDXF_BEACON"`),
+/// with unrecognized tokens kept as their raw text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SpotType {
     /// Standard CQ call
     Cq,
@@ -17,24 +23,152 @@ pub enum SpotType {
     NcdxfBeacon,
     /// Generic beacon
     Beacon,
-    /// Unknown or other type
-    Other,
+    /// Station calling for DX
+    Dx,
+    /// No type reported (digital spots without one, human cluster spots)
+    Unknown,
+    /// A type token this crate doesn't know, as reported
+    Other(String),
+}
+
+impl SpotType {
+    /// The name used in filter configs and serialized spots.
+    pub fn name(&self) -> &str {
+        self.as_spot_type_ref().name()
+    }
+
+    /// Parse a config or serialized name, case-insensitively.
+    ///
+    /// Both `NCDXF_BEACON` and the on-air `NCDXF B` are accepted; anything
+    /// unrecognized becomes [`SpotType::Other`].
+    pub fn from_name(name: &str) -> Self {
+        SpotTypeRef::from_name(name).into_owned()
+    }
+
+    /// Borrow this spot type as a [`SpotTypeRef`].
+    pub fn as_spot_type_ref(&self) -> SpotTypeRef<'_> {
+        match self {
+            SpotType::Cq => SpotTypeRef::Cq,
+            SpotType::NcdxfBeacon => SpotTypeRef::NcdxfBeacon,
+            SpotType::Beacon => SpotTypeRef::Beacon,
+            SpotType::Dx => SpotTypeRef::Dx,
+            SpotType::Unknown => SpotTypeRef::Unknown,
+            SpotType::Other(token) => SpotTypeRef::Other(token),
+        }
+    }
 }
 
 impl fmt::Display for SpotType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_spot_type_ref().fmt(f)
+    }
+}
+
+impl PartialEq<SpotTypeRef<'_>> for SpotType {
+    fn eq(&self, other: &SpotTypeRef<'_>) -> bool {
+        self.as_spot_type_ref() == *other
+    }
+}
+
+impl Serialize for SpotType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for SpotType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(SpotType::from_name(&name))
+    }
+}
+
+/// A [`SpotType`] that borrows an unrecognized token instead of owning it,
+/// as held by [`CwSpotRef`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpotTypeRef<'a> {
+    /// Standard CQ call
+    Cq,
+    /// NCDXF/IARU beacon
+    NcdxfBeacon,
+    /// Generic beacon
+    Beacon,
+    /// Station calling for DX
+    Dx,
+    /// No type reported
+    Unknown,
+    /// A type token this crate doesn't know, as reported
+    Other(&'a str),
+}
+
+impl<'a> SpotTypeRef<'a> {
+    /// The name used in filter configs and serialized spots.
+    pub fn name(&self) -> &'a str {
+        match self {
+            SpotTypeRef::Cq => "CQ",
+            SpotTypeRef::NcdxfBeacon => "NCDXF_BEACON",
+            SpotTypeRef::Beacon => "BEACON",
+            SpotTypeRef::Dx => "DX",
+            SpotTypeRef::Unknown => "UNKNOWN",
+            SpotTypeRef::Other(token) => token,
+        }
+    }
+
+    /// Parse a name like [`SpotType::from_name`], borrowing unrecognized
+    /// tokens.
+    pub fn from_name(name: &'a str) -> Self {
+        [
+            ("CQ", SpotTypeRef::Cq),
+            ("NCDXF_BEACON", SpotTypeRef::NcdxfBeacon),
+            ("NCDXF B", SpotTypeRef::NcdxfBeacon),
+            ("BEACON", SpotTypeRef::Beacon),
+            ("DX", SpotTypeRef::Dx),
+            ("UNKNOWN", SpotTypeRef::Unknown),
+        ]
+        .into_iter()
+        .find(|(known, _)| name.eq_ignore_ascii_case(known))
+        .map_or(SpotTypeRef::Other(name), |(_, spot_type)| spot_type)
+    }
+
+    /// Copy into an owned [`SpotType`].
+    pub fn into_owned(self) -> SpotType {
+        match self {
+            SpotTypeRef::Cq => SpotType::Cq,
+            SpotTypeRef::NcdxfBeacon => SpotType::NcdxfBeacon,
+            SpotTypeRef::Beacon => SpotType::Beacon,
+            SpotTypeRef::Dx => SpotType::Dx,
+            SpotTypeRef::Unknown => SpotType::Unknown,
+            SpotTypeRef::Other(token) => SpotType::Other(token.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for SpotTypeRef<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            SpotType::Cq => write!(f, "CQ"),
-            SpotType::NcdxfBeacon => write!(f, "NCDXF B"),
-            SpotType::Beacon => write!(f, "BEACON"),
-            SpotType::Other => write!(f, "OTHER"),
+            SpotTypeRef::NcdxfBeacon => write!(f, "NCDXF B"),
+            other => write!(f, "{}", other.name()),
         }
     }
 }
 
+impl Serialize for SpotTypeRef<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl PartialEq<SpotType> for SpotTypeRef<'_> {
+    fn eq(&self, other: &SpotType) -> bool {
+        *self == other.as_spot_type_ref()
+    }
+}
+
 /// The transmission mode of the spot.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "UPPERCASE")]
+///
+/// Serializes as the upper-case mode name (`"CW"`), with unrecognized modes
+/// kept as their raw text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Mode {
     /// Continuous Wave (Morse code)
     Cw,
@@ -46,26 +180,357 @@ pub enum Mode {
     Ft4,
     /// PSK31 digital mode
     Psk31,
-    /// Unknown mode
+    /// No mode reported (human cluster spots)
     Unknown,
+    /// A mode token this crate doesn't know, as reported
+    Other(String),
+}
+
+impl Mode {
+    /// The mode's name as reported by RBN.
+    pub fn name(&self) -> &str {
+        self.as_mode_ref().name()
+    }
+
+    /// Parse a mode name, case-insensitively. Anything unrecognized
+    /// becomes [`Mode::Other`].
+    pub fn from_name(name: &str) -> Self {
+        ModeRef::from_name(name).into_owned()
+    }
+
+    /// Borrow this mode as a [`ModeRef`].
+    pub fn as_mode_ref(&self) -> ModeRef<'_> {
+        match self {
+            Mode::Cw => ModeRef::Cw,
+            Mode::Rtty => ModeRef::Rtty,
+            Mode::Ft8 => ModeRef::Ft8,
+            Mode::Ft4 => ModeRef::Ft4,
+            Mode::Psk31 => ModeRef::Psk31,
+            Mode::Unknown => ModeRef::Unknown,
+            Mode::Other(token) => ModeRef::Other(token),
+        }
+    }
 }
 
 impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl PartialEq<ModeRef<'_>> for Mode {
+    fn eq(&self, other: &ModeRef<'_>) -> bool {
+        self.as_mode_ref() == *other
+    }
+}
+
+impl Serialize for Mode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for Mode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(Mode::from_name(&name))
+    }
+}
+
+/// A [`Mode`] that borrows an unrecognized token instead of owning it, as
+/// held by [`CwSpotRef`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModeRef<'a> {
+    /// Continuous Wave (Morse code)
+    Cw,
+    /// Radio Teletype
+    Rtty,
+    /// FT8 digital mode
+    Ft8,
+    /// FT4 digital mode
+    Ft4,
+    /// PSK31 digital mode
+    Psk31,
+    /// No mode reported
+    Unknown,
+    /// A mode token this crate doesn't know, as reported
+    Other(&'a str),
+}
+
+impl<'a> ModeRef<'a> {
+    /// The mode's name as reported by RBN.
+    pub fn name(&self) -> &'a str {
+        match self {
+            ModeRef::Cw => "CW",
+            ModeRef::Rtty => "RTTY",
+            ModeRef::Ft8 => "FT8",
+            ModeRef::Ft4 => "FT4",
+            ModeRef::Psk31 => "PSK31",
+            ModeRef::Unknown => "UNKNOWN",
+            ModeRef::Other(token) => token,
+        }
+    }
+
+    /// Parse a mode name like [`Mode::from_name`], borrowing unrecognized
+    /// tokens.
+    pub fn from_name(name: &'a str) -> Self {
+        [
+            ModeRef::Cw,
+            ModeRef::Rtty,
+            ModeRef::Ft8,
+            ModeRef::Ft4,
+            ModeRef::Psk31,
+            ModeRef::Unknown,
+        ]
+        .into_iter()
+        .find(|mode| name.eq_ignore_ascii_case(mode.name()))
+        .unwrap_or(ModeRef::Other(name))
+    }
+
+    /// Copy into an owned [`Mode`].
+    pub fn into_owned(self) -> Mode {
+        match self {
+            ModeRef::Cw => Mode::Cw,
+            ModeRef::Rtty => Mode::Rtty,
+            ModeRef::Ft8 => Mode::Ft8,
+            ModeRef::Ft4 => Mode::Ft4,
+            ModeRef::Psk31 => Mode::Psk31,
+            ModeRef::Unknown => Mode::Unknown,
+            ModeRef::Other(token) => Mode::Other(token.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for ModeRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Serialize for ModeRef<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl PartialEq<Mode> for ModeRef<'_> {
+    fn eq(&self, other: &Mode) -> bool {
+        *self == other.as_mode_ref()
+    }
+}
+
+/// The reported sending speed of a spot.
+///
+/// CW skimmers report speed in words per minute, while RTTY and PSK
+/// decoders report the symbol rate in baud (bits per second).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Speed {
+    /// CW speed in words per minute
+    Wpm(u16),
+    /// Digital mode symbol rate in bits per second
+    Bps(u16),
+}
+
+impl Speed {
+    /// Returns the numeric speed value, regardless of unit.
+    pub fn value(&self) -> u16 {
+        match self {
+            Speed::Wpm(v) | Speed::Bps(v) => *v,
+        }
+    }
+
+    /// Returns the speed in WPM, if it's a CW speed.
+    pub fn wpm(&self) -> Option<u16> {
+        match self {
+            Speed::Wpm(wpm) => Some(*wpm),
+            Speed::Bps(_) => None,
+        }
+    }
+
+    /// Returns the speed in BPS, if it's a digital symbol rate.
+    pub fn bps(&self) -> Option<u16> {
+        match self {
+            Speed::Bps(bps) => Some(*bps),
+            Speed::Wpm(_) => None,
+        }
+    }
+
+    /// Returns the unit label as it appears in RBN spots.
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Speed::Wpm(_) => "WPM",
+            Speed::Bps(_) => "BPS",
+        }
+    }
+}
+
+impl fmt::Display for Speed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.value(), self.unit())
+    }
+}
+
+/// The identity of a spotting station, split into its parts.
+///
+/// RBN skimmers report as `EA5WU-#`, or `KM3T-2-#` when a station runs
+/// several skimmers. Human cluster spotters may carry an SSID-style
+/// instance too (`DL1ABC-2`). The base callsign groups every instance of
+/// the same station.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpotterId<'a> {
+    /// Callsign of the station, without instance or skimmer marker.
+    pub base: &'a str,
+    /// Skimmer or node instance number (the `2` in `KM3T-2-#`).
+    pub instance: Option<u16>,
+    /// Whether the spotter carries the `-#` skimmer marker.
+    pub skimmer: bool,
+}
+
+impl<'a> SpotterId<'a> {
+    /// Split a spotter callsign into base call, instance and skimmer marker.
+    pub fn parse(spotter: &'a str) -> Self {
+        let (rest, skimmer) = match spotter.strip_suffix("-#") {
+            Some(rest) => (rest, true),
+            None => (spotter, false),
+        };
+
+        if let Some((base, instance)) = rest.rsplit_once('-')
+            && !base.is_empty()
+            && let Ok(instance) = instance.parse::<u16>()
+        {
+            return Self {
+                base,
+                instance: Some(instance),
+                skimmer,
+            };
+        }
+
+        Self {
+            base: rest,
+            instance: None,
+            skimmer,
+        }
+    }
+}
+
+impl fmt::Display for SpotterId<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.base)?;
+        if let Some(instance) = self.instance {
+            write!(f, "-{}", instance)?;
+        }
+        if self.skimmer {
+            write!(f, "-#")?;
+        }
+        Ok(())
+    }
+}
+
+/// A special-purpose suffix on a callsign, such as `/P` or `/MM`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CallsignModifier {
+    /// Portable (`/P`)
+    #[serde(rename = "P")]
+    Portable,
+    /// Mobile (`/M`)
+    #[serde(rename = "M")]
+    Mobile,
+    /// Maritime mobile (`/MM`)
+    #[serde(rename = "MM")]
+    MaritimeMobile,
+    /// Aeronautical mobile (`/AM`)
+    #[serde(rename = "AM")]
+    AeronauticalMobile,
+    /// Low power (`/QRP`)
+    #[serde(rename = "QRP")]
+    Qrp,
+    /// Beacon (`/B`)
+    #[serde(rename = "B")]
+    Beacon,
+}
+
+impl CallsignModifier {
+    /// Parse a suffix segment (without the slash), case-insensitively.
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        match suffix.to_ascii_uppercase().as_str() {
+            "P" => Some(CallsignModifier::Portable),
+            "M" => Some(CallsignModifier::Mobile),
+            "MM" => Some(CallsignModifier::MaritimeMobile),
+            "AM" => Some(CallsignModifier::AeronauticalMobile),
+            "QRP" => Some(CallsignModifier::Qrp),
+            "B" => Some(CallsignModifier::Beacon),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CallsignModifier {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Mode::Cw => write!(f, "CW"),
-            Mode::Rtty => write!(f, "RTTY"),
-            Mode::Ft8 => write!(f, "FT8"),
-            Mode::Ft4 => write!(f, "FT4"),
-            Mode::Psk31 => write!(f, "PSK31"),
-            Mode::Unknown => write!(f, "UNKNOWN"),
+            CallsignModifier::Portable => write!(f, "P"),
+            CallsignModifier::Mobile => write!(f, "M"),
+            CallsignModifier::MaritimeMobile => write!(f, "MM"),
+            CallsignModifier::AeronauticalMobile => write!(f, "AM"),
+            CallsignModifier::Qrp => write!(f, "QRP"),
+            CallsignModifier::Beacon => write!(f, "B"),
+        }
+    }
+}
+
+/// A callsign split into its home call, operating location and modifiers.
+///
+/// `EA8/DL1ABC/P` has home call `DL1ABC`, location `EA8` and the portable
+/// modifier. The location may also be written after the home call
+/// (`DL1ABC/EA8`) or be a bare call area digit (`W6JSV/7`); whichever
+/// non-modifier part is shorter is taken as the location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Callsign<'a> {
+    /// The station's own callsign.
+    pub home: &'a str,
+    /// Operating-location prefix or call area, if any.
+    pub location: Option<&'a str>,
+    /// Special-purpose suffixes, in the order they appear.
+    pub modifiers: Vec<CallsignModifier>,
+}
+
+impl<'a> Callsign<'a> {
+    /// Split a callsign on `/` into its parts.
+    pub fn parse(call: &'a str) -> Self {
+        let mut home = "";
+        let mut location = None;
+        let mut modifiers = Vec::new();
+
+        for part in call.split('/').filter(|p| !p.is_empty()) {
+            if let Some(modifier) = CallsignModifier::from_suffix(part) {
+                modifiers.push(modifier);
+            } else if home.is_empty() {
+                home = part;
+            } else if part.len() > home.len() {
+                location.get_or_insert(home);
+                home = part;
+            } else {
+                location.get_or_insert(part);
+            }
+        }
+
+        Self {
+            home,
+            location,
+            modifiers,
         }
     }
+
+    /// Whether the callsign carries the given modifier.
+    pub fn has_modifier(&self, modifier: CallsignModifier) -> bool {
+        self.modifiers.contains(&modifier)
+    }
 }
 
 /// A parsed CW spot from the Reverse Beacon Network.
 ///
-/// This represents a single decoded signal detected by a skimmer station.
+/// This represents a single decoded signal detected by a skimmer station,
+/// or a spot posted by an operator on a DX cluster.
 ///
 /// # Example
 ///
@@ -76,21 +541,28 @@ impl fmt::Display for Mode {
 ///
 /// Would be parsed into a `CwSpot` with:
 /// - `spotter`: "EA5WU-#"
-/// - `frequency_khz`: 7018.3
+/// - `frequency`: 7018.3 kHz
 /// - `dx_call`: "RW1M"
 /// - `mode`: Mode::Cw
-/// - `snr_db`: 19
-/// - `wpm`: 18
+/// - `snr_db`: Some(19)
+/// - `speed`: Some(Speed::Wpm(18))
 /// - `spot_type`: SpotType::Cq
 /// - `time`: 22:59 UTC
+/// - `comment`: None
+/// - `received_at` / `timestamp`: None until stamped with [`CwSpot::with_received_at`]
+///
+/// Human spots from regular DX clusters carry no mode, SNR or speed. They
+/// are represented with `Mode::Unknown`, `SpotType::Unknown`, `snr_db: None`
+/// and the free-text remark in `comment`.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CwSpot {
-    /// The callsign of the skimmer station that detected this signal.
+    /// The callsign of the skimmer station (or operator) that reported this signal.
     /// Typically includes a `-#` suffix indicating it's an RBN skimmer.
     pub spotter: String,
 
-    /// The frequency in kHz where the signal was detected.
-    pub frequency_khz: f64,
+    /// The frequency where the signal was detected, as `frequency_khz` in JSON.
+    #[serde(rename = "frequency_khz")]
+    pub frequency: Frequency,
 
     /// The callsign of the station being spotted (the DX station).
     pub dx_call: String,
@@ -98,40 +570,121 @@ pub struct CwSpot {
     /// The transmission mode (CW, RTTY, etc.).
     pub mode: Mode,
 
-    /// Signal-to-noise ratio in decibels.
-    pub snr_db: i32,
+    /// Signal-to-noise ratio in decibels (absent on human cluster spots).
+    pub snr_db: Option<i32>,
 
-    /// CW speed in words per minute.
-    pub wpm: u16,
+    /// Reported speed (WPM for CW, BPS for RTTY/PSK), if the spot includes one.
+    pub speed: Option<Speed>,
 
     /// The type of activity (CQ, BEACON, etc.).
     pub spot_type: SpotType,
 
     /// The UTC time when the spot was reported (time only, no date).
     pub time: NaiveTime,
+
+    /// Free-text comment from a human cluster spot (e.g., "tnx qso 599").
+    pub comment: Option<String>,
+
+    /// When the line carrying this spot was received locally.
+    pub received_at: Option<DateTime<Utc>>,
+
+    /// Full UTC spot time, with the date inferred from `received_at`.
+    pub timestamp: Option<DateTime<Utc>>,
+
+    /// Where the DX station is, once resolved with [`CwSpot::with_dxcc`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dx_dxcc: Option<DxccInfo>,
+
+    /// Where the spotter is, once resolved with [`CwSpot::with_dxcc`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spotter_dxcc: Option<DxccInfo>,
+
+    /// Name of the feed the spot came from, once tagged with
+    /// [`CwSpot::with_source`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
 }
 
 impl CwSpot {
     /// Returns the amateur radio band for this spot's frequency.
     ///
     /// Returns `None` if the frequency doesn't fall within a recognized band.
-    pub fn band(&self) -> Option<&'static str> {
-        match self.frequency_khz as u32 {
-            135..=138 => Some("2200m"),
-            472..=479 => Some("630m"),
-            1800..=2000 => Some("160m"),
-            3500..=4000 => Some("80m"),
-            5330..=5410 => Some("60m"),
-            7000..=7300 => Some("40m"),
-            10100..=10150 => Some("30m"),
-            14000..=14350 => Some("20m"),
-            18068..=18168 => Some("17m"),
-            21000..=21450 => Some("15m"),
-            24890..=24990 => Some("12m"),
-            28000..=29700 => Some("10m"),
-            50000..=54000 => Some("6m"),
-            144000..=148000 => Some("2m"),
-            _ => None,
+    pub fn band(&self) -> Option<Band> {
+        band_for_frequency(self.frequency)
+    }
+
+    /// Returns the band-plan segments (CW, beacon, etc.) this spot's
+    /// frequency falls in, using the installed [`BandPlan`].
+    pub fn segments(&self) -> Vec<Segment> {
+        BandPlan::current().segments_for(self.frequency)
+    }
+
+    /// Returns the spotter split into base call, instance and skimmer marker.
+    pub fn spotter_id(&self) -> SpotterId<'_> {
+        SpotterId::parse(&self.spotter)
+    }
+
+    /// Returns the DX call split into home call, location and modifiers.
+    pub fn dx_callsign(&self) -> Callsign<'_> {
+        Callsign::parse(&self.dx_call)
+    }
+
+    /// Record when this spot was received and reconstruct its full timestamp.
+    ///
+    /// The spot itself only carries HHMMZ, so the date is taken from the
+    /// receive clock, picking whichever of yesterday, today or tomorrow puts
+    /// the spot time closest to `received_at`. This handles the 2359Z→0000Z
+    /// boundary in both directions (late delivery, or a skimmer clock that
+    /// runs slightly ahead).
+    pub fn with_received_at(mut self, received_at: DateTime<Utc>) -> Self {
+        self.received_at = Some(received_at);
+        self.timestamp = Some(infer_spot_timestamp(self.time, received_at));
+        self
+    }
+
+    /// Resolve the DX call and spotter to their DXCC entities.
+    ///
+    /// The spotter is looked up by its base call, so `KM3T-2-#` resolves
+    /// like `KM3T`.
+    pub fn with_dxcc(mut self, dxcc: &DxccDatabase) -> Self {
+        self.dx_dxcc = dxcc.resolve(&self.dx_call);
+        self.spotter_dxcc = dxcc.resolve(self.spotter_id().base);
+        self
+    }
+
+    /// Tag this spot with the name of the feed it came from.
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Returns the CW speed in WPM, if this spot reports one.
+    pub fn wpm(&self) -> Option<u16> {
+        self.speed.and_then(|s| s.wpm())
+    }
+
+    /// Returns the digital symbol rate in BPS, if this spot reports one.
+    pub fn bps(&self) -> Option<u16> {
+        self.speed.and_then(|s| s.bps())
+    }
+
+    /// Borrow this spot as a [`CwSpotRef`] without copying any strings.
+    pub fn as_spot_ref(&self) -> CwSpotRef<'_> {
+        CwSpotRef {
+            spotter: &self.spotter,
+            frequency: self.frequency,
+            dx_call: &self.dx_call,
+            mode: self.mode.as_mode_ref(),
+            snr_db: self.snr_db,
+            speed: self.speed,
+            spot_type: self.spot_type.as_spot_type_ref(),
+            time: self.time,
+            comment: self.comment.as_deref(),
+            received_at: self.received_at,
+            timestamp: self.timestamp,
+            dx_dxcc: self.dx_dxcc.as_ref(),
+            spotter_dxcc: self.spotter_dxcc.as_ref(),
+            source: self.source.as_deref(),
         }
     }
 
@@ -142,20 +695,160 @@ impl CwSpot {
     }
 }
 
+/// A spot that borrows its text fields from the line it was parsed from.
+///
+/// This is the zero-copy counterpart of [`CwSpot`], produced by
+/// [`parse_spot_ref`](crate::parser::parse_spot_ref). Convert it with
+/// [`CwSpotRef::into_owned`] once a spot needs to outlive its input line.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CwSpotRef<'a> {
+    /// The callsign of the skimmer station (or operator) that reported this signal.
+    pub spotter: &'a str,
+
+    /// The frequency where the signal was detected, as `frequency_khz` in JSON.
+    #[serde(rename = "frequency_khz")]
+    pub frequency: Frequency,
+
+    /// The callsign of the station being spotted (the DX station).
+    pub dx_call: &'a str,
+
+    /// The transmission mode (CW, RTTY, etc.).
+    pub mode: ModeRef<'a>,
+
+    /// Signal-to-noise ratio in decibels (absent on human cluster spots).
+    pub snr_db: Option<i32>,
+
+    /// Reported speed (WPM for CW, BPS for RTTY/PSK), if the spot includes one.
+    pub speed: Option<Speed>,
+
+    /// The type of activity (CQ, BEACON, etc.).
+    pub spot_type: SpotTypeRef<'a>,
+
+    /// The UTC time when the spot was reported (time only, no date).
+    pub time: NaiveTime,
+
+    /// Free-text comment from a human cluster spot.
+    pub comment: Option<&'a str>,
+
+    /// When the line carrying this spot was received locally.
+    pub received_at: Option<DateTime<Utc>>,
+
+    /// Full UTC spot time, with the date inferred from `received_at`.
+    pub timestamp: Option<DateTime<Utc>>,
+
+    /// Where the DX station is, if resolved.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dx_dxcc: Option<&'a DxccInfo>,
+
+    /// Where the spotter is, if resolved.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spotter_dxcc: Option<&'a DxccInfo>,
+
+    /// Name of the feed the spot came from, if tagged.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<&'a str>,
+}
+
+impl<'a> CwSpotRef<'a> {
+    /// Returns the amateur radio band for this spot's frequency.
+    pub fn band(&self) -> Option<Band> {
+        band_for_frequency(self.frequency)
+    }
+
+    /// Returns the band-plan segments (CW, beacon, etc.) this spot's
+    /// frequency falls in, using the installed [`BandPlan`].
+    pub fn segments(&self) -> Vec<Segment> {
+        BandPlan::current().segments_for(self.frequency)
+    }
+
+    /// Returns the spotter split into base call, instance and skimmer marker.
+    pub fn spotter_id(&self) -> SpotterId<'a> {
+        SpotterId::parse(self.spotter)
+    }
+
+    /// Returns the DX call split into home call, location and modifiers.
+    pub fn dx_callsign(&self) -> Callsign<'a> {
+        Callsign::parse(self.dx_call)
+    }
+
+    /// Returns the CW speed in WPM, if this spot reports one.
+    pub fn wpm(&self) -> Option<u16> {
+        self.speed.and_then(|s| s.wpm())
+    }
+
+    /// Returns the digital symbol rate in BPS, if this spot reports one.
+    pub fn bps(&self) -> Option<u16> {
+        self.speed.and_then(|s| s.bps())
+    }
+
+    /// Copy the borrowed fields into an owned [`CwSpot`].
+    pub fn into_owned(self) -> CwSpot {
+        CwSpot {
+            spotter: self.spotter.to_string(),
+            frequency: self.frequency,
+            dx_call: self.dx_call.to_string(),
+            mode: self.mode.into_owned(),
+            snr_db: self.snr_db,
+            speed: self.speed,
+            spot_type: self.spot_type.into_owned(),
+            time: self.time,
+            comment: self.comment.map(str::to_string),
+            received_at: self.received_at,
+            timestamp: self.timestamp,
+            dx_dxcc: self.dx_dxcc.cloned(),
+            spotter_dxcc: self.spotter_dxcc.cloned(),
+            source: self.source.map(str::to_string),
+        }
+    }
+}
+
+/// Returns the amateur radio band for a frequency.
+///
+/// Uses the installed [`BandPlan`]. Returns `None` if the frequency doesn't
+/// fall within a band of the plan.
+pub fn band_for_frequency(frequency: Frequency) -> Option<Band> {
+    BandPlan::current().band_for(frequency)
+}
+
+/// Reconstruct the full UTC timestamp of a spot time-of-day received at `received_at`.
+pub fn infer_spot_timestamp(time: NaiveTime, received_at: DateTime<Utc>) -> DateTime<Utc> {
+    let today = received_at.date_naive();
+    [
+        today.checked_sub_days(Days::new(1)),
+        Some(today),
+        today.checked_add_days(Days::new(1)),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|date| date.and_time(time).and_utc())
+    .min_by_key(|candidate| (*candidate - received_at).abs())
+    .unwrap_or_else(|| today.and_time(time).and_utc())
+}
+
+/// A compact, human-readable rendering. Use [`crate::format::format_spot`]
+/// for the exact RBN column layout.
 impl fmt::Display for CwSpot {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "DX de {}: {:>8.1} {} {} {} dB {} WPM {} {}",
+            "DX de {}: {:>8.1} {} ",
             self.spotter,
-            self.frequency_khz,
-            self.dx_call,
-            self.mode,
-            self.snr_db,
-            self.wpm,
-            self.spot_type,
-            self.time.format("%H%MZ")
-        )
+            self.frequency.khz(),
+            self.dx_call
+        )?;
+        if let Some(snr_db) = self.snr_db {
+            write!(f, "{} {} dB ", self.mode, snr_db)?;
+            if let Some(speed) = self.speed {
+                write!(f, "{} ", speed)?;
+            }
+            if self.spot_type != SpotType::Unknown {
+                write!(f, "{} ", self.spot_type)?;
+            }
+        }
+        if let Some(ref comment) = self.comment {
+            write!(f, "{} ", comment)?;
+        }
+        write!(f, "{}", self.time.format("%H%MZ"))
     }
 }
 
@@ -167,33 +860,282 @@ mod tests {
     fn test_band_detection() {
         let spot = CwSpot {
             spotter: "TEST-#".to_string(),
-            frequency_khz: 14025.0,
+            frequency: Frequency::from_khz(14025.0),
             dx_call: "W1AW".to_string(),
             mode: Mode::Cw,
-            snr_db: 10,
-            wpm: 20,
+            snr_db: Some(10),
+            speed: Some(Speed::Wpm(20)),
             spot_type: SpotType::Cq,
             time: NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+            comment: None,
+            received_at: None,
+            timestamp: None,
+            dx_dxcc: None,
+            spotter_dxcc: None,
+            source: None,
         };
 
-        assert_eq!(spot.band(), Some("20m"));
+        assert_eq!(spot.band(), Some(Band::B20m));
     }
 
     #[test]
     fn test_band_detection_edge_cases() {
         let make_spot = |freq: f64| CwSpot {
             spotter: "TEST-#".to_string(),
-            frequency_khz: freq,
+            frequency: Frequency::from_khz(freq),
             dx_call: "W1AW".to_string(),
             mode: Mode::Cw,
-            snr_db: 10,
-            wpm: 20,
+            snr_db: Some(10),
+            speed: Some(Speed::Wpm(20)),
             spot_type: SpotType::Cq,
             time: NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+            comment: None,
+            received_at: None,
+            timestamp: None,
+            dx_dxcc: None,
+            spotter_dxcc: None,
+            source: None,
         };
 
-        assert_eq!(make_spot(7000.0).band(), Some("40m"));
-        assert_eq!(make_spot(7300.0).band(), Some("40m"));
+        assert_eq!(make_spot(7000.0).band(), Some(Band::B40m));
+        assert_eq!(make_spot(7300.0).band(), Some(Band::B40m));
         assert_eq!(make_spot(6999.0).band(), None);
     }
+
+    #[test]
+    fn test_speed_accessors() {
+        let mut spot = CwSpot {
+            spotter: "TEST-#".to_string(),
+            frequency: Frequency::from_khz(14080.0),
+            dx_call: "W1AW".to_string(),
+            mode: Mode::Rtty,
+            snr_db: Some(10),
+            speed: Some(Speed::Bps(45)),
+            spot_type: SpotType::Cq,
+            time: NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+            comment: None,
+            received_at: None,
+            timestamp: None,
+            dx_dxcc: None,
+            spotter_dxcc: None,
+            source: None,
+        };
+
+        assert_eq!(spot.bps(), Some(45));
+        assert_eq!(spot.wpm(), None);
+        assert!(spot.to_string().contains("45 BPS"));
+
+        spot.speed = None;
+        assert_eq!(spot.bps(), None);
+        assert!(!spot.to_string().contains("WPM"));
+    }
+
+    #[test]
+    fn test_infer_spot_timestamp_same_day() {
+        let received = "2026-01-08T22:59:40Z".parse::<DateTime<Utc>>().unwrap();
+        let time = NaiveTime::from_hms_opt(22, 59, 0).unwrap();
+
+        assert_eq!(
+            infer_spot_timestamp(time, received),
+            "2026-01-08T22:59:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_infer_spot_timestamp_midnight_rollover() {
+        // 2359Z spot delivered just after midnight belongs to the previous day
+        let received = "2026-01-09T00:00:20Z".parse::<DateTime<Utc>>().unwrap();
+        let time = NaiveTime::from_hms_opt(23, 59, 0).unwrap();
+        assert_eq!(
+            infer_spot_timestamp(time, received),
+            "2026-01-08T23:59:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+
+        // 0000Z spot from a skimmer whose clock runs ahead belongs to the next day
+        let received = "2026-01-08T23:59:50Z".parse::<DateTime<Utc>>().unwrap();
+        let time = NaiveTime::from_hms_opt(0, 0, 0).unwrap();
+        assert_eq!(
+            infer_spot_timestamp(time, received),
+            "2026-01-09T00:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_with_received_at_serializes_timestamps() {
+        let received = "2026-01-09T00:00:20Z".parse::<DateTime<Utc>>().unwrap();
+        let spot = CwSpot {
+            spotter: "TEST-#".to_string(),
+            frequency: Frequency::from_khz(14025.0),
+            dx_call: "W1AW".to_string(),
+            mode: Mode::Cw,
+            snr_db: Some(10),
+            speed: Some(Speed::Wpm(20)),
+            spot_type: SpotType::Cq,
+            time: NaiveTime::from_hms_opt(23, 59, 0).unwrap(),
+            comment: None,
+            received_at: None,
+            timestamp: None,
+            dx_dxcc: None,
+            spotter_dxcc: None,
+            source: None,
+        }
+        .with_received_at(received);
+
+        let json = serde_json::to_value(&spot).unwrap();
+        assert_eq!(json["received_at"], "2026-01-09T00:00:20Z");
+        assert_eq!(json["timestamp"], "2026-01-08T23:59:00Z");
+    }
+
+    #[test]
+    fn test_frequency_serializes_as_khz() {
+        let line = "DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z";
+        let spot = crate::parser::parse_spot(line).unwrap();
+        assert_eq!(spot.frequency, Frequency::from_hz(7_018_300));
+
+        let json = serde_json::to_value(&spot).unwrap();
+        assert_eq!(json["frequency_khz"], 7018.3);
+
+        let back: CwSpot = serde_json::from_value(json).unwrap();
+        assert_eq!(back, spot);
+    }
+
+    #[test]
+    fn test_mode_and_spot_type_serde_keep_raw_token() {
+        let cases = [
+            (Mode::Cw, "\"CW\""),
+            (Mode::Other("JT65".to_string()), "\"JT65\""),
+        ];
+        for (mode, json) in cases {
+            assert_eq!(serde_json::to_string(&mode).unwrap(), json);
+            assert_eq!(serde_json::from_str::<Mode>(json).unwrap(), mode);
+        }
+        assert_eq!(serde_json::from_str::<Mode>("\"ft8\"").unwrap(), Mode::Ft8);
+
+        let cases = [
+            (SpotType::NcdxfBeacon, "\"NCDXF_BEACON\""),
+            (SpotType::Dx, "\"DX\""),
+            (SpotType::Other("QSO".to_string()), "\"QSO\""),
+        ];
+        for (spot_type, json) in cases {
+            assert_eq!(serde_json::to_string(&spot_type).unwrap(), json);
+            assert_eq!(serde_json::from_str::<SpotType>(json).unwrap(), spot_type);
+        }
+        assert_eq!(
+            serde_json::from_str::<SpotType>("\"NCDXF B\"").unwrap(),
+            SpotType::NcdxfBeacon
+        );
+    }
+
+    #[test]
+    fn test_borrowed_mode_and_spot_type() {
+        assert_eq!(ModeRef::from_name("ft8"), ModeRef::Ft8);
+        assert_eq!(ModeRef::from_name("JT65"), ModeRef::Other("JT65"));
+        assert_eq!(
+            ModeRef::from_name("JT65").into_owned(),
+            Mode::from_name("JT65")
+        );
+        assert_eq!(Mode::Other("JT65".to_string()), ModeRef::Other("JT65"));
+        assert_ne!(Mode::Cw, ModeRef::Rtty);
+
+        assert_eq!(SpotTypeRef::from_name("ncdxf b"), SpotTypeRef::NcdxfBeacon);
+        assert_eq!(SpotTypeRef::from_name("QSO"), SpotTypeRef::Other("QSO"));
+        assert_eq!(SpotTypeRef::NcdxfBeacon.to_string(), "NCDXF B");
+        assert_eq!(
+            serde_json::to_string(&SpotTypeRef::NcdxfBeacon).unwrap(),
+            "\"NCDXF_BEACON\""
+        );
+        assert_eq!(
+            SpotType::Other("QSO".to_string()).as_spot_type_ref(),
+            SpotTypeRef::Other("QSO")
+        );
+    }
+
+    #[test]
+    fn test_spotter_id() {
+        let cases = [
+            ("EA5WU-#", "EA5WU", None, true),
+            ("KM3T-2-#", "KM3T", Some(2), true),
+            ("W1NT-6-#", "W1NT", Some(6), true),
+            ("DL1ABC-2", "DL1ABC", Some(2), false),
+            ("K1ABC", "K1ABC", None, false),
+            ("OH6BG/P-#", "OH6BG/P", None, true),
+        ];
+
+        for (spotter, base, instance, skimmer) in cases {
+            let id = SpotterId::parse(spotter);
+            assert_eq!(
+                id,
+                SpotterId {
+                    base,
+                    instance,
+                    skimmer
+                },
+                "{}",
+                spotter
+            );
+            assert_eq!(id.to_string(), spotter);
+        }
+    }
+
+    #[test]
+    fn test_callsign_parts() {
+        use CallsignModifier::*;
+
+        let cases: [(&str, &str, Option<&str>, Vec<CallsignModifier>); 9] = [
+            ("W6JSV", "W6JSV", None, vec![]),
+            ("W6JSV/P", "W6JSV", None, vec![Portable]),
+            ("VA3XCD/B", "VA3XCD", None, vec![Beacon]),
+            ("EA8/DL1ABC", "DL1ABC", Some("EA8"), vec![]),
+            ("DL1ABC/EA8", "DL1ABC", Some("EA8"), vec![]),
+            ("EA8/DL1ABC/p", "DL1ABC", Some("EA8"), vec![Portable]),
+            ("K1ABC/MM", "K1ABC", None, vec![MaritimeMobile]),
+            ("W6JSV/7/QRP", "W6JSV", Some("7"), vec![Qrp]),
+            ("N1NSP/AM", "N1NSP", None, vec![AeronauticalMobile]),
+        ];
+
+        for (call, home, location, modifiers) in cases {
+            assert_eq!(
+                Callsign::parse(call),
+                Callsign {
+                    home,
+                    location,
+                    modifiers
+                },
+                "{}",
+                call
+            );
+        }
+    }
+
+    #[test]
+    fn test_callsign_has_modifier() {
+        let call = Callsign::parse("DL1ABC/M");
+        assert!(call.has_modifier(CallsignModifier::Mobile));
+        assert!(!call.has_modifier(CallsignModifier::MaritimeMobile));
+    }
+
+    #[test]
+    fn test_display_human_spot() {
+        let spot = CwSpot {
+            spotter: "K1ABC".to_string(),
+            frequency: Frequency::from_khz(14025.0),
+            dx_call: "JA1XYZ".to_string(),
+            mode: Mode::Unknown,
+            snr_db: None,
+            speed: None,
+            spot_type: SpotType::Unknown,
+            time: NaiveTime::from_hms_opt(12, 34, 0).unwrap(),
+            comment: Some("tnx qso 599".to_string()),
+            received_at: None,
+            timestamp: None,
+            dx_dxcc: None,
+            spotter_dxcc: None,
+            source: None,
+        };
+
+        assert_eq!(
+            spot.to_string(),
+            "DX de K1ABC:  14025.0 JA1XYZ tnx qso 599 1234Z"
+        );
+    }
 }
@@ -7,11 +7,16 @@
 use hdrhistogram::Histogram;
 use serde::Serialize;
 use std::collections::HashMap;
-use std::sync::RwLock;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
-use crate::spot::{CwSpot, Mode, SpotType};
+use crate::band::Band;
+use crate::calibration::{FrequencyCalibrator, SpotterOffset};
+use crate::dxcc::Continent;
+use crate::line::{PropagationReport, PropagationSource, RbnLine};
+use crate::parser::ParseError;
+use crate::spot::{CwSpot, Mode, Speed, SpotType};
 
 /// Thread-safe statistics collector for RBN spots.
 #[derive(Debug)]
@@ -31,17 +36,26 @@ pub struct SpotStats {
     /// Total bytes of raw input processed
     pub bytes_processed: AtomicU64,
 
+    /// Total number of reconnection attempts
+    pub reconnect_attempts: AtomicU64,
+
+    /// Reconnection state per source
+    connections: RwLock<HashMap<String, SourceConnection>>,
+
     /// Histogram of spot sizes (JSON serialized size)
     size_histogram: RwLock<Histogram<u64>>,
 
     /// Histogram of SNR values
     snr_histogram: RwLock<Histogram<u64>>,
 
-    /// Histogram of WPM values
+    /// Histogram of WPM values (CW spots)
     wpm_histogram: RwLock<Histogram<u64>>,
 
+    /// Histogram of BPS values (RTTY/PSK spots)
+    bps_histogram: RwLock<Histogram<u64>>,
+
     /// Spots per band
-    spots_by_band: RwLock<HashMap<String, u64>>,
+    spots_by_band: RwLock<HashMap<Band, u64>>,
 
     /// Spots per mode
     spots_by_mode: RwLock<HashMap<Mode, u64>>,
@@ -49,9 +63,39 @@ pub struct SpotStats {
     /// Spots per type (CQ, BEACON, etc.)
     spots_by_type: RwLock<HashMap<SpotType, u64>>,
 
+    /// Spots per source feed (tagged spots only)
+    spots_by_source: RwLock<HashMap<String, u64>>,
+
     /// Top spotters (skimmers)
     top_spotters: RwLock<HashMap<String, u64>>,
 
+    /// Spots per base station, combining all of its skimmer instances
+    top_stations: RwLock<HashMap<String, u64>>,
+
+    /// Spots per DX continent (spots with a resolved DX call only)
+    spots_by_dx_continent: RwLock<HashMap<Continent, u64>>,
+
+    /// Spots per spotter continent (spots with a resolved spotter only)
+    spots_by_spotter_continent: RwLock<HashMap<Continent, u64>>,
+
+    /// Spots per DX CQ zone
+    spots_by_dx_cq_zone: RwLock<HashMap<u8, u64>>,
+
+    /// Spots per DX entity
+    top_dx_entities: RwLock<HashMap<String, u64>>,
+
+    /// Parse failures per reason (invalid_mode, missing_time, etc.)
+    parse_failures_by_reason: RwLock<HashMap<String, u64>>,
+
+    /// Non-spot lines per kind (banner, prompt, propagation, etc.)
+    non_spot_by_kind: RwLock<HashMap<&'static str, u64>>,
+
+    /// Latest propagation bulletin per source (WWV, WCY)
+    latest_propagation: RwLock<HashMap<PropagationSource, PropagationReport>>,
+
+    /// Per-skimmer frequency offset estimation, if enabled
+    calibrator: Option<Arc<FrequencyCalibrator>>,
+
     /// When stats collection started
     start_time: Instant,
 }
@@ -65,6 +109,8 @@ impl SpotStats {
             parse_failures: AtomicU64::new(0),
             non_spot_lines: AtomicU64::new(0),
             bytes_processed: AtomicU64::new(0),
+            reconnect_attempts: AtomicU64::new(0),
+            connections: RwLock::new(HashMap::new()),
             // Size histogram: 1 byte to 10KB, 3 significant figures
             size_histogram: RwLock::new(
                 Histogram::new_with_bounds(1, 10_000, 3).expect("Failed to create size histogram"),
@@ -77,14 +123,39 @@ impl SpotStats {
             wpm_histogram: RwLock::new(
                 Histogram::new_with_bounds(1, 100, 2).expect("Failed to create WPM histogram"),
             ),
+            // BPS histogram: 1 to 1000 baud
+            bps_histogram: RwLock::new(
+                Histogram::new_with_bounds(1, 1000, 2).expect("Failed to create BPS histogram"),
+            ),
             spots_by_band: RwLock::new(HashMap::new()),
             spots_by_mode: RwLock::new(HashMap::new()),
             spots_by_type: RwLock::new(HashMap::new()),
+            spots_by_source: RwLock::new(HashMap::new()),
             top_spotters: RwLock::new(HashMap::new()),
+            top_stations: RwLock::new(HashMap::new()),
+            spots_by_dx_continent: RwLock::new(HashMap::new()),
+            spots_by_spotter_continent: RwLock::new(HashMap::new()),
+            spots_by_dx_cq_zone: RwLock::new(HashMap::new()),
+            top_dx_entities: RwLock::new(HashMap::new()),
+            parse_failures_by_reason: RwLock::new(HashMap::new()),
+            non_spot_by_kind: RwLock::new(HashMap::new()),
+            latest_propagation: RwLock::new(HashMap::new()),
+            calibrator: None,
             start_time: Instant::now(),
         }
     }
 
+    /// Feed every recorded spot to a frequency calibrator.
+    pub fn with_calibrator(mut self, calibrator: Arc<FrequencyCalibrator>) -> Self {
+        self.calibrator = Some(calibrator);
+        self
+    }
+
+    /// The frequency calibrator, if one was attached.
+    pub fn calibrator(&self) -> Option<&FrequencyCalibrator> {
+        self.calibrator.as_deref()
+    }
+
     /// Record a successfully parsed spot.
     pub fn record_spot(&self, spot: &CwSpot) {
         self.total_spots.fetch_add(1, Ordering::Relaxed);
@@ -100,42 +171,99 @@ impl SpotStats {
         }
 
         // Record SNR distribution (offset by 30 to handle negatives)
-        let snr_offset = (spot.snr_db + 30).max(0) as u64;
-        if let Ok(mut hist) = self.snr_histogram.write() {
+        if let Some(snr_db) = spot.snr_db
+            && let Ok(mut hist) = self.snr_histogram.write()
+        {
+            let snr_offset = (snr_db + 30).max(0) as u64;
             let _ = hist.record(snr_offset.clamp(1, 99));
         }
 
-        // Record WPM distribution
-        if let Ok(mut hist) = self.wpm_histogram.write() {
-            let _ = hist.record((spot.wpm as u64).clamp(1, 99));
+        // Record speed distribution in the histogram matching its unit
+        match spot.speed {
+            Some(Speed::Wpm(wpm)) => {
+                if let Ok(mut hist) = self.wpm_histogram.write() {
+                    let _ = hist.record((wpm as u64).clamp(1, 99));
+                }
+            }
+            Some(Speed::Bps(bps)) => {
+                if let Ok(mut hist) = self.bps_histogram.write() {
+                    let _ = hist.record((bps as u64).clamp(1, 999));
+                }
+            }
+            None => {}
         }
 
         // Record by band
         if let Some(band) = spot.band()
             && let Ok(mut map) = self.spots_by_band.write()
         {
-            *map.entry(band.to_string()).or_insert(0) += 1;
+            *map.entry(band).or_insert(0) += 1;
         }
 
         // Record by mode
         if let Ok(mut map) = self.spots_by_mode.write() {
-            *map.entry(spot.mode).or_insert(0) += 1;
+            *map.entry(spot.mode.clone()).or_insert(0) += 1;
         }
 
         // Record by type
         if let Ok(mut map) = self.spots_by_type.write() {
-            *map.entry(spot.spot_type).or_insert(0) += 1;
+            *map.entry(spot.spot_type.clone()).or_insert(0) += 1;
+        }
+
+        // Record by source
+        if let Some(ref source) = spot.source
+            && let Ok(mut map) = self.spots_by_source.write()
+        {
+            *map.entry(source.clone()).or_insert(0) += 1;
         }
 
         // Record spotter
         if let Ok(mut map) = self.top_spotters.write() {
             *map.entry(spot.spotter.clone()).or_insert(0) += 1;
         }
+        if let Ok(mut map) = self.top_stations.write() {
+            *map.entry(spot.spotter_id().base.to_string()).or_insert(0) += 1;
+        }
+
+        // Record where the DX station and spotter are, if resolved
+        if let Some(ref dxcc) = spot.dx_dxcc {
+            if let Ok(mut map) = self.spots_by_dx_continent.write() {
+                *map.entry(dxcc.continent).or_insert(0) += 1;
+            }
+            if let Ok(mut map) = self.spots_by_dx_cq_zone.write() {
+                *map.entry(dxcc.cq_zone).or_insert(0) += 1;
+            }
+            if let Ok(mut map) = self.top_dx_entities.write() {
+                *map.entry(dxcc.entity.to_string()).or_insert(0) += 1;
+            }
+        }
+        if let Some(ref dxcc) = spot.spotter_dxcc
+            && let Ok(mut map) = self.spots_by_spotter_continent.write()
+        {
+            *map.entry(dxcc.continent).or_insert(0) += 1;
+        }
+
+        if let Some(ref calibrator) = self.calibrator {
+            calibrator.observe(spot);
+        }
     }
 
-    /// Record a parse failure.
+    /// Record a parse failure whose cause is not known.
     pub fn record_parse_failure(&self) {
+        self.record_parse_failure_reason("unknown");
+    }
+
+    /// Record a parse failure, counted by the error's reason.
+    pub fn record_parse_error(&self, error: &ParseError) {
+        self.record_parse_failure_reason(&error.reason());
+    }
+
+    fn record_parse_failure_reason(&self, reason: &str) {
         self.parse_failures.fetch_add(1, Ordering::Relaxed);
+
+        if let Ok(mut map) = self.parse_failures_by_reason.write() {
+            *map.entry(reason.to_string()).or_insert(0) += 1;
+        }
     }
 
     /// Record a non-spot line.
@@ -143,11 +271,67 @@ impl SpotStats {
         self.non_spot_lines.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// Record a classified non-spot line.
+    ///
+    /// Counts the line by kind and keeps the latest propagation bulletin
+    /// from each source.
+    pub fn record_non_spot_line(&self, line: &RbnLine) {
+        self.record_non_spot();
+
+        if let Ok(mut map) = self.non_spot_by_kind.write() {
+            *map.entry(line.kind()).or_insert(0) += 1;
+        }
+
+        if let RbnLine::Propagation(report) = line
+            && let Ok(mut map) = self.latest_propagation.write()
+        {
+            map.insert(report.source, report.clone());
+        }
+    }
+
     /// Record bytes processed.
     pub fn record_bytes(&self, bytes: u64) {
         self.bytes_processed.fetch_add(bytes, Ordering::Relaxed);
     }
 
+    /// Record a scheduled reconnection attempt of a source and its
+    /// backoff delay.
+    pub fn record_reconnect(&self, source: &str, attempt: u32, delay: Duration) {
+        self.reconnect_attempts.fetch_add(1, Ordering::Relaxed);
+        if let Ok(mut map) = self.connections.write() {
+            let connection = map.entry(source.to_string()).or_default();
+            connection.reconnect_attempts += 1;
+            connection.consecutive_reconnects = u64::from(attempt);
+            connection.reconnect_backoff_secs = delay.as_secs_f64();
+        }
+    }
+
+    /// Record that a source's reconnect backoff started over, after a
+    /// connection stayed up long enough.
+    pub fn record_backoff_reset(&self, source: &str) {
+        if let Ok(mut map) = self.connections.write() {
+            let connection = map.entry(source.to_string()).or_default();
+            connection.consecutive_reconnects = 0;
+            connection.reconnect_backoff_secs = 0.0;
+        }
+    }
+
+    /// Record a successful login of a source to `endpoint`.
+    ///
+    /// The backoff is kept until [`SpotStats::record_backoff_reset`], as a
+    /// login alone doesn't reset it in the client either.
+    pub fn record_connected(&self, source: &str, endpoint: &str) {
+        if let Ok(mut map) = self.connections.write() {
+            let connection = map.entry(source.to_string()).or_default();
+            if connection.endpoint.as_deref() != Some(endpoint) {
+                if connection.endpoint.is_some() {
+                    connection.endpoint_switches += 1;
+                }
+                connection.endpoint = Some(endpoint.to_string());
+            }
+        }
+    }
+
     /// Get the elapsed time since stats collection started.
     pub fn elapsed(&self) -> Duration {
         self.start_time.elapsed()
@@ -182,6 +366,7 @@ impl SpotStats {
                 min: h.min(),
                 max: h.max(),
                 mean: h.mean(),
+                count: h.len(),
             })
             .ok();
 
@@ -196,6 +381,7 @@ impl SpotStats {
                 min: h.min().saturating_sub(30),
                 max: h.max().saturating_sub(30),
                 mean: h.mean() - 30.0,
+                count: h.len(),
             })
             .ok();
 
@@ -209,13 +395,28 @@ impl SpotStats {
                 min: h.min(),
                 max: h.max(),
                 mean: h.mean(),
+                count: h.len(),
+            })
+            .ok();
+
+        let bps_percentiles = self
+            .bps_histogram
+            .read()
+            .map(|h| HistogramPercentiles {
+                p50: h.value_at_quantile(0.50),
+                p90: h.value_at_quantile(0.90),
+                p99: h.value_at_quantile(0.99),
+                min: h.min(),
+                max: h.max(),
+                mean: h.mean(),
+                count: h.len(),
             })
             .ok();
 
         let spots_by_band = self
             .spots_by_band
             .read()
-            .map(|m| m.clone())
+            .map(|m| m.iter().map(|(k, v)| (k.to_string(), *v)).collect())
             .unwrap_or_default();
 
         let spots_by_mode = self
@@ -224,6 +425,18 @@ impl SpotStats {
             .map(|m| m.iter().map(|(k, v)| (k.to_string(), *v)).collect())
             .unwrap_or_default();
 
+        let spots_by_source = self
+            .spots_by_source
+            .read()
+            .map(|m| m.clone())
+            .unwrap_or_default();
+
+        let connections = self
+            .connections
+            .read()
+            .map(|m| m.clone())
+            .unwrap_or_default();
+
         let spots_by_type = self
             .spots_by_type
             .read()
@@ -236,7 +449,79 @@ impl SpotStats {
             .read()
             .map(|m| {
                 let mut vec: Vec<_> = m.iter().map(|(k, v)| (k.clone(), *v)).collect();
-                vec.sort_by(|a, b| b.1.cmp(&a.1));
+                vec.sort_by_key(|b| std::cmp::Reverse(b.1));
+                vec.truncate(10);
+                vec
+            })
+            .unwrap_or_default();
+
+        // Get top 10 stations
+        let top_stations = self
+            .top_stations
+            .read()
+            .map(|m| {
+                let mut vec: Vec<_> = m.iter().map(|(k, v)| (k.clone(), *v)).collect();
+                vec.sort_by_key(|b| std::cmp::Reverse(b.1));
+                vec.truncate(10);
+                vec
+            })
+            .unwrap_or_default();
+
+        let spots_by_dx_continent = self
+            .spots_by_dx_continent
+            .read()
+            .map(|m| m.iter().map(|(k, v)| (k.to_string(), *v)).collect())
+            .unwrap_or_default();
+
+        let spots_by_spotter_continent = self
+            .spots_by_spotter_continent
+            .read()
+            .map(|m| m.iter().map(|(k, v)| (k.to_string(), *v)).collect())
+            .unwrap_or_default();
+
+        let spots_by_dx_cq_zone = self
+            .spots_by_dx_cq_zone
+            .read()
+            .map(|m| m.clone())
+            .unwrap_or_default();
+
+        // Get top 10 DX entities
+        let top_dx_entities = self
+            .top_dx_entities
+            .read()
+            .map(|m| {
+                let mut vec: Vec<_> = m.iter().map(|(k, v)| (k.clone(), *v)).collect();
+                vec.sort_by_key(|b| std::cmp::Reverse(b.1));
+                vec.truncate(10);
+                vec
+            })
+            .unwrap_or_default();
+
+        let parse_failures_by_reason = self
+            .parse_failures_by_reason
+            .read()
+            .map(|m| m.clone())
+            .unwrap_or_default();
+
+        let non_spot_by_kind = self
+            .non_spot_by_kind
+            .read()
+            .map(|m| m.iter().map(|(k, v)| (k.to_string(), *v)).collect())
+            .unwrap_or_default();
+
+        let propagation = self
+            .latest_propagation
+            .read()
+            .map(|m| m.iter().map(|(k, v)| (k.to_string(), v.clone())).collect())
+            .unwrap_or_default();
+
+        // Get the 10 skimmers furthest off frequency
+        let frequency_offsets = self
+            .calibrator
+            .as_ref()
+            .map(|c| {
+                let mut vec = c.offsets();
+                vec.sort_by(|a, b| b.offset_khz.abs().total_cmp(&a.offset_khz.abs()));
                 vec.truncate(10);
                 vec
             })
@@ -247,16 +532,29 @@ impl SpotStats {
             total_spots: total,
             cw_spots: cw,
             parse_failures: failures,
+            parse_failures_by_reason,
             non_spot_lines: non_spots,
             bytes_processed: bytes,
             spots_per_second: self.spots_per_second(),
+            reconnect_attempts: self.reconnect_attempts.load(Ordering::Relaxed),
+            connections,
             size_percentiles,
             snr_percentiles,
             wpm_percentiles,
+            bps_percentiles,
             spots_by_band,
             spots_by_mode,
             spots_by_type,
+            spots_by_source,
             top_spotters,
+            top_stations,
+            spots_by_dx_continent,
+            spots_by_spotter_continent,
+            spots_by_dx_cq_zone,
+            top_dx_entities,
+            non_spot_by_kind,
+            propagation,
+            frequency_offsets,
         }
     }
 }
@@ -267,6 +565,21 @@ impl Default for SpotStats {
     }
 }
 
+/// Reconnection state of one source.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SourceConnection {
+    /// Total reconnection attempts.
+    pub reconnect_attempts: u64,
+    /// Attempts since the backoff last started over.
+    pub consecutive_reconnects: u64,
+    /// Delay before the latest attempt, 0 once the backoff starts over.
+    pub reconnect_backoff_secs: f64,
+    /// Endpoint (`host:port`) of the current or last login.
+    pub endpoint: Option<String>,
+    /// Logins to a different endpoint than the one before.
+    pub endpoint_switches: u64,
+}
+
 /// Percentile values from a histogram.
 #[derive(Debug, Clone, Serialize)]
 pub struct HistogramPercentiles {
@@ -276,6 +589,7 @@ pub struct HistogramPercentiles {
     pub min: u64,
     pub max: u64,
     pub mean: f64,
+    pub count: u64,
 }
 
 /// Summary of collected statistics.
@@ -285,16 +599,35 @@ pub struct StatsSummary {
     pub total_spots: u64,
     pub cw_spots: u64,
     pub parse_failures: u64,
+    pub parse_failures_by_reason: HashMap<String, u64>,
     pub non_spot_lines: u64,
     pub bytes_processed: u64,
     pub spots_per_second: f64,
+    pub reconnect_attempts: u64,
+    /// Reconnection state per source.
+    pub connections: HashMap<String, SourceConnection>,
     pub size_percentiles: Option<HistogramPercentiles>,
     pub snr_percentiles: Option<HistogramPercentiles>,
     pub wpm_percentiles: Option<HistogramPercentiles>,
+    pub bps_percentiles: Option<HistogramPercentiles>,
     pub spots_by_band: HashMap<String, u64>,
     pub spots_by_mode: HashMap<String, u64>,
     pub spots_by_type: HashMap<String, u64>,
+    /// Spots per source feed.
+    pub spots_by_source: HashMap<String, u64>,
     pub top_spotters: Vec<(String, u64)>,
+    /// Top base stations, with all skimmer instances combined.
+    pub top_stations: Vec<(String, u64)>,
+    /// Spots per DX continent, for spots resolved from the country file.
+    pub spots_by_dx_continent: HashMap<String, u64>,
+    /// Spots per spotter continent, for spots resolved from the country file.
+    pub spots_by_spotter_continent: HashMap<String, u64>,
+    pub spots_by_dx_cq_zone: HashMap<u8, u64>,
+    pub top_dx_entities: Vec<(String, u64)>,
+    pub non_spot_by_kind: HashMap<String, u64>,
+    pub propagation: HashMap<String, PropagationReport>,
+    /// Skimmers with the largest calibrated frequency offsets.
+    pub frequency_offsets: Vec<SpotterOffset>,
 }
 
 impl std::fmt::Display for StatsSummary {
@@ -316,9 +649,30 @@ impl std::fmt::Display for StatsSummary {
             }
         )?;
         writeln!(f, "Parse failures: {}", self.parse_failures)?;
+        let mut reasons: Vec<_> = self.parse_failures_by_reason.iter().collect();
+        reasons.sort_by_key(|r| std::cmp::Reverse(r.1));
+        for (reason, count) in reasons {
+            writeln!(f, "  {}: {}", reason, count)?;
+        }
         writeln!(f, "Non-spot lines: {}", self.non_spot_lines)?;
         writeln!(f, "Bytes processed: {} KB", self.bytes_processed / 1024)?;
         writeln!(f, "Rate: {:.1} spots/sec", self.spots_per_second)?;
+        if self.reconnect_attempts > 0 {
+            writeln!(f, "Reconnect attempts: {}", self.reconnect_attempts)?;
+            let mut connections: Vec<_> = self
+                .connections
+                .iter()
+                .filter(|(_, c)| c.consecutive_reconnects > 0)
+                .collect();
+            connections.sort_by(|a, b| a.0.cmp(b.0));
+            for (source, c) in connections {
+                writeln!(
+                    f,
+                    "  {}: {:.1}s backoff before attempt {}",
+                    source, c.reconnect_backoff_secs, c.consecutive_reconnects
+                )?;
+            }
+        }
         writeln!(f)?;
 
         if let Some(ref p) = self.size_percentiles {
@@ -350,6 +704,15 @@ impl std::fmt::Display for StatsSummary {
             writeln!(f)?;
         }
 
+        if let Some(ref p) = self.bps_percentiles
+            && p.count > 0
+        {
+            writeln!(f, "BPS Distribution:")?;
+            writeln!(f, "  Min: {}, Max: {}, Mean: {:.1}", p.min, p.max, p.mean)?;
+            writeln!(f, "  P50: {}, P90: {}, P99: {}", p.p50, p.p90, p.p99)?;
+            writeln!(f)?;
+        }
+
         if !self.spots_by_band.is_empty() {
             writeln!(f, "Spots by Band:")?;
             let mut bands: Vec<_> = self.spots_by_band.iter().collect();
@@ -360,6 +723,34 @@ impl std::fmt::Display for StatsSummary {
             writeln!(f)?;
         }
 
+        let mut endpoints: Vec<_> = self
+            .connections
+            .iter()
+            .filter_map(|(source, c)| Some((source, c.endpoint.as_ref()?, c.endpoint_switches)))
+            .collect();
+        if !endpoints.is_empty() {
+            writeln!(f, "Endpoints:")?;
+            endpoints.sort();
+            for (source, endpoint, switches) in endpoints {
+                if switches > 0 {
+                    writeln!(f, "  {}: {} ({} switches)", source, endpoint, switches)?;
+                } else {
+                    writeln!(f, "  {}: {}", source, endpoint)?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        if !self.spots_by_source.is_empty() {
+            writeln!(f, "Spots by Source:")?;
+            let mut sources: Vec<_> = self.spots_by_source.iter().collect();
+            sources.sort_by(|a, b| b.1.cmp(a.1));
+            for (source, count) in sources {
+                writeln!(f, "  {}: {}", source, count)?;
+            }
+            writeln!(f)?;
+        }
+
         if !self.spots_by_type.is_empty() {
             writeln!(f, "Spots by Type:")?;
             let mut types: Vec<_> = self.spots_by_type.iter().collect();
@@ -370,6 +761,78 @@ impl std::fmt::Display for StatsSummary {
             writeln!(f)?;
         }
 
+        if !self.spots_by_dx_continent.is_empty() || !self.spots_by_spotter_continent.is_empty() {
+            writeln!(f, "Spots by Continent (DX / spotter):")?;
+            let mut continents: Vec<_> = self
+                .spots_by_dx_continent
+                .keys()
+                .chain(self.spots_by_spotter_continent.keys())
+                .collect();
+            continents.sort();
+            continents.dedup();
+            for continent in continents {
+                writeln!(
+                    f,
+                    "  {}: {} / {}",
+                    continent,
+                    self.spots_by_dx_continent.get(continent).unwrap_or(&0),
+                    self.spots_by_spotter_continent.get(continent).unwrap_or(&0),
+                )?;
+            }
+            writeln!(f)?;
+        }
+
+        if !self.spots_by_dx_cq_zone.is_empty() {
+            writeln!(f, "Spots by DX CQ Zone:")?;
+            let mut zones: Vec<_> = self.spots_by_dx_cq_zone.iter().collect();
+            zones.sort();
+            for (zone, count) in zones {
+                writeln!(f, "  {}: {}", zone, count)?;
+            }
+            writeln!(f)?;
+        }
+
+        if !self.top_dx_entities.is_empty() {
+            writeln!(f, "Top 10 DX Entities:")?;
+            for (i, (entity, count)) in self.top_dx_entities.iter().enumerate() {
+                writeln!(f, "  {}. {}: {}", i + 1, entity, count)?;
+            }
+            writeln!(f)?;
+        }
+
+        if !self.propagation.is_empty() {
+            writeln!(f, "Propagation:")?;
+            let mut reports: Vec<_> = self.propagation.iter().collect();
+            reports.sort_by(|a, b| a.0.cmp(b.0));
+            for (source, report) in reports {
+                let fmt_value = |v: Option<u16>| v.map_or("-".to_string(), |v| v.to_string());
+                writeln!(
+                    f,
+                    "  {} ({}): SFI={} A={} K={}",
+                    source,
+                    report.reporter,
+                    fmt_value(report.sfi),
+                    fmt_value(report.a_index),
+                    fmt_value(report.k_index.map(u16::from)),
+                )?;
+            }
+            writeln!(f)?;
+        }
+
+        if !self.frequency_offsets.is_empty() {
+            writeln!(f, "Largest Skimmer Frequency Offsets:")?;
+            for offset in &self.frequency_offsets {
+                writeln!(
+                    f,
+                    "  {}: {:+.0} Hz ({} samples)",
+                    offset.spotter,
+                    offset.offset_khz * 1000.0,
+                    offset.samples
+                )?;
+            }
+            writeln!(f)?;
+        }
+
         if !self.top_spotters.is_empty() {
             writeln!(f, "Top 10 Spotters:")?;
             for (i, (spotter, count)) in self.top_spotters.iter().enumerate() {
@@ -377,6 +840,14 @@ impl std::fmt::Display for StatsSummary {
             }
         }
 
+        if !self.top_stations.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Top 10 Stations (all skimmers):")?;
+            for (i, (station, count)) in self.top_stations.iter().enumerate() {
+                writeln!(f, "  {}. {}: {}", i + 1, station, count)?;
+            }
+        }
+
         Ok(())
     }
 }
@@ -384,18 +855,25 @@ impl std::fmt::Display for StatsSummary {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::frequency::Frequency;
     use chrono::NaiveTime;
 
     fn make_test_spot() -> CwSpot {
         CwSpot {
             spotter: "TEST-#".to_string(),
-            frequency_khz: 14025.0,
+            frequency: Frequency::from_khz(14025.0),
             dx_call: "W1AW".to_string(),
             mode: Mode::Cw,
-            snr_db: 15,
-            wpm: 22,
+            snr_db: Some(15),
+            speed: Some(Speed::Wpm(22)),
             spot_type: SpotType::Cq,
             time: NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+            comment: None,
+            received_at: None,
+            timestamp: None,
+            dx_dxcc: None,
+            spotter_dxcc: None,
+            source: None,
         }
     }
 
@@ -441,4 +919,257 @@ mod tests {
         assert_eq!(summary.non_spot_lines, 1);
         assert_eq!(summary.bytes_processed, 1000);
     }
+
+    #[test]
+    fn test_unknown_mode_and_type_breakdowns() {
+        let stats = SpotStats::new();
+        let mut spot = make_test_spot();
+        spot.mode = Mode::Other("JT65".to_string());
+        spot.spot_type = SpotType::Other("QSO".to_string());
+
+        stats.record_spot(&spot);
+        stats.record_spot(&make_test_spot());
+
+        let summary = stats.summary();
+        assert_eq!(summary.spots_by_mode.get("JT65"), Some(&1));
+        assert_eq!(summary.spots_by_mode.get("CW"), Some(&1));
+        assert_eq!(summary.spots_by_type.get("QSO"), Some(&1));
+    }
+
+    #[test]
+    fn test_frequency_offsets_in_summary() {
+        use crate::config::CalibrationConfig;
+        use crate::parser::parse_spot;
+
+        let calibrator = FrequencyCalibrator::new(&CalibrationConfig {
+            min_samples: 1,
+            ..Default::default()
+        });
+        let stats = SpotStats::new().with_calibrator(Arc::new(calibrator));
+        let received = "2026-01-09T23:00:00Z".parse().unwrap();
+        for line in [
+            "DX de EA5WU-#:    7018.2  RW1M           CW    19 dB  18 WPM  CQ      2259Z",
+            "DX de KM3T-#:     7018.2  RW1M           CW    12 dB  18 WPM  CQ      2259Z",
+            "DX de W3OA-#:     7018.3  RW1M           CW     8 dB  18 WPM  CQ      2259Z",
+        ] {
+            stats.record_spot(&parse_spot(line).unwrap().with_received_at(received));
+        }
+
+        let summary = stats.summary();
+        assert_eq!(summary.frequency_offsets.len(), 1);
+        assert_eq!(summary.frequency_offsets[0].spotter, "W3OA-#");
+        assert!(summary.to_string().contains("W3OA-#: +100 Hz (1 samples)"));
+    }
+
+    #[test]
+    fn test_parse_failures_by_reason() {
+        use crate::parser::parse_line;
+
+        let stats = SpotStats::new();
+        for line in [
+            "DX de EA5WU-#:    7018.3  RW1M           C?    19 dB  18 WPM  CQ      2259Z",
+            "DX de EA5WU-#:    7018.3  RW1M           Z.    19 dB  18 WPM  CQ      2259Z",
+            "DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2561Z",
+        ] {
+            stats.record_parse_error(&parse_line(line).unwrap_err());
+        }
+        stats.record_parse_failure();
+
+        let summary = stats.summary();
+        assert_eq!(summary.parse_failures, 4);
+        assert_eq!(
+            summary.parse_failures_by_reason.get("invalid_mode"),
+            Some(&2)
+        );
+        assert_eq!(
+            summary.parse_failures_by_reason.get("invalid_time"),
+            Some(&1)
+        );
+        assert_eq!(summary.parse_failures_by_reason.get("unknown"), Some(&1));
+    }
+
+    #[test]
+    fn test_top_stations_combine_instances() {
+        let stats = SpotStats::new();
+        for spotter in ["KM3T-#", "KM3T-2-#", "KM3T-2-#", "EA5WU-#"] {
+            let mut spot = make_test_spot();
+            spot.spotter = spotter.to_string();
+            stats.record_spot(&spot);
+        }
+
+        let summary = stats.summary();
+        assert_eq!(summary.top_spotters[0], ("KM3T-2-#".to_string(), 2));
+        assert_eq!(summary.top_stations[0], ("KM3T".to_string(), 3));
+        assert_eq!(summary.top_stations[1], ("EA5WU".to_string(), 1));
+    }
+
+    #[test]
+    fn test_dxcc_breakdowns() {
+        let dxcc = crate::dxcc::DxccDatabase::parse_dat(
+            "Fed. Rep. of Germany: 14: 28: EU: 51.00: -10.00: -1.0: DL:\n DK,DL;\n\
+             United States: 05: 08: NA: 37.53: 91.67: 5.0: K:\n K,W,W6(3)[6];\n",
+        )
+        .unwrap();
+
+        let stats = SpotStats::new();
+        for (dx_call, spotter) in [
+            ("DK8NE", "KM3T-#"),
+            ("DL1ABC", "W3OA-#"),
+            ("W6JSV", "DK9IP-#"),
+            ("JA1XYZ", "KM3T-#"),
+        ] {
+            let mut spot = make_test_spot();
+            spot.dx_call = dx_call.to_string();
+            spot.spotter = spotter.to_string();
+            stats.record_spot(&spot.with_dxcc(&dxcc));
+        }
+
+        let summary = stats.summary();
+        assert_eq!(summary.spots_by_dx_continent.get("EU"), Some(&2));
+        assert_eq!(summary.spots_by_dx_continent.get("NA"), Some(&1));
+        assert_eq!(summary.spots_by_spotter_continent.get("NA"), Some(&3));
+        assert_eq!(summary.spots_by_spotter_continent.get("EU"), Some(&1));
+        assert_eq!(summary.spots_by_dx_cq_zone.get(&3), Some(&1));
+        assert_eq!(
+            summary.top_dx_entities[0],
+            ("Fed. Rep. of Germany".to_string(), 2)
+        );
+        assert!(summary.to_string().contains("  EU: 2 / 1"));
+    }
+
+    #[test]
+    fn test_record_non_spot_line() {
+        use crate::parser::parse_line;
+
+        let stats = SpotStats::new();
+        for line in [
+            "WWV de W0MU <18>:   SFI=70, A=5, K=1, No Storms -> No Storms",
+            "WWV de W0MU <21>:   SFI=72, A=6, K=2, No Storms -> No Storms",
+            "Welcome to the Reverse Beacon Network",
+            "W6JSV de RELAY 08-Jan-2026 03:13Z >",
+        ] {
+            stats.record_non_spot_line(&parse_line(line).unwrap());
+        }
+
+        let summary = stats.summary();
+        assert_eq!(summary.non_spot_lines, 4);
+        assert_eq!(summary.non_spot_by_kind.get("propagation"), Some(&2));
+        assert_eq!(summary.non_spot_by_kind.get("banner"), Some(&1));
+        assert_eq!(summary.non_spot_by_kind.get("prompt"), Some(&1));
+
+        // Only the latest WWV bulletin is kept
+        let wwv = summary.propagation.get("WWV").unwrap();
+        assert_eq!(wwv.hour, Some(21));
+        assert_eq!(wwv.sfi, Some(72));
+    }
+
+    #[test]
+    fn test_speed_histograms_by_unit() {
+        let stats = SpotStats::new();
+
+        stats.record_spot(&make_test_spot());
+        stats.record_spot(&CwSpot {
+            mode: Mode::Rtty,
+            speed: Some(Speed::Bps(45)),
+            ..make_test_spot()
+        });
+        stats.record_spot(&CwSpot {
+            mode: Mode::Rtty,
+            speed: None,
+            ..make_test_spot()
+        });
+
+        let summary = stats.summary();
+        let wpm = summary.wpm_percentiles.unwrap();
+        let bps = summary.bps_percentiles.unwrap();
+
+        assert_eq!(summary.total_spots, 3);
+        assert_eq!(wpm.count, 1);
+        assert_eq!(wpm.p50, 22);
+        assert_eq!(bps.count, 1);
+        assert_eq!(bps.p50, 45);
+    }
+
+    #[test]
+    fn test_record_reconnects() {
+        let stats = SpotStats::new();
+        assert!(!stats.summary().to_string().contains("Reconnect"));
+
+        stats.record_connected("rbn-cw", "telnet.reversebeacon.net:7000");
+        stats.record_reconnect("rbn-digital", 1, Duration::from_millis(2500));
+        stats.record_reconnect("rbn-digital", 2, Duration::from_millis(7250));
+        let summary = stats.summary();
+        assert_eq!(summary.reconnect_attempts, 2);
+        let digital = &summary.connections["rbn-digital"];
+        assert_eq!(digital.reconnect_attempts, 2);
+        assert_eq!(digital.consecutive_reconnects, 2);
+        assert_eq!(digital.reconnect_backoff_secs, 7.25);
+        assert_eq!(summary.connections["rbn-cw"].reconnect_attempts, 0);
+        assert!(
+            summary
+                .to_string()
+                .contains("  rbn-digital: 7.2s backoff before attempt 2")
+        );
+
+        // A login alone keeps the backoff, like the client does
+        stats.record_connected("rbn-digital", "telnet.reversebeacon.net:7001");
+        let digital = &stats.summary().connections["rbn-digital"];
+        assert_eq!(digital.consecutive_reconnects, 2);
+        assert_eq!(digital.reconnect_backoff_secs, 7.25);
+
+        stats.record_backoff_reset("rbn-digital");
+        let summary = stats.summary();
+        let digital = &summary.connections["rbn-digital"];
+        assert_eq!(digital.reconnect_attempts, 2);
+        assert_eq!(digital.consecutive_reconnects, 0);
+        assert_eq!(digital.reconnect_backoff_secs, 0.0);
+        assert!(!summary.to_string().contains("backoff"));
+    }
+
+    #[test]
+    fn test_record_endpoint_switches() {
+        let stats = SpotStats::new();
+        stats.record_connected("rbn", "telnet.reversebeacon.net:7000");
+        stats.record_connected("rbn", "telnet.reversebeacon.net:7000");
+        let summary = stats.summary();
+        assert_eq!(summary.connections["rbn"].endpoint_switches, 0);
+        assert!(
+            summary
+                .to_string()
+                .contains("Endpoints:\n  rbn: telnet.reversebeacon.net:7000\n")
+        );
+
+        stats.record_connected("rbn", "mirror.example.net:7000");
+        stats.record_connected("rbn", "telnet.reversebeacon.net:7000");
+        let summary = stats.summary();
+        let rbn = &summary.connections["rbn"];
+        assert_eq!(
+            rbn.endpoint.as_deref(),
+            Some("telnet.reversebeacon.net:7000")
+        );
+        assert_eq!(rbn.endpoint_switches, 2);
+        assert!(
+            summary
+                .to_string()
+                .contains("  rbn: telnet.reversebeacon.net:7000 (2 switches)")
+        );
+    }
+
+    #[test]
+    fn test_spots_by_source() {
+        let stats = SpotStats::new();
+        stats.record_spot(&make_test_spot());
+        stats.record_spot(&make_test_spot().with_source("skimmer"));
+        stats.record_spot(&make_test_spot().with_source("skimmer"));
+
+        let summary = stats.summary();
+        assert_eq!(summary.total_spots, 3);
+        assert_eq!(summary.spots_by_source.len(), 1);
+        assert_eq!(summary.spots_by_source.get("skimmer"), Some(&2));
+        assert!(
+            summary
+                .to_string()
+                .contains("Spots by Source:\n  skimmer: 2")
+        );
+    }
 }
@@ -11,8 +11,11 @@ use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering::Relaxed};
 
 use serde::Serialize;
 
+use crate::band::{Band, Segment};
+use crate::calibration::FrequencyCalibrator;
 use crate::config::StorageConfig;
 use crate::filter::SpotFilter;
+use crate::frequency::Frequency;
 use crate::polo::PoloNotesManager;
 use crate::spot::CwSpot;
 
@@ -23,6 +26,14 @@ pub struct StoredSpot {
     pub seq: u64,
     /// The actual spot data.
     pub spot: CwSpot,
+    /// Band of the spot's frequency, derived from the band plan.
+    pub band: Option<Band>,
+    /// Band-plan segments of the spot's frequency (cw, beacon, etc.).
+    pub segments: Vec<Segment>,
+    /// Frequency with the spotter's estimated offset removed, when
+    /// calibration is enabled and the spotter is calibrated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub corrected_frequency_khz: Option<Frequency>,
 }
 
 /// Per-filter storage queue.
@@ -83,11 +94,29 @@ impl FilterStorage {
             .collect()
     }
 
+    /// Get spots with sequence number greater than `since` from one station.
+    ///
+    /// `station` is compared against the spotter's base callsign
+    /// (case-insensitive), so it covers every skimmer the station runs.
+    pub fn get_station_spots_since(&self, since: u64, station: &str) -> Vec<StoredSpot> {
+        self.spots
+            .iter()
+            .filter(|s| s.seq > since && s.spot.spotter_id().base.eq_ignore_ascii_case(station))
+            .cloned()
+            .collect()
+    }
+
     /// Push a spot, returning its size in bytes.
-    fn push(&mut self, spot: CwSpot) -> usize {
+    fn push(&mut self, spot: CwSpot, corrected_frequency_khz: Option<Frequency>) -> usize {
         let size = spot.json_size();
         let seq = self.next_seq.fetch_add(1, Relaxed);
-        self.spots.push_back(StoredSpot { seq, spot });
+        self.spots.push_back(StoredSpot {
+            seq,
+            band: spot.band(),
+            segments: spot.segments(),
+            corrected_frequency_khz,
+            spot,
+        });
         self.current_size_bytes.fetch_add(size, Relaxed);
         size
     }
@@ -119,6 +148,9 @@ pub struct SpotStorage {
 
     /// PoLo notes manager for callsign lookup (if any filter uses polo_notes_url).
     polo_manager: Option<Arc<PoloNotesManager>>,
+
+    /// Frequency calibrator used to add corrected frequencies, if enabled.
+    calibrator: Option<Arc<FrequencyCalibrator>>,
 }
 
 impl SpotStorage {
@@ -155,9 +187,16 @@ impl SpotStorage {
             total_size_bytes: AtomicUsize::new(0),
             global_evictions: AtomicU64::new(0),
             polo_manager,
+            calibrator: None,
         }
     }
 
+    /// Add corrected frequencies from `calibrator` to stored spots.
+    pub fn with_calibrator(mut self, calibrator: Arc<FrequencyCalibrator>) -> Self {
+        self.calibrator = Some(calibrator);
+        self
+    }
+
     /// Store a spot that matched the filter at the given index.
     ///
     /// Handles both per-filter and global limit enforcement with eviction.
@@ -187,7 +226,11 @@ impl SpotStorage {
         }
 
         // Add the new spot
-        let added_size = storage.push(spot);
+        let corrected = self
+            .calibrator
+            .as_ref()
+            .and_then(|c| c.corrected_frequency(&spot));
+        let added_size = storage.push(spot, corrected);
         self.total_size_bytes.fetch_add(added_size, Relaxed);
     }
 
@@ -281,19 +324,25 @@ impl SpotStorage {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::spot::{Mode, SpotType};
+    use crate::spot::{Mode, Speed, SpotType};
     use chrono::NaiveTime;
 
     fn make_spot(dx_call: &str) -> CwSpot {
         CwSpot {
             spotter: "TEST-#".to_string(),
-            frequency_khz: 14025.0,
+            frequency: Frequency::from_khz(14025.0),
             dx_call: dx_call.to_string(),
             mode: Mode::Cw,
-            snr_db: 15,
-            wpm: 20,
+            snr_db: Some(15),
+            speed: Some(Speed::Wpm(20)),
             spot_type: SpotType::Cq,
             time: NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+            comment: None,
+            received_at: None,
+            timestamp: None,
+            dx_dxcc: None,
+            spotter_dxcc: None,
+            source: None,
         }
     }
 
@@ -301,14 +350,45 @@ mod tests {
     fn test_filter_storage_basic() {
         let mut storage = FilterStorage::new("test".to_string(), 3);
 
-        storage.push(make_spot("W1AW"));
+        storage.push(make_spot("W1AW"), None);
         assert_eq!(storage.len(), 1);
 
-        storage.push(make_spot("W2AW"));
-        storage.push(make_spot("W3AW"));
+        storage.push(make_spot("W2AW"), None);
+        storage.push(make_spot("W3AW"), None);
         assert_eq!(storage.len(), 3);
     }
 
+    #[test]
+    fn test_stored_spot_derived_fields() {
+        let mut storage = FilterStorage::new("test".to_string(), 3);
+        storage.push(make_spot("W1AW"), None);
+
+        let stored = &storage.get_spots_since(0)[0];
+        assert_eq!(stored.band, Some(Band::B20m));
+        assert_eq!(stored.segments, vec![Segment::Cw]);
+
+        let json = serde_json::to_value(stored).unwrap();
+        assert_eq!(json["band"], "20m");
+        assert_eq!(json["segments"], serde_json::json!(["cw"]));
+    }
+
+    #[test]
+    fn test_get_station_spots_since() {
+        let mut storage = FilterStorage::new("test".to_string(), 10);
+        for spotter in ["KM3T-#", "EA5WU-#", "KM3T-2-#"] {
+            let mut spot = make_spot("W1AW");
+            spot.spotter = spotter.to_string();
+            storage.push(spot, None);
+        }
+
+        let spots = storage.get_station_spots_since(0, "km3t");
+        let seqs: Vec<_> = spots.iter().map(|s| s.seq).collect();
+        assert_eq!(seqs, vec![1, 3]);
+
+        assert_eq!(storage.get_station_spots_since(1, "KM3T").len(), 1);
+        assert!(storage.get_station_spots_since(0, "K3LR").is_empty());
+    }
+
     #[test]
     fn test_per_filter_limit() {
         let config = StorageConfig {
@@ -363,6 +443,48 @@ mod tests {
         assert!(storage.global_evictions.load(Relaxed) >= 1);
     }
 
+    #[test]
+    fn test_store_corrected_frequency() {
+        use crate::config::CalibrationConfig;
+        use chrono::{DateTime, Utc};
+
+        let calibrator = Arc::new(FrequencyCalibrator::new(&CalibrationConfig {
+            min_samples: 1,
+            ..Default::default()
+        }));
+        let received = "2026-01-09T12:00:30Z".parse::<DateTime<Utc>>().unwrap();
+        for (spotter, freq) in [
+            ("EA5WU-#", 14025.0),
+            ("KM3T-#", 14025.0),
+            ("W3OA-#", 14025.1),
+        ] {
+            let mut spot = make_spot("W1AW").with_received_at(received);
+            spot.spotter = spotter.to_string();
+            spot.frequency = Frequency::from_khz(freq);
+            calibrator.observe(&spot);
+        }
+
+        let storage =
+            SpotStorage::new(&StorageConfig::default(), vec![SpotFilter::default()], None)
+                .with_calibrator(calibrator);
+        let mut spot = make_spot("K1ABC");
+        spot.spotter = "W3OA-#".to_string();
+        spot.frequency = Frequency::from_khz(14030.0);
+        storage.try_store(&spot);
+        storage.try_store(&make_spot("K1ABC"));
+
+        let (_, fs_lock) = &storage.filters[0];
+        let stored = fs_lock.read().unwrap().get_spots_since(0);
+        assert_eq!(
+            stored[0].corrected_frequency_khz,
+            Some(Frequency::from_khz(14029.9))
+        );
+        assert_eq!(stored[1].corrected_frequency_khz, None);
+
+        let json = serde_json::to_value(&stored[1]).unwrap();
+        assert!(json.get("corrected_frequency_khz").is_none());
+    }
+
     #[test]
     fn test_try_store_matches() {
         let config = StorageConfig {
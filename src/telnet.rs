@@ -0,0 +1,620 @@
+//! Byte-level line framing for Telnet sessions.
+//!
+//! The RBN servers speak Telnet, so the raw byte stream can contain IAC
+//! option negotiation, subnegotiation blocks and stray high-bit bytes in
+//! addition to spot text. [`TelnetDecoder`] strips the protocol bytes,
+//! answers option negotiation and splits what is left into lines.
+//! [`TelnetStream`] wraps it around an async reader/writer pair and writes
+//! the negotiation replies back to the server.
+//!
+//! Negotiation follows RFC 854 and is kept minimal: we agree to report a
+//! terminal type (RFC 1091) and window size (RFC 1073) when asked, since
+//! some cluster software waits for them, and politely refuse everything
+//! else, including echo. Requests for a state an option is already in are
+//! not answered, so a chatty server can't start a negotiation loop.
+
+use std::collections::VecDeque;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Default maximum length of a single line, in bytes.
+pub const DEFAULT_MAX_LINE_LENGTH: usize = 1024;
+
+/// Terminal type reported to servers that ask for one.
+pub const DEFAULT_TERMINAL_TYPE: &str = "DUMB";
+
+/// Window size (columns, rows) reported to servers that ask for one.
+pub const DEFAULT_WINDOW_SIZE: (u16, u16) = (80, 24);
+
+/// Longest subnegotiation block we keep; anything beyond is dropped.
+const MAX_SUBNEGOTIATION_LENGTH: usize = 64;
+
+/// Interpret As Command: introduces every Telnet command.
+const IAC: u8 = 255;
+/// Request that the other side disable an option.
+const DONT: u8 = 254;
+/// Request that the other side enable an option.
+const DO: u8 = 253;
+/// Refuse to enable an option.
+const WONT: u8 = 252;
+/// Offer to enable an option.
+const WILL: u8 = 251;
+/// Start of subnegotiation.
+const SB: u8 = 250;
+/// End of subnegotiation.
+const SE: u8 = 240;
+
+/// Terminal type option (RFC 1091).
+const TERMINAL_TYPE: u8 = 24;
+/// Negotiate About Window Size option (RFC 1073).
+const NAWS: u8 = 31;
+/// Terminal type subnegotiation: here is my terminal type.
+const TERMINAL_TYPE_IS: u8 = 0;
+/// Terminal type subnegotiation: send me your terminal type.
+const TERMINAL_TYPE_SEND: u8 = 1;
+
+/// Position of the decoder within the Telnet protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Plain data bytes
+    Data,
+    /// Just saw IAC
+    Iac,
+    /// Saw IAC followed by DO/DONT/WILL/WONT; the option byte is next
+    Negotiate(u8),
+    /// Inside an SB ... IAC SE block
+    Subnegotiation,
+    /// Saw IAC inside a subnegotiation block
+    SubnegotiationIac,
+}
+
+/// Splits a raw Telnet byte stream into text lines and answers option
+/// negotiation.
+///
+/// Bytes are fed in arbitrarily sized chunks; IAC sequences may be split
+/// across chunk boundaries. Lines end at `\n`; `\r` and NUL bytes are
+/// dropped. Lines that are valid UTF-8 are decoded as such, anything else
+/// is decoded as Latin-1 so that a stray high-bit byte never loses the line.
+///
+/// Lines longer than the maximum length are discarded up to their newline
+/// and counted in [`TelnetDecoder::overlong_lines`].
+///
+/// The only options ever enabled are terminal type and window size on our
+/// side; the replies to send back are collected in
+/// [`TelnetDecoder::take_replies`].
+#[derive(Debug)]
+pub struct TelnetDecoder {
+    state: State,
+    line: Vec<u8>,
+    max_line_length: usize,
+    discarding: bool,
+    lines: VecDeque<String>,
+    replies: Vec<u8>,
+    overlong_lines: u64,
+    subnegotiation: Vec<u8>,
+    /// Options enabled on our side
+    enabled: Vec<u8>,
+    terminal_type: String,
+    window_size: (u16, u16),
+}
+
+impl TelnetDecoder {
+    /// Create a decoder that accepts lines of up to `max_line_length` bytes.
+    pub fn new(max_line_length: usize) -> Self {
+        Self {
+            state: State::Data,
+            line: Vec::with_capacity(256),
+            max_line_length,
+            discarding: false,
+            lines: VecDeque::new(),
+            replies: Vec::new(),
+            overlong_lines: 0,
+            subnegotiation: Vec::new(),
+            enabled: Vec::new(),
+            terminal_type: DEFAULT_TERMINAL_TYPE.to_string(),
+            window_size: DEFAULT_WINDOW_SIZE,
+        }
+    }
+
+    /// Set the terminal type reported to the server.
+    pub fn with_terminal_type(mut self, terminal_type: impl Into<String>) -> Self {
+        self.terminal_type = terminal_type.into();
+        self
+    }
+
+    /// Set the window size (columns, rows) reported to the server.
+    pub fn with_window_size(mut self, columns: u16, rows: u16) -> Self {
+        self.window_size = (columns, rows);
+        self
+    }
+
+    /// Feed a chunk of bytes received from the server.
+    pub fn feed(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.state = match self.state {
+                State::Data => {
+                    if byte == IAC {
+                        State::Iac
+                    } else {
+                        self.push_data(byte);
+                        State::Data
+                    }
+                }
+                State::Iac => match byte {
+                    // Escaped 0xFF data byte
+                    IAC => {
+                        self.push_data(IAC);
+                        State::Data
+                    }
+                    DO | DONT | WILL | WONT => State::Negotiate(byte),
+                    SB => {
+                        self.subnegotiation.clear();
+                        State::Subnegotiation
+                    }
+                    // NOP, GA, AYT and friends carry no data
+                    _ => State::Data,
+                },
+                State::Negotiate(command) => {
+                    self.answer(command, byte);
+                    State::Data
+                }
+                State::Subnegotiation => {
+                    if byte == IAC {
+                        State::SubnegotiationIac
+                    } else {
+                        self.push_subnegotiation(byte);
+                        State::Subnegotiation
+                    }
+                }
+                State::SubnegotiationIac => match byte {
+                    SE => {
+                        self.subnegotiate();
+                        State::Data
+                    }
+                    IAC => {
+                        self.push_subnegotiation(IAC);
+                        State::Subnegotiation
+                    }
+                    _ => State::Subnegotiation,
+                },
+            };
+        }
+    }
+
+    /// Take the next complete line, without its line ending.
+    pub fn next_line(&mut self) -> Option<String> {
+        self.lines.pop_front()
+    }
+
+    /// The text received since the last line ending.
+    ///
+    /// Prompts such as "Please enter your call:" are sent without a newline
+    /// and only ever show up here.
+    pub fn pending(&self) -> String {
+        if self.discarding {
+            String::new()
+        } else {
+            decode(self.line.clone())
+        }
+    }
+
+    /// Take the text received since the last line ending, clearing it.
+    pub fn take_pending(&mut self) -> String {
+        let pending = self.pending();
+        self.line.clear();
+        pending
+    }
+
+    /// Take the negotiation replies that should be sent to the server.
+    pub fn take_replies(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.replies)
+    }
+
+    /// Number of lines dropped for exceeding the maximum length.
+    pub fn overlong_lines(&self) -> u64 {
+        self.overlong_lines
+    }
+
+    fn push_data(&mut self, byte: u8) {
+        match byte {
+            b'\n' => {
+                if self.discarding {
+                    self.discarding = false;
+                } else {
+                    self.lines.push_back(decode(std::mem::take(&mut self.line)));
+                }
+            }
+            b'\r' | 0 => {}
+            _ if self.discarding => {}
+            _ if self.line.len() >= self.max_line_length => {
+                self.line.clear();
+                self.discarding = true;
+                self.overlong_lines += 1;
+            }
+            _ => self.line.push(byte),
+        }
+    }
+
+    fn push_subnegotiation(&mut self, byte: u8) {
+        if self.subnegotiation.len() < MAX_SUBNEGOTIATION_LENGTH {
+            self.subnegotiation.push(byte);
+        }
+    }
+
+    /// Answer a DO/DONT/WILL/WONT request.
+    ///
+    /// We only ever enable terminal type and window size on our side, and
+    /// never let the server enable anything on its side. Requests that
+    /// wouldn't change an option's state are not acknowledged.
+    fn answer(&mut self, command: u8, option: u8) {
+        let enabled = self.enabled.contains(&option);
+        match command {
+            DO if enabled => {}
+            DO if matches!(option, TERMINAL_TYPE | NAWS) => {
+                self.enabled.push(option);
+                self.replies.extend_from_slice(&[IAC, WILL, option]);
+                if option == NAWS {
+                    self.send_window_size();
+                }
+            }
+            DO => self.replies.extend_from_slice(&[IAC, WONT, option]),
+            DONT if enabled => {
+                self.enabled.retain(|&o| o != option);
+                self.replies.extend_from_slice(&[IAC, WONT, option]);
+            }
+            WILL => self.replies.extend_from_slice(&[IAC, DONT, option]),
+            // Disabling something that is already off needs no reply
+            _ => {}
+        }
+    }
+
+    /// Handle a complete SB ... IAC SE block.
+    fn subnegotiate(&mut self) {
+        if self.subnegotiation == [TERMINAL_TYPE, TERMINAL_TYPE_SEND]
+            && self.enabled.contains(&TERMINAL_TYPE)
+        {
+            let mut data = vec![TERMINAL_TYPE, TERMINAL_TYPE_IS];
+            data.extend_from_slice(self.terminal_type.as_bytes());
+            self.send_subnegotiation(&data);
+        }
+    }
+
+    /// Report the window size: two bytes each of columns and rows.
+    fn send_window_size(&mut self) {
+        let (columns, rows) = self.window_size;
+        let mut data = vec![NAWS];
+        data.extend_from_slice(&columns.to_be_bytes());
+        data.extend_from_slice(&rows.to_be_bytes());
+        self.send_subnegotiation(&data);
+    }
+
+    fn send_subnegotiation(&mut self, data: &[u8]) {
+        self.replies.extend_from_slice(&[IAC, SB]);
+        for &byte in data {
+            if byte == IAC {
+                self.replies.push(IAC);
+            }
+            self.replies.push(byte);
+        }
+        self.replies.extend_from_slice(&[IAC, SE]);
+    }
+}
+
+impl Default for TelnetDecoder {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_LINE_LENGTH)
+    }
+}
+
+/// Decode line bytes as UTF-8, falling back to Latin-1.
+fn decode(bytes: Vec<u8>) -> String {
+    String::from_utf8(bytes)
+        .unwrap_or_else(|e| e.into_bytes().iter().map(|&b| char::from(b)).collect())
+}
+
+/// A Telnet connection: decodes incoming bytes into lines and answers
+/// option negotiation on the write half.
+pub struct TelnetStream<R, W> {
+    reader: R,
+    writer: W,
+    decoder: TelnetDecoder,
+    buf: Vec<u8>,
+}
+
+impl<R, W> TelnetStream<R, W>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    /// Wrap a reader/writer pair.
+    pub fn new(reader: R, writer: W, max_line_length: usize) -> Self {
+        Self {
+            reader,
+            writer,
+            decoder: TelnetDecoder::new(max_line_length),
+            buf: vec![0; 4096],
+        }
+    }
+
+    /// Read one chunk from the server and decode it.
+    ///
+    /// Any negotiation replies are written before returning. Returns the
+    /// number of bytes read, 0 at end of stream.
+    pub async fn fill(&mut self) -> std::io::Result<usize> {
+        let n = self.reader.read(&mut self.buf).await?;
+        self.decoder.feed(&self.buf[..n]);
+
+        let replies = self.decoder.take_replies();
+        if !replies.is_empty() {
+            self.writer.write_all(&replies).await?;
+            self.writer.flush().await?;
+        }
+
+        Ok(n)
+    }
+
+    /// Read the next complete line.
+    ///
+    /// At end of stream, returns any unterminated trailing text as a final
+    /// line, then `None`.
+    pub async fn read_line(&mut self) -> std::io::Result<Option<String>> {
+        loop {
+            if let Some(line) = self.decoder.next_line() {
+                return Ok(Some(line));
+            }
+            if self.fill().await? == 0 {
+                let rest = self.decoder.take_pending();
+                return Ok((!rest.is_empty()).then_some(rest));
+            }
+        }
+    }
+
+    /// Send a line of text terminated by CRLF.
+    pub async fn send_line(&mut self, line: &str) -> std::io::Result<()> {
+        let mut data = Vec::with_capacity(line.len() + 2);
+        for &byte in line.as_bytes() {
+            if byte == IAC {
+                data.push(IAC);
+            }
+            data.push(byte);
+        }
+        data.extend_from_slice(b"\r\n");
+
+        self.writer.write_all(&data).await?;
+        self.writer.flush().await
+    }
+
+    /// The underlying decoder, for inspecting buffered lines and prompts.
+    pub fn decoder(&mut self) -> &mut TelnetDecoder {
+        &mut self.decoder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ECHO: u8 = 1;
+    const SUPPRESS_GO_AHEAD: u8 = 3;
+
+    fn lines(decoder: &mut TelnetDecoder) -> Vec<String> {
+        std::iter::from_fn(|| decoder.next_line()).collect()
+    }
+
+    #[test]
+    fn test_plain_lines() {
+        let mut decoder = TelnetDecoder::default();
+        decoder.feed(
+            b"DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z\r\n",
+        );
+        decoder.feed(b"second\nthird");
+
+        assert_eq!(
+            lines(&mut decoder),
+            vec![
+                "DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z",
+                "second",
+            ]
+        );
+        assert_eq!(decoder.pending(), "third");
+    }
+
+    #[test]
+    fn test_login_negotiation_is_stripped_and_answered() {
+        // Captured from a DX Spider node: WILL ECHO, WILL SGA, DO TTYPE, DO NAWS
+        let capture = [
+            &[IAC, WILL, ECHO, IAC, WILL, SUPPRESS_GO_AHEAD][..],
+            &[IAC, DO, TERMINAL_TYPE, IAC, DO, NAWS],
+            b"\r\nPlease enter your call: ",
+        ]
+        .concat();
+
+        let mut decoder = TelnetDecoder::default();
+        decoder.feed(&capture);
+
+        assert_eq!(lines(&mut decoder), vec![""]);
+        assert_eq!(decoder.pending(), "Please enter your call: ");
+        assert_eq!(
+            decoder.take_replies(),
+            [
+                &[IAC, DONT, ECHO, IAC, DONT, SUPPRESS_GO_AHEAD][..],
+                &[IAC, WILL, TERMINAL_TYPE, IAC, WILL, NAWS],
+                &[IAC, SB, NAWS, 0, 80, 0, 24, IAC, SE],
+            ]
+            .concat()
+        );
+        assert!(decoder.take_replies().is_empty());
+    }
+
+    #[test]
+    fn test_terminal_type_is_sent_on_request() {
+        let mut decoder = TelnetDecoder::default().with_terminal_type("XTERM");
+        decoder.feed(&[IAC, SB, TERMINAL_TYPE, TERMINAL_TYPE_SEND, IAC, SE]);
+        // Not enabled yet, so the request is ignored
+        assert!(decoder.take_replies().is_empty());
+
+        decoder.feed(&[IAC, DO, TERMINAL_TYPE]);
+        decoder.feed(&[IAC, SB, TERMINAL_TYPE, TERMINAL_TYPE_SEND, IAC, SE]);
+        assert_eq!(
+            decoder.take_replies(),
+            [
+                &[IAC, WILL, TERMINAL_TYPE][..],
+                &[IAC, SB, TERMINAL_TYPE, TERMINAL_TYPE_IS],
+                b"XTERM",
+                &[IAC, SE],
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn test_window_size_escapes_iac() {
+        let mut decoder = TelnetDecoder::default().with_window_size(255, 300);
+        decoder.feed(&[IAC, DO, NAWS]);
+        assert_eq!(
+            decoder.take_replies(),
+            vec![IAC, WILL, NAWS, IAC, SB, NAWS, 0, IAC, IAC, 1, 44, IAC, SE]
+        );
+    }
+
+    #[test]
+    fn test_repeated_requests_are_not_acknowledged() {
+        let mut decoder = TelnetDecoder::default();
+        decoder.feed(&[IAC, DO, TERMINAL_TYPE]);
+        assert_eq!(decoder.take_replies(), vec![IAC, WILL, TERMINAL_TYPE]);
+
+        // Already enabled: answering again could start a loop
+        decoder.feed(&[IAC, DO, TERMINAL_TYPE]);
+        assert!(decoder.take_replies().is_empty());
+
+        decoder.feed(&[IAC, DONT, TERMINAL_TYPE, IAC, DONT, TERMINAL_TYPE]);
+        assert_eq!(decoder.take_replies(), vec![IAC, WONT, TERMINAL_TYPE]);
+    }
+
+    #[test]
+    fn test_disable_requests_are_not_answered() {
+        let mut decoder = TelnetDecoder::default();
+        decoder.feed(&[IAC, WONT, ECHO, IAC, DONT, NAWS]);
+        assert!(decoder.take_replies().is_empty());
+    }
+
+    #[test]
+    fn test_subnegotiation_is_stripped() {
+        let mut decoder = TelnetDecoder::default();
+        let capture = [
+            &b"abc"[..],
+            &[IAC, SB, TERMINAL_TYPE, 1, IAC, IAC, IAC, SE],
+            b"def\r\n",
+        ]
+        .concat();
+        decoder.feed(&capture);
+
+        assert_eq!(lines(&mut decoder), vec!["abcdef"]);
+    }
+
+    #[test]
+    fn test_commands_split_across_chunks() {
+        let mut decoder = TelnetDecoder::default();
+        for chunk in [
+            &b"W6JSV de RELAY"[..],
+            &[IAC],
+            &[DO],
+            &[ECHO],
+            b" >\r",
+            b"\n",
+        ] {
+            decoder.feed(chunk);
+        }
+
+        assert_eq!(lines(&mut decoder), vec!["W6JSV de RELAY >"]);
+        assert_eq!(decoder.take_replies(), vec![IAC, WONT, ECHO]);
+    }
+
+    #[test]
+    fn test_escaped_iac_and_other_commands() {
+        const NOP: u8 = 241;
+        const GA: u8 = 249;
+
+        let mut decoder = TelnetDecoder::default();
+        decoder.feed(&[b'a', IAC, NOP, b'b', IAC, GA, b'c', IAC, IAC, b'\n']);
+
+        assert_eq!(lines(&mut decoder), vec!["abc\u{ff}"]);
+    }
+
+    #[test]
+    fn test_invalid_utf8_falls_back_to_latin1() {
+        let mut decoder = TelnetDecoder::default();
+        decoder.feed(b"DX de DL1ABC:  14025.0  JA1XYZ  gr\xfc\xdfe  1234Z\r\n");
+        decoder.feed("73 de Jürgen\r\n".as_bytes());
+
+        assert_eq!(
+            lines(&mut decoder),
+            vec![
+                "DX de DL1ABC:  14025.0  JA1XYZ  grüße  1234Z",
+                "73 de Jürgen"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overlong_line_is_discarded() {
+        let mut decoder = TelnetDecoder::new(16);
+        decoder.feed(b"short\r\n");
+        decoder.feed(&[b'x'; 40]);
+        decoder.feed(b"\r\nafter\r\n");
+
+        assert_eq!(lines(&mut decoder), vec!["short", "after"]);
+        assert_eq!(decoder.overlong_lines(), 1);
+    }
+
+    #[test]
+    fn test_line_at_max_length_is_kept() {
+        let mut decoder = TelnetDecoder::new(5);
+        decoder.feed(b"12345\r\n");
+
+        assert_eq!(lines(&mut decoder), vec!["12345"]);
+        assert_eq!(decoder.overlong_lines(), 0);
+    }
+
+    #[test]
+    fn test_take_pending() {
+        let mut decoder = TelnetDecoder::default();
+        decoder.feed(b"Please enter your call: ");
+
+        assert_eq!(decoder.take_pending(), "Please enter your call: ");
+        decoder.feed(b"Hello\r\n");
+        assert_eq!(lines(&mut decoder), vec!["Hello"]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_reads_lines_and_answers_negotiation() {
+        let capture = [
+            &[IAC, DO, NAWS][..],
+            b"DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z\r\n",
+            b"trailing",
+        ]
+        .concat();
+        let mut written = Vec::new();
+        let mut stream = TelnetStream::new(&capture[..], &mut written, DEFAULT_MAX_LINE_LENGTH);
+
+        assert_eq!(
+            stream.read_line().await.unwrap().as_deref(),
+            Some("DX de EA5WU-#:    7018.3  RW1M           CW    19 dB  18 WPM  CQ      2259Z")
+        );
+        assert_eq!(
+            stream.read_line().await.unwrap().as_deref(),
+            Some("trailing")
+        );
+        assert_eq!(stream.read_line().await.unwrap(), None);
+
+        stream.send_line("W6JSV").await.unwrap();
+        drop(stream);
+        assert_eq!(
+            written,
+            [
+                &[IAC, WILL, NAWS, IAC, SB, NAWS, 0, 80, 0, 24, IAC, SE][..],
+                b"W6JSV\r\n"
+            ]
+            .concat()
+        );
+    }
+}
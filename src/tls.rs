@@ -0,0 +1,372 @@
+//! TLS transport for cluster connections.
+//!
+//! Some DX cluster nodes offer telnet wrapped in TLS. [`TlsConfig`] holds
+//! the per-source settings and builds a rustls connector from them:
+//!
+//! - the server certificate is checked against the Mozilla root store, or
+//!   only against the CAs in `ca_file` if one is given
+//! - `server_name` overrides the name sent via SNI and checked in the
+//!   certificate (by default the host being connected to)
+//! - with `pin_sha256`, the server certificate must instead match one of the
+//!   pinned SHA-256 fingerprints, which also works for self-signed certs
+
+use anyhow::{Context, Result};
+use ring::digest;
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio_rustls::TlsConnector;
+use tokio_rustls::rustls::client::danger::{
+    HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
+};
+use tokio_rustls::rustls::crypto::{self, CryptoProvider};
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use tokio_rustls::rustls::{
+    ClientConfig, DigitallySignedStruct, Error, RootCertStore, SignatureScheme,
+};
+
+/// TLS settings of a source. Present (even if empty) means TLS is used.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct TlsConfig {
+    /// Name to send via SNI and verify in the certificate, if not the host.
+    pub server_name: Option<String>,
+
+    /// PEM file of CA certificates to trust instead of the built-in roots.
+    pub ca_file: Option<PathBuf>,
+
+    /// SHA-256 fingerprints of accepted server certificates, in hex (colons
+    /// optional). When set, the CA chain and name are not checked.
+    pub pin_sha256: Vec<String>,
+}
+
+impl TlsConfig {
+    /// Validate the settings.
+    ///
+    /// Returns an error if the server name is invalid or a pin is not a
+    /// SHA-256 fingerprint.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(ref name) = self.server_name {
+            ServerName::try_from(name.as_str())
+                .map_err(|_| format!("Invalid server_name '{}'", name))?;
+        }
+        for pin in &self.pin_sha256 {
+            parse_fingerprint(pin)?;
+        }
+        Ok(())
+    }
+
+    /// Build a connector from these settings.
+    ///
+    /// Fails if the CA file can't be read or holds no usable certificates.
+    pub fn connector(&self) -> Result<TlsConnector> {
+        let provider = Arc::new(crypto::ring::default_provider());
+        let builder = ClientConfig::builder_with_provider(Arc::clone(&provider))
+            .with_safe_default_protocol_versions()
+            .context("No usable TLS protocol versions")?;
+
+        let config = if !self.pin_sha256.is_empty() {
+            let pins = self
+                .pin_sha256
+                .iter()
+                .map(|pin| parse_fingerprint(pin))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(anyhow::Error::msg)?;
+            builder
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(PinnedCertVerifier { pins, provider }))
+                .with_no_client_auth()
+        } else {
+            builder
+                .with_root_certificates(self.root_store()?)
+                .with_no_client_auth()
+        };
+
+        Ok(TlsConnector::from(Arc::new(config)))
+    }
+
+    /// The name to request and verify when connecting to `host`.
+    pub fn server_name(&self, host: &str) -> Result<ServerName<'static>> {
+        let name = self.server_name.as_deref().unwrap_or(host);
+        ServerName::try_from(name.to_string())
+            .with_context(|| format!("Invalid TLS server name '{}'", name))
+    }
+
+    /// The CAs in `ca_file`, or the built-in Mozilla roots.
+    fn root_store(&self) -> Result<RootCertStore> {
+        let Some(ref path) = self.ca_file else {
+            return Ok(RootCertStore {
+                roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+            });
+        };
+
+        let mut roots = RootCertStore::empty();
+        for cert in CertificateDer::pem_file_iter(path)
+            .with_context(|| format!("Failed to read CA file {}", path.display()))?
+        {
+            let cert =
+                cert.with_context(|| format!("Invalid certificate in {}", path.display()))?;
+            roots
+                .add(cert)
+                .with_context(|| format!("Unusable CA certificate in {}", path.display()))?;
+        }
+        if roots.is_empty() {
+            anyhow::bail!("No certificates found in CA file {}", path.display());
+        }
+        Ok(roots)
+    }
+}
+
+/// SHA-256 fingerprint of a DER certificate, as lowercase hex with colons.
+pub fn fingerprint(cert: &[u8]) -> String {
+    let digest = digest::digest(&digest::SHA256, cert);
+    digest
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Parse a hex SHA-256 fingerprint, ignoring colons, spaces and case.
+fn parse_fingerprint(pin: &str) -> Result<[u8; 32], String> {
+    let hex: String = pin
+        .chars()
+        .filter(|c| *c != ':' && !c.is_whitespace())
+        .collect();
+    let invalid = || format!("Invalid pin_sha256 '{}': expected 64 hex digits", pin);
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(invalid());
+    }
+    let mut bytes = [0u8; 32];
+    for (byte, digits) in bytes.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        let digits = std::str::from_utf8(digits).map_err(|_| invalid())?;
+        *byte = u8::from_str_radix(digits, 16).map_err(|_| invalid())?;
+    }
+    Ok(bytes)
+}
+
+/// Accepts a server certificate whose SHA-256 fingerprint is pinned.
+///
+/// Handshake signatures are still verified, so the server must hold the
+/// pinned certificate's private key.
+#[derive(Debug)]
+struct PinnedCertVerifier {
+    pins: Vec<[u8; 32]>,
+    provider: Arc<CryptoProvider>,
+}
+
+impl ServerCertVerifier for PinnedCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, Error> {
+        let digest = digest::digest(&digest::SHA256, end_entity);
+        if self.pins.iter().any(|pin| pin[..] == *digest.as_ref()) {
+            Ok(ServerCertVerified::assertion())
+        } else {
+            Err(Error::General(format!(
+                "server certificate {} is not pinned",
+                fingerprint(end_entity)
+            )))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio_rustls::TlsAcceptor;
+    use tokio_rustls::rustls::ServerConfig;
+    use tokio_rustls::rustls::pki_types::{PrivateKeyDer, PrivatePkcs8KeyDer};
+
+    /// A self-signed certificate for "localhost", as PEM and DER, and a TLS
+    /// acceptor serving it.
+    pub(crate) fn self_signed() -> (String, CertificateDer<'static>, TlsAcceptor) {
+        let rcgen::CertifiedKey { cert, key_pair } =
+            rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key_pair.serialize_der()));
+        let config =
+            ServerConfig::builder_with_provider(Arc::new(crypto::ring::default_provider()))
+                .with_safe_default_protocol_versions()
+                .unwrap()
+                .with_no_client_auth()
+                .with_single_cert(vec![cert.der().clone()], key)
+                .unwrap();
+        (
+            cert.pem(),
+            cert.der().clone(),
+            TlsAcceptor::from(Arc::new(config)),
+        )
+    }
+
+    /// Write `contents` to a file in the temp directory unique to this test.
+    pub(crate) fn temp_file(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("rbn-parser-{}-{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    /// Serve one TLS connection that sends a greeting, then try to read it
+    /// through a client built from `config`.
+    async fn handshake(acceptor: TlsAcceptor, config: &TlsConfig) -> Result<String> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            if let Ok(mut tls) = acceptor.accept(socket).await {
+                let _ = tls.write_all(b"hello").await;
+                let _ = tls.shutdown().await;
+            }
+        });
+
+        let result = async {
+            let tcp = tokio::net::TcpStream::connect(("127.0.0.1", port)).await?;
+            let mut tls = config
+                .connector()?
+                .connect(config.server_name("localhost")?, tcp)
+                .await?;
+            let mut greeting = String::new();
+            tls.read_to_string(&mut greeting).await?;
+            Ok(greeting)
+        }
+        .await;
+        server.await.unwrap();
+        result
+    }
+
+    #[tokio::test]
+    async fn test_custom_ca_file() {
+        let (pem, _, acceptor) = self_signed();
+        let config = TlsConfig {
+            ca_file: Some(temp_file("ca.pem", &pem)),
+            ..Default::default()
+        };
+        assert_eq!(handshake(acceptor, &config).await.unwrap(), "hello");
+
+        // The name sent via SNI must match the certificate
+        let (pem, _, acceptor) = self_signed();
+        let config = TlsConfig {
+            ca_file: Some(temp_file("ca-sni.pem", &pem)),
+            server_name: Some("dxc.example.net".to_string()),
+            ..Default::default()
+        };
+        let err = handshake(acceptor, &config).await.unwrap_err();
+        assert!(
+            format!("{:#}", err).contains("not valid for name"),
+            "{:#}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn test_builtin_roots_reject_self_signed() {
+        let (_, _, acceptor) = self_signed();
+        let err = handshake(acceptor, &TlsConfig::default())
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("UnknownIssuer"), "{:#}", err);
+    }
+
+    #[tokio::test]
+    async fn test_certificate_pinning() {
+        let (_, der, acceptor) = self_signed();
+        let config = TlsConfig {
+            // Pins need neither a CA nor a matching name
+            server_name: Some("dxc.example.net".to_string()),
+            pin_sha256: vec![fingerprint(&der).to_uppercase()],
+            ..Default::default()
+        };
+        assert_eq!(handshake(acceptor, &config).await.unwrap(), "hello");
+
+        let (_, _, acceptor) = self_signed();
+        let err = handshake(acceptor, &config).await.unwrap_err();
+        assert!(format!("{:#}", err).contains("is not pinned"), "{:#}", err);
+    }
+
+    #[test]
+    fn test_validate() {
+        let pin = "ab".repeat(32);
+        assert_eq!(parse_fingerprint(&pin), Ok([0xab; 32]));
+        assert_eq!(
+            parse_fingerprint(&fingerprint(b"certificate")),
+            Ok(digest::digest(&digest::SHA256, b"certificate")
+                .as_ref()
+                .try_into()
+                .unwrap())
+        );
+
+        assert!(TlsConfig::default().validate().is_ok());
+        let config = TlsConfig {
+            pin_sha256: vec![pin[..62].to_string()],
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+        let config = TlsConfig {
+            pin_sha256: vec![format!("zz{}", &pin[2..])],
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+        let config = TlsConfig {
+            server_name: Some("not a name".to_string()),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_missing_ca_file() {
+        let config = TlsConfig {
+            ca_file: Some(PathBuf::from("/nonexistent/ca.pem")),
+            ..Default::default()
+        };
+        assert!(config.connector().is_err());
+
+        let config = TlsConfig {
+            ca_file: Some(temp_file("empty-ca.pem", "")),
+            ..Default::default()
+        };
+        let err = config.connector().err().unwrap().to_string();
+        assert!(err.contains("No certificates found"), "{}", err);
+    }
+}